@@ -0,0 +1,138 @@
+//! Turns JSON documents into type definitions for several languages, as the `json_parser` binary does.
+//!
+//! A document is split into tokens by the [Lexer], read into a tree of fields by the [Tokenizer] and
+//! rendered by the [Transformer] according to a [TransformConfig], such as [RUST_DEFINITION].
+//!
+//! ```
+//! use json_parser::{JsonArrayType, JsonRoot, Lexer, ParseOptions, Tokenizer};
+//!
+//! let tokens = Lexer::new("[1, 2, 3]").start_lex();
+//! let root = Tokenizer::new(tokens, ParseOptions::default()).start_tokenizer()?;
+//! assert_eq!(root, JsonRoot::Array(JsonArrayType::Int));
+//! # Ok::<(), json_parser::TokenizerError>(())
+//! ```
+#![allow(special_module_name)]
+
+// Named `lib` before the crate had a library target, its modules refer to each other through `crate::lib`.
+#[path = "lib/mod.rs"]
+mod lib;
+
+use anyhow::bail;
+
+pub use crate::lib::{Config, RunStatus, run};
+pub use crate::lib::inference::{InferredType, PrefixInference, TypeInference};
+pub use crate::lib::model::parse_options::ParseOptions;
+pub use crate::lib::model::transform_config::{CaseType, DART_DEFINITION, EmptyObject, JAVA_DEFINITION, KOTLIN_DEFINITION, KOTLIN_SERIALIZABLE_DEFINITION, PHP_DEFINITION, RUST_DEFINITION, TYPESCRIPT_DEFINITION, TransformConfig, TransformOptions, Underscores};
+pub use crate::lib::model::tree::{JsonArrayType, JsonRoot, JsonTree, Samples};
+pub use crate::lib::parser::{parse_value, parse_value_with};
+pub use crate::lib::parser::lexer::Lexer;
+pub use crate::lib::parser::tokenizer::{Tokenizer, TokenizerError};
+pub use crate::lib::transformer::{TAB_WIDTH, Transformer, TransformerError};
+
+/// Usage of the binary, printed by `--help` and along with the errors.
+pub const HELP_MESSAGE: &str = r#"Usage: json-parser --definition="definition" [options] file_name...
+Available definitions: rust, java, kotlin, kotlin-serializable, dart, typescript, php.
+You can also provide the path or, when built with the remote-definition feature, the http(s) URL
+of a custom definition in a .toml file, see --help-definition. Several definitions separated by commas,
+such as --definition=rust,typescript, are all generated from the same input into --out-dir.
+Several files can be given, their root objects are merged and fields missing from some become optional.
+A file holding a single value instead of an object or an array gets an alias of its type, such as type Root = i32;.
+Because the type of a value needs to be inferred, null values are not supported, except for nulls mixed with
+other values in an array (see --null-elements) or held by fields (see --null-fields). Arrays only seen empty
+hold the definition's unknown type (see --empty-array-type). Duplicate or empty field names are reported as errors,
+the types are still generated but the exit status is 1.
+Options:
+    --acronym=ID                upper case word kept together when converting names, can be repeated.
+    --allow-dead-code           mark every type with the definition's attribute silencing unused field warnings (rust).
+    --allow-nan                 read the Infinity, -Infinity and NaN literals written by some encoders as floats.
+    --ascii-only                transliterate the non-ASCII letters of names to ASCII, "café" becoming cafe, keeping the original key.
+    --banner                    place the definition's banner, a comment against editing by default, at the top of the output.
+    --base64-bytes              map long base64 looking string fields to the definition's bytes type.
+    --bool-strings              map string fields only holding "yes"/"no", "true"/"false", "on"/"off" or "y"/"n" to booleans read
+                                by the definition's helper (rust), other definitions and array elements keeping strings.
+    --bool-type=bool            type of boolean values, overriding the definition's.
+    --builder                   emit public fields and the definition's builder attribute, for DTOs built field by field (rust).
+    --concatenated              merge several root objects written back to back, fields missing from some become optional.
+    --constant-enums            map string fields always holding the same value to a single-variant enum.
+    --decimal-comma             read a comma between two digits of an object value as a decimal point, {"x":1,5} being 1.5.
+    --decimal-strings           map string fields holding decimal numbers such as "19.99" or "$19.99" to the definition's decimal type (rust).
+    --declaration-attribute=Line
+                                line placed between the derives and the declaration of every struct, can be repeated (rust).
+    --deny-unknown-fields       mark every struct with the definition's attribute failing deserialization on unknown keys (rust).
+    --diff=existing.rs          compare the generated types with a file instead of printing them, exiting with 1 if they differ.
+    --discriminator=type        field telling apart the kinds of objects of an array, each kind gets its own type and a wrapper.
+    --docs-from=schema.json     place the descriptions of the properties of a JSON Schema above the fields generated from the documents.
+    --empty-array-type=Value    type of the elements of arrays only seen empty, the definition's unknown type by default.
+    --empty-object-as=map       give objects without fields the definition's map of any value (map), a unit type (unit, rust)
+                                or their own empty type (struct, default).
+    --empty-string-as-null      make string fields that are empty in every sample optional, as if they were null.
+    --fallback                  give arrays mixing element types the definition's unknown type instead of failing.
+    --field-sources             note above every field the input files it appeared in, when merging several files.
+    --file-header=Line          text placed at the top of the output, or of every file with --out-dir.
+    --fixed-arrays              give arrays always holding the same number of elements the definition's fixed-size array type (rust).
+    --flatten                   inline the fields of nested objects into their parent, prefixed by the object's name.
+    --float-type=f64            type of floating point values, overriding the definition's.
+    --format-with=rustfmt       pipe the output through a formatter reading stdin, left unformatted if it can't be run.
+    --int-type=i64              type of integer values, overriding the definition's.
+    --integer-maps              map objects whose keys are all integers, not 0, 1, 2..., to the definition's map type keyed by the int type.
+    --item-name=Name            name of the elements of a root array, the root name followed by Item by default.
+    --list-keys                 print every field name of the input, sorted and without duplicates, instead of the types.
+    --max-line-width=80         wrap constructor arguments one per line when the constructor is wider, tabs counting as 4 columns.
+    --max-types=N               generate at most N types, further objects get the definition's unknown type.
+    --modules                   declare every nested type inside a module named after it, fields referencing it by path (rust).
+    --nested-types              declare the types needed by an object inside it (java, kotlin, kotlin-serializable).
+    --no-annotations            leave out the definition's annotation above fields whose name was converted, keeping the converted name.
+    --null-elements             allow nulls among the values of an array, making its element type optional.
+    --null-fields               allow null field values, making the field optional with the type of its other values.
+    --out-dir=dir               write every type to its own file in dir (java, kotlin, kotlin-serializable, php), or all the types
+                                to a file named after the root for the other definitions, root.rs for rust.
+    --output=dir                same as --out-dir.
+    --output-file=file          write the types to file instead of printing them, as they are generated, the root type last.
+    --parse-helper              emit the definition's function parsing the root object from a JSON string after it (rust).
+    --path-type=a.b[].c=Type    type of the field at a path, array elements being marked by []. Can be repeated.
+    --print-diff                print the lines that differ from the --diff file.
+    --readonly                  mark fields with the definition's readonly modifier (typescript, php).
+    --root-name=Name            name of the root type, Root by default.
+    --schema                    read the file as a JSON Schema, properties missing from required become optional.
+    --shallow-arrays            skip the contents of arrays, giving them the definition's unknown element type.
+    --sort-types                order the types so that every type comes after the types it references.
+    --sort-types-by-name        order the types alphabetically by name, after the types they reference with --sort-types.
+    --string-prefix=usr_=Type   type of string fields whose values all start with a prefix. Can be repeated.
+    --string-type=String        type of string values, overriding the definition's.
+    --tuples                    give arrays holding elements of different types, always in the same positions, the definition's tuple type (rust, typescript).
+    --type-attribute=Line       line placed above every generated type, can be repeated. The Eq, Ord and Hash derives
+                                of this and --declaration-attribute are left out of the types holding a float.
+    --underscores=strip         remove the underscores names start or end with (strip) or keep them around the converted name (preserve, default).
+    --unknown-type=Value        type of values whose type can't be inferred, overriding the definition's.
+    --value-conversions         emit a conversion from a dynamic value after every struct, for definitions with one (rust).
+    --visibility=pub            visibility of the generated types, for definitions with a {visibility} placeholder."#;
+
+/// Parses a JSON document whose root is an object, chaining the [Lexer] and the [Tokenizer].
+/// Documents holding an array or a single value are read by [parse_value].
+/// # Arguments
+/// * `json` JSON document.
+/// # Returns
+/// Fields of the root object.
+/// # Examples
+/// ```
+/// use json_parser::{JsonRoot, JsonTree, RUST_DEFINITION, Transformer};
+///
+/// let fields = json_parser::parse("{\"id\": 1, \"tags\": [\"a\"]}")?;
+/// assert_eq!(fields[0], JsonTree::Int("id".to_owned()));
+///
+/// let transformer = Transformer::new(RUST_DEFINITION, JsonRoot::Object(fields), None)?;
+/// assert_eq!(transformer.start_transform(), vec![vec![
+///     "#[derive(Serialize, Deserialize, Debug)]",
+///     "struct Root {",
+///     "\tid: i32,",
+///     "\ttags: Vec<String>,",
+///     "}",
+/// ]]);
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+pub fn parse(json: &str) -> anyhow::Result<Vec<JsonTree>> {
+    match parse_value(json)? {
+        JsonRoot::Object(fields) => Ok(fields),
+        _ => bail!("the root of the document is not an object, use parse_value to read any root"),
+    }
+}
@@ -1,4 +1,4 @@
-use crate::lib::model::transform_config::CaseType;
+use crate::lib::model::transform_config::{CaseType, Underscores};
 
 
 /// Converts between different case types.
@@ -10,31 +10,25 @@ use crate::lib::model::transform_config::CaseType;
 pub fn convert_case(str: &str, case_type: &CaseType) -> String {
     let mut result = str.to_owned();
 
+    if case_type == &CaseType::Preserve || str.is_empty() {
+        return result;
+    }
+
+    match case_type {
+        CaseType::SnakeCase => return snake_case(str),
+        CaseType::ScreamingSnakeCase => return snake_case(str).to_ascii_uppercase(),
+        _ => (),
+    }
+
     if case_type == &CaseType::UpperCamelCase {
         result[0..=0].make_ascii_uppercase();
     }
 
     for (i, char) in str.chars().enumerate() {
         match char {
-            'A'..='Z' => {
-                match case_type {
-                    CaseType::SnakeCase => {
-                        if i != 0 {
-                            result.insert(i, '_');
-                            result[i + 1..=i + 1].make_ascii_lowercase();
-                        } else {
-                            result[i..=i].make_ascii_lowercase();
-                        }
-                    },
-                    _ => {}
-                }
-            },
             '_' | '-' => {
                 match case_type {
-                    CaseType::SnakeCase => {
-                        result = result.replace('-', "_");
-                        return result;
-                    }
+                    CaseType::SnakeCase | CaseType::ScreamingSnakeCase | CaseType::Preserve => {}
                     CaseType::CamelCase | CaseType::UpperCamelCase  => {
                         if i != 0 {
                             // Absolutely ugly way of ignoring the first char of the string (in case it is a '_').
@@ -53,9 +47,183 @@ pub fn convert_case(str: &str, case_type: &CaseType) -> String {
     result
 }
 
+/// Converts a name to snake case. A run of upper case letters is a single word, an acronym, whose last
+/// letter starts the next word when followed by a lower case letter: `parseHTTPResponse` becomes
+/// `parse_http_response`. Dashes become underscores.
+fn snake_case(str: &str) -> String {
+    let chars: Vec<char> = str.chars().collect();
+    let mut result = String::with_capacity(str.len() + 4);
+
+    for (i, &char) in chars.iter().enumerate() {
+        if char == '-' {
+            result.push('_');
+            continue;
+        }
+
+        if char.is_ascii_uppercase() && i != 0 {
+            let previous = chars[i - 1];
+            let ends_acronym = previous.is_ascii_uppercase() && chars.get(i + 1).is_some_and(|next| next.is_ascii_lowercase());
+            if previous.is_ascii_lowercase() || previous.is_ascii_digit() || ends_acronym {
+                result.push('_');
+            }
+        }
+        result.push(char.to_ascii_lowercase());
+    }
+
+    result
+}
+
+/// Converts between different case types, treating each of `acronyms` as a single word.
+/// # Arguments
+/// * `str` string to convert
+/// * `case_type` case type to convert to. [CaseType]
+/// * `acronyms` upper case words, such as `ID` or `URL`.
+/// # Returns
+/// String in `case_type` case
+pub fn convert_case_with_acronyms(str: &str, case_type: &CaseType, acronyms: &[String]) -> String {
+    if case_type == &CaseType::Preserve {
+        return str.to_owned();
+    }
+
+    convert_case(&normalize_acronyms(str, acronyms), case_type)
+}
+
+/// Converts a name like [convert_case_with_acronyms], its leading and trailing underscores being
+/// kept or removed according to `underscores` instead of being converted.
+/// # Arguments
+/// * `str` name to convert
+/// * `case_type` case type to convert to. [CaseType]
+/// * `acronyms` upper case words, such as `ID` or `URL`.
+/// * `underscores` handling of the underscores around the name. [Underscores]
+/// # Returns
+/// Name in `case_type` case. Names made only of underscores are returned unchanged.
+pub fn convert_name(str: &str, case_type: &CaseType, acronyms: &[String], underscores: &Underscores) -> String {
+    let core = str.trim_matches('_');
+    if core.is_empty() {
+        return str.to_owned();
+    }
+
+    let converted = convert_case_with_acronyms(core, case_type, acronyms);
+    match underscores {
+        Underscores::Preserve => {
+            let leading = str.len() - str.trim_start_matches('_').len();
+            let trailing = str.len() - str.trim_end_matches('_').len();
+            format!("{}{}{}", &str[..leading], converted, &str[str.len() - trailing..])
+        }
+        Underscores::Strip => converted,
+    }
+}
+
+/// Rewrites the non-ASCII letters of a name as the nearest ASCII letters, dropping their diacritics,
+/// so `café` becomes `cafe` and `Straße` becomes `Strasse`. Other non-ASCII characters are removed.
+/// # Arguments
+/// * `str` name to convert
+/// # Returns
+/// Name made only of ASCII characters.
+pub fn transliterate(str: &str) -> String {
+    let mut result = String::with_capacity(str.len());
+
+    for char in str.chars() {
+        if char.is_ascii() {
+            result.push(char);
+            continue;
+        }
+
+        let lowercase = char.to_lowercase().next().unwrap_or(char);
+        let ascii = match lowercase {
+            'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' | 'ā' | 'ă' | 'ą' => "a",
+            'ç' | 'ć' | 'ĉ' | 'ċ' | 'č' => "c",
+            'ď' | 'đ' | 'ð' => "d",
+            'è' | 'é' | 'ê' | 'ë' | 'ē' | 'ĕ' | 'ė' | 'ę' | 'ě' => "e",
+            'ĝ' | 'ğ' | 'ġ' | 'ģ' => "g",
+            'ĥ' | 'ħ' => "h",
+            'ì' | 'í' | 'î' | 'ï' | 'ĩ' | 'ī' | 'ĭ' | 'į' | 'ı' => "i",
+            'ĵ' => "j",
+            'ķ' => "k",
+            'ĺ' | 'ļ' | 'ľ' | 'ŀ' | 'ł' => "l",
+            'ñ' | 'ń' | 'ņ' | 'ň' => "n",
+            'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' | 'ō' | 'ŏ' | 'ő' => "o",
+            'ŕ' | 'ŗ' | 'ř' => "r",
+            'ś' | 'ŝ' | 'ş' | 'š' | 'ș' => "s",
+            'ţ' | 'ť' | 'ŧ' | 'ț' => "t",
+            'ù' | 'ú' | 'û' | 'ü' | 'ũ' | 'ū' | 'ŭ' | 'ů' | 'ű' | 'ų' => "u",
+            'ŵ' => "w",
+            'ý' | 'ÿ' | 'ŷ' => "y",
+            'ź' | 'ż' | 'ž' => "z",
+            'ß' => "ss",
+            'æ' => "ae",
+            'œ' => "oe",
+            'þ' => "th",
+            _ => continue,
+        };
+
+        if char.is_uppercase() {
+            result.push_str(&ascii[..1].to_ascii_uppercase());
+            result.push_str(&ascii[1..]);
+        } else {
+            result.push_str(ascii);
+        }
+    }
+
+    result
+}
+
+/// Rewrites the acronyms found in `str` as capitalized words, so `userID` becomes `userId`.
+/// An acronym followed by a lower case letter is part of another word and is kept.
+fn normalize_acronyms(str: &str, acronyms: &[String]) -> String {
+    let mut result = String::with_capacity(str.len());
+    let mut rest = str;
+
+    'rest: while let Some(char) = rest.chars().next() {
+        for acronym in acronyms.iter().map(|acronym| acronym.to_ascii_uppercase()) {
+            if acronym.is_empty() || !rest.starts_with(&acronym) {
+                continue;
+            }
+
+            let after = &rest[acronym.len()..];
+            if !after.starts_with(|char: char| char.is_ascii_lowercase()) {
+                result.push_str(&acronym[..1]);
+                result.push_str(&acronym[1..].to_ascii_lowercase());
+                rest = after;
+                continue 'rest;
+            }
+        }
+
+        result.push(char);
+        rest = &rest[char.len_utf8()..];
+    }
+
+    result
+}
+
+/// Turns the last word of a plural name into its singular, used to name the elements of an array.
+/// Only regular english plurals are handled, other names are returned unchanged.
+/// # Arguments
+/// * `str` name to convert, in any case.
+/// # Returns
+/// Singular name, keeping the case of `str`.
+pub fn singularize(str: &str) -> String {
+    let lowercase = str.to_ascii_lowercase();
+
+    let remove = if lowercase.ends_with("ies") && str.len() > 3 {
+        let mut result = str[..str.len() - 3].to_owned();
+        result.push(if str.ends_with("IES") { 'Y' } else { 'y' });
+        return result;
+    } else if ["sses", "xes", "ches", "shes", "zzes"].iter().any(|suffix| lowercase.ends_with(suffix)) {
+        2
+    } else if lowercase.ends_with('s') && !["ss", "us", "is"].iter().any(|suffix| lowercase.ends_with(suffix)) && str.len() > 1 {
+        1
+    } else {
+        0
+    };
+
+    str[..str.len() - remove].to_owned()
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::lib::case::{CaseType, convert_case};
+    use crate::lib::case::{CaseType, convert_case, convert_case_with_acronyms, convert_name, singularize, transliterate};
+    use crate::lib::model::transform_config::Underscores;
 
     #[test]
     fn camel_to_snake() {
@@ -93,6 +261,53 @@ mod tests {
         assert_eq!(result, expected_result);
     }
 
+    #[test]
+    fn empty_name() {
+        assert_eq!(convert_case("", &CaseType::UpperCamelCase), "");
+        assert_eq!(convert_case("", &CaseType::SnakeCase), "");
+    }
+
+    #[test]
+    fn acronyms_to_snake() {
+        assert_eq!(convert_case("userID", &CaseType::SnakeCase), "user_id");
+        assert_eq!(convert_case("HTTPServer", &CaseType::SnakeCase), "http_server");
+        assert_eq!(convert_case("APIKey", &CaseType::SnakeCase), "api_key");
+        assert_eq!(convert_case("parseHTTPResponse", &CaseType::SnakeCase), "parse_http_response");
+        assert_eq!(convert_case("field1Name", &CaseType::SnakeCase), "field1_name");
+        assert_eq!(convert_case("user_id", &CaseType::CamelCase), "userId");
+        assert_eq!(convert_case("APIKey", &CaseType::ScreamingSnakeCase), "API_KEY");
+    }
+
+    #[test]
+    fn to_screaming_snake() {
+        assert_eq!(convert_case("fieldName", &CaseType::ScreamingSnakeCase), "FIELD_NAME");
+        assert_eq!(convert_case("FieldName", &CaseType::ScreamingSnakeCase), "FIELD_NAME");
+        assert_eq!(convert_case("field_name", &CaseType::ScreamingSnakeCase), "FIELD_NAME");
+        assert_eq!(convert_case("field-name", &CaseType::ScreamingSnakeCase), "FIELD_NAME");
+        assert_eq!(convert_case("hoLa", &CaseType::ScreamingSnakeCase), "HO_LA");
+        assert_eq!(convert_case("ho-la", &CaseType::ScreamingSnakeCase), "HO_LA");
+        assert_eq!(convert_case("_ho_la", &CaseType::ScreamingSnakeCase), "_HO_LA");
+        assert_eq!(convert_case_with_acronyms("userID", &CaseType::ScreamingSnakeCase, &[String::from("ID")]), "USER_ID");
+    }
+
+    #[test]
+    fn ascii_names() {
+        assert_eq!(transliterate("café"), "cafe");
+        assert_eq!(transliterate("Straße"), "Strasse");
+        assert_eq!(transliterate("Ñandú_Æble"), "Nandu_Aeble");
+        assert_eq!(transliterate("price€"), "price");
+    }
+
+    #[test]
+    fn surrounding_underscores() {
+        assert_eq!(convert_name("_internal_id", &CaseType::UpperCamelCase, &[], &Underscores::Preserve), "_InternalId");
+        assert_eq!(convert_name("_internal_id", &CaseType::UpperCamelCase, &[], &Underscores::Strip), "InternalId");
+        assert_eq!(convert_name("_internal", &CaseType::CamelCase, &[], &Underscores::Strip), "internal");
+        assert_eq!(convert_name("type_", &CaseType::CamelCase, &[], &Underscores::Preserve), "type_");
+        assert_eq!(convert_name("__userId__", &CaseType::SnakeCase, &[], &Underscores::Preserve), "__user_id__");
+        assert_eq!(convert_name("_", &CaseType::CamelCase, &[], &Underscores::Strip), "_");
+    }
+
     #[test]
     fn kebab_to_camel() {
         let str = "ho-la";
@@ -119,4 +334,43 @@ mod tests {
 
         assert_eq!(result, expected_result);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn multiple_camel_to_snake() {
+        let str = "hoLaEh";
+        let expected_result = String::from("ho_la_eh");
+        let result = convert_case(str, &CaseType::SnakeCase);
+
+        assert_eq!(result, expected_result);
+    }
+
+    #[test]
+    fn acronyms() {
+        let acronyms = vec!["ID".to_owned(), "URL".to_owned(), "API".to_owned(), "HTTP".to_owned()];
+
+        assert_eq!(convert_case_with_acronyms("httpURL", &CaseType::SnakeCase, &acronyms), "http_url");
+        assert_eq!(convert_case_with_acronyms("httpURL", &CaseType::UpperCamelCase, &acronyms), "HttpUrl");
+        assert_eq!(convert_case_with_acronyms("userID", &CaseType::SnakeCase, &acronyms), "user_id");
+        assert_eq!(convert_case_with_acronyms("apiURL", &CaseType::SnakeCase, &acronyms), "api_url");
+        assert_eq!(convert_case_with_acronyms("HTTPAPIKey", &CaseType::SnakeCase, &acronyms), "http_api_key");
+        assert_eq!(convert_case_with_acronyms("IDentity", &CaseType::CamelCase, &acronyms), "IDentity");
+    }
+
+    #[test]
+    fn preserve() {
+        let acronyms = vec!["ID".to_owned()];
+
+        assert_eq!(convert_case("user_Id-x", &CaseType::Preserve), "user_Id-x");
+        assert_eq!(convert_case_with_acronyms("userID", &CaseType::Preserve, &acronyms), "userID");
+    }
+
+    #[test]
+    fn singularize_names() {
+        let names = ["users", "order_items", "categories", "addresses", "boxes", "status", "data", "HTTPProxies", "s"];
+        let expected_result = ["user", "order_item", "category", "address", "box", "status", "data", "HTTPProxy", "s"];
+
+        let result: Vec<String> = names.iter().map(|name| singularize(name)).collect();
+
+        assert_eq!(result, expected_result);
+    }
+}
@@ -0,0 +1,284 @@
+use std::collections::BTreeMap;
+use crate::lib::model::tree::Samples;
+
+/// Pairs of string values commonly used in place of a boolean.
+const BOOL_STRING_PAIRS: [(&str, &str); 4] = [
+    ("true", "false"),
+    ("yes", "no"),
+    ("y", "n"),
+    ("on", "off"),
+];
+
+/// Minimum length of a string to be considered base64, shorter strings are too likely to be plain words.
+const MIN_BASE64_LENGTH: usize = 24;
+
+/// Currency symbols allowed before the number of a decimal string.
+const CURRENCY_SYMBOLS: [char; 4] = ['$', '€', '£', '¥'];
+
+/// Checks whether the values of a string field can be represented as a boolean.
+/// # Arguments
+/// * `samples` distinct values seen for the field.
+/// # Returns
+/// `true` if every value belongs to the same pair of [BOOL_STRING_PAIRS], ignoring case.
+pub fn is_bool_string(samples: &[String]) -> bool {
+    if samples.is_empty() {
+        return false;
+    }
+
+    BOOL_STRING_PAIRS.iter().any(|(true_value, false_value)| {
+        samples.iter().all(|sample| sample.eq_ignore_ascii_case(true_value) || sample.eq_ignore_ascii_case(false_value))
+    })
+}
+
+/// Checks whether the values of a string field look like base64 encoded binary data.
+/// # Arguments
+/// * `samples` distinct values seen for the field.
+/// # Returns
+/// `true` if every value is at least [MIN_BASE64_LENGTH] characters of the standard or url safe base64 alphabet,
+/// correctly padded and mixing upper case letters, lower case letters and digits or symbols.
+pub fn is_base64(samples: &[String]) -> bool {
+    !samples.is_empty() && samples.iter().all(|sample| is_base64_value(sample))
+}
+
+/// Checks whether the values of a string field are decimal numbers, such as prices.
+/// # Arguments
+/// * `samples` distinct values seen for the field.
+/// # Returns
+/// `true` if every value is a number with an optional sign, fraction and leading [CURRENCY_SYMBOLS] symbol,
+/// and at least one of them has a fraction, plain integers being too likely to be codes.
+pub fn is_decimal_string(samples: &[String]) -> bool {
+    let mut fraction = false;
+
+    for sample in samples {
+        match decimal_fraction(sample) {
+            Some(has_fraction) => fraction |= has_fraction,
+            None => return false,
+        }
+    }
+
+    fraction
+}
+
+/// Checks whether some values of a decimal string field start with a currency symbol, see [is_decimal_string].
+/// # Arguments
+/// * `samples` distinct values seen for the field.
+/// # Returns
+/// `true` if any value starts with one of [CURRENCY_SYMBOLS].
+pub fn has_currency_symbol(samples: &[String]) -> bool {
+    samples.iter().any(|sample| sample.starts_with(CURRENCY_SYMBOLS))
+}
+
+/// Checks whether a string field always holds the same value.
+/// # Arguments
+/// * `samples` values seen for the field.
+/// # Returns
+/// `true` if the field was seen at least twice, always with the same value.
+pub fn is_constant_string(samples: &Samples) -> bool {
+    samples.count >= 2 && samples.values.len() == 1
+}
+
+/// Checks whether a string field was only seen empty.
+/// # Arguments
+/// * `samples` values seen for the field.
+/// # Returns
+/// `true` if every value seen is `""`.
+pub fn is_empty_string(samples: &Samples) -> bool {
+    samples.values.len() == 1 && samples.values[0].is_empty()
+}
+
+/// Type chosen for a string value by a [TypeInference].
+#[derive(Debug, PartialEq, Eq)]
+pub enum InferredType {
+    /// No particular type, the built-in heuristics decide.
+    Plain,
+    /// Type written as is in the output.
+    Named(String),
+}
+
+/// Classification of string values, consulted by the transformer before its built-in heuristics.
+/// A string field gets a [InferredType::Named] type only if every value seen for it was given that type.
+/// # Examples
+/// ```
+/// use json_parser::{InferredType, RUST_DEFINITION, Transformer, TypeInference};
+///
+/// // Gives the strings looking like e-mail addresses their own type.
+/// struct EmailInference;
+///
+/// impl TypeInference for EmailInference {
+///     fn classify_string(&self, value: &str) -> InferredType {
+///         if value.contains('@') {
+///             InferredType::Named("Email".to_owned())
+///         } else {
+///             InferredType::Plain
+///         }
+///     }
+/// }
+///
+/// let root = json_parser::parse_value("{\"contact\": \"a@example.com\", \"name\": \"a\"}")?;
+/// let transformer = Transformer::with_inference(RUST_DEFINITION, root, None, Box::new(EmailInference))?;
+/// assert_eq!(transformer.start_transform(), vec![vec![
+///     "#[derive(Serialize, Deserialize, Debug)]",
+///     "struct Root {",
+///     "\tcontact: Email,",
+///     "\tname: String,",
+///     "}",
+/// ]]);
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+pub trait TypeInference {
+    fn classify_string(&self, value: &str) -> InferredType;
+}
+
+/// Default [TypeInference], naming the type of strings starting with a configured prefix,
+/// see [TransformOptions::string_prefixes](crate::lib::model::transform_config::TransformOptions::string_prefixes).
+pub struct PrefixInference {
+    /// Types by prefix.
+    prefixes: BTreeMap<String, String>,
+}
+
+impl PrefixInference {
+    pub fn new(prefixes: BTreeMap<String, String>) -> Self {
+        Self { prefixes }
+    }
+}
+
+impl TypeInference for PrefixInference {
+    fn classify_string(&self, value: &str) -> InferredType {
+        // The longest prefix wins, so that `usr_admin_` can be told apart from `usr_`.
+        self.prefixes.iter()
+            .filter(|(prefix, _)| value.starts_with(prefix.as_str()))
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map_or(InferredType::Plain, |(_, type_str)| InferredType::Named(type_str.clone()))
+    }
+}
+
+fn is_base64_value(value: &str) -> bool {
+    let content = value.trim_end_matches('=');
+    let padding = value.len() - content.len();
+
+    if value.len() < MIN_BASE64_LENGTH || padding > 2 || (padding > 0 && !value.len().is_multiple_of(4)) {
+        return false;
+    }
+
+    let mut upper = false;
+    let mut lower = false;
+    let mut other = false;
+
+    for char in content.chars() {
+        match char {
+            'A'..='Z' => upper = true,
+            'a'..='z' => lower = true,
+            '0'..='9' | '+' | '/' | '-' | '_' => other = true,
+            _ => return false,
+        }
+    }
+
+    upper && lower && other
+}
+
+/// Parses a single decimal string, see [is_decimal_string].
+/// # Returns
+/// Whether the number has a fraction, or `None` if the value isn't a decimal number.
+fn decimal_fraction(value: &str) -> Option<bool> {
+    let value = value.strip_prefix(CURRENCY_SYMBOLS).unwrap_or(value);
+    let value = value.strip_prefix('-').unwrap_or(value);
+    let (integer, fraction) = match value.split_once('.') {
+        Some((integer, fraction)) => (integer, Some(fraction)),
+        None => (value, None),
+    };
+
+    let digits = |part: &str| !part.is_empty() && part.chars().all(|char| char.is_ascii_digit());
+    if !digits(integer) || fraction.is_some_and(|fraction| !digits(fraction)) {
+        return None;
+    }
+
+    Some(fraction.is_some())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+    use crate::lib::inference::{InferredType, PrefixInference, TypeInference, has_currency_symbol, is_base64, is_bool_string, is_constant_string, is_decimal_string, is_empty_string};
+    use crate::lib::model::tree::Samples;
+
+    #[test]
+    fn yes_no_is_bool() {
+        let samples = vec!["yes".to_owned(), "No".to_owned()];
+
+        assert!(is_bool_string(&samples));
+    }
+
+    #[test]
+    fn mixed_pairs_are_not_bool() {
+        let samples = vec!["yes".to_owned(), "false".to_owned()];
+
+        assert!(!is_bool_string(&samples));
+    }
+
+    #[test]
+    fn no_samples_are_not_bool() {
+        assert!(!is_bool_string(&[]));
+    }
+
+    #[test]
+    fn base64_string() {
+        let samples = vec!["SGVsbG8sIGJhc2U2NCBlbmNvZGVkIHdvcmxkIQ==".to_owned()];
+
+        assert!(is_base64(&samples));
+    }
+
+    #[test]
+    fn prices_are_decimal() {
+        let samples = vec!["19.99".to_owned(), "$5".to_owned(), "€-0.50".to_owned()];
+
+        assert!(is_decimal_string(&samples));
+        assert!(!is_decimal_string(&["N/A".to_owned()]));
+        assert!(!is_decimal_string(&["12345".to_owned()]));
+        assert!(!is_decimal_string(&["1.".to_owned()]));
+        assert!(has_currency_symbol(&samples));
+        assert!(!has_currency_symbol(&["19.99".to_owned(), "-0.50".to_owned()]));
+    }
+
+    #[test]
+    fn short_word_is_not_base64() {
+        let samples = vec!["Hello1".to_owned()];
+
+        assert!(!is_base64(&samples));
+    }
+
+    #[test]
+    fn hex_hash_is_not_base64() {
+        let samples = vec!["9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08".to_owned()];
+
+        assert!(!is_base64(&samples));
+    }
+
+    #[test]
+    fn repeated_value_is_constant() {
+        let samples = Samples { values: vec!["user".to_owned()], count: 2, truncated: false };
+
+        assert!(is_constant_string(&samples));
+    }
+
+    #[test]
+    fn single_value_is_not_constant() {
+        assert!(!is_constant_string(&Samples::new("user".to_owned())));
+    }
+
+    #[test]
+    fn only_empty_values_are_empty() {
+        assert!(is_empty_string(&Samples { values: vec!["".to_owned()], count: 3, truncated: false }));
+        assert!(!is_empty_string(&Samples { values: vec!["".to_owned(), "a".to_owned()], count: 2, truncated: false }));
+        assert!(!is_empty_string(&Samples::default()));
+    }
+
+    #[test]
+    fn longest_prefix_names_type() {
+        let prefixes = BTreeMap::from([("usr_".to_owned(), "UserId".to_owned()), ("usr_admin_".to_owned(), "AdminId".to_owned())]);
+        let inference = PrefixInference::new(prefixes);
+
+        assert_eq!(inference.classify_string("usr_42"), InferredType::Named("UserId".to_owned()));
+        assert_eq!(inference.classify_string("usr_admin_1"), InferredType::Named("AdminId".to_owned()));
+        assert_eq!(inference.classify_string("grp_1"), InferredType::Plain);
+    }
+}
@@ -1,106 +1,1404 @@
-use std::env::Args;
 use std::{fs, process};
+use std::borrow::Cow;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
 use std::path::Path;
-use anyhow::bail;
+use std::process::{Command, Stdio};
+use std::{iter, thread};
+use anyhow::{anyhow, bail};
 use crate::HELP_MESSAGE;
-use crate::lib::model::transform_config::{DART_DEFINITION, JAVA_DEFINITION, KOTLIN_DEFINITION, RUST_DEFINITION, TransformConfig};
-use crate::lib::parser::lexer::Lexer;
+use crate::lib::model::parse_options::ParseOptions;
+use crate::lib::case::convert_case;
+use crate::lib::model::transform_config::{CaseType, DART_DEFINITION, EmptyObject, JAVA_DEFINITION, KOTLIN_DEFINITION, KOTLIN_SERIALIZABLE_DEFINITION, PHP_DEFINITION, RUST_DEFINITION, TYPESCRIPT_DEFINITION, TransformConfig, Underscores};
+use crate::lib::model::token::Token;
+use crate::lib::parser::{lex, parse_value_with};
+use crate::lib::parser::schema::{FieldDocs, SchemaReader};
+use crate::lib::model::tree::JsonRoot;
 use crate::lib::parser::tokenizer::Tokenizer;
-use crate::lib::transformer::Transformer;
+use crate::lib::sources::{FieldSources, add_sources};
+use crate::lib::transformer::{PLACEHOLDERS, Transformer};
+use crate::lib::validation::{Diagnostic, Severity, validate_root};
 
-mod parser;
-mod model;
-mod transformer;
+pub mod parser;
+pub mod model;
+pub mod transformer;
 mod case;
+pub mod inference;
+mod validation;
+mod sources;
 
 
 pub struct Config {
-    filename: String,
+    /// Input files, their root objects being merged. Fields missing from some of them become optional.
+    filenames: Vec<String>,
+    /// Print the tokens produced by the lexer instead of the types, for debugging.
+    dump_tokens: bool,
+    /// Print the tree produced by the tokenizer instead of the types, for debugging.
+    dump_tree: bool,
+    /// Print every field name of the input, sorted and without duplicates, instead of the types.
+    list_keys: bool,
+    /// The input is a JSON Schema describing the documents instead of a document.
+    schema: bool,
+    /// JSON Schema whose property descriptions are placed above the fields generated from the documents.
+    docs_from: Option<String>,
+    /// Directory where every type is written to its own file, instead of printing them. Set by `--out-dir` or `--output`.
+    out_dir: Option<String>,
+    /// File where the types are written, instead of printing them.
+    output: Option<String>,
+    /// File holding previously generated types, compared with the types generated now instead of printing them.
+    diff: Option<String>,
+    /// Print the lines that differ from the `diff` file.
+    print_diff: bool,
+    /// Command the generated text is piped through before being printed or written, such as `rustfmt`.
+    format_with: Option<String>,
+    root_name: Option<String>,
     transformer_config: TransformConfig,
+    /// Definitions generated from the same input after `transformer_config`, each one written to `out_dir`.
+    targets: Vec<TransformConfig>,
+    parse_options: ParseOptions,
 }
 
 
 impl Config {
-    pub fn new(args: Args) -> anyhow::Result<Self> {
+    pub fn new<I: Iterator<Item = String>>(args: I) -> anyhow::Result<Self> {
         let mut help = None;
 
         let mut definition_arg = None;
 
-        let mut filename = None;
+        let mut filenames = Vec::new();
 
-        args.skip(1).for_each(|arg| {
-            if arg.contains("--definition") {
-                definition_arg = Some(arg)
-            } else if arg == "--help" {
+        let mut bool_strings = false;
+
+        let mut base64_bytes = false;
+
+        let mut decimal_strings = false;
+
+        let mut empty_strings_as_null = false;
+
+        let mut flatten = false;
+
+        let mut constant_enums = false;
+
+        let mut value_conversions = false;
+
+        let mut parse_helper = false;
+
+        let mut nested_types = false;
+
+        let mut readonly = false;
+
+        let mut skip_annotations = false;
+
+        let mut sort_types = false;
+        let mut sort_types_by_name = false;
+
+        let mut allow_dead_code = false;
+
+        let mut field_sources = false;
+
+        let mut deny_unknown_fields = false;
+
+        let mut banner = false;
+
+        let mut builder = false;
+
+        let mut modules = false;
+
+        let mut dump_tokens = false;
+
+        let mut dump_tree = false;
+
+        let mut list_keys = false;
+
+        let mut schema = false;
+
+        let mut parse_options = ParseOptions::default();
+
+        let mut root_name = None;
+
+        let mut out_dir = None;
+
+        let mut output = None;
+
+        let mut format_with = None;
+
+        let mut docs_from = None;
+
+        let mut diff = None;
+
+        let mut print_diff = false;
+
+        let mut file_header = None;
+
+        let mut item_name = None;
+
+        let mut visibility = None;
+
+        let mut type_attributes = Vec::new();
+        let mut declaration_attributes = Vec::new();
+
+        let mut acronyms = Vec::new();
+
+        let mut path_overrides = Vec::new();
+
+        let mut string_prefixes = Vec::new();
+
+        let mut max_types = None;
+
+        let mut max_line_width = None;
+
+        let mut empty_object_as = None;
+
+        let mut underscores = None;
+        let mut ascii_only = false;
+
+        let mut int_type = None;
+
+        let mut float_type = None;
+
+        let mut string_type = None;
+
+        let mut bool_type = None;
+
+        let mut unknown_type = None;
+
+        let mut empty_array_type = None;
+
+        let mut args = args.skip(1);
+        while let Some(arg) = args.next() {
+            if let Some(definition) = Self::option_value("--definition", &arg, &mut args)? {
+                definition_arg = Some(definition)
+            } else if let Some(name) = Self::option_value("--root-name", &arg, &mut args)? {
+                root_name = Some(name);
+            } else if let Some(dir) = Self::option_value("--out-dir", &arg, &mut args)? {
+                out_dir = Some(dir);
+            } else if let Some(dir) = Self::option_value("--output", &arg, &mut args)? {
+                out_dir = Some(dir);
+            } else if let Some(file) = Self::option_value("--output-file", &arg, &mut args)? {
+                output = Some(file);
+            } else if let Some(command) = Self::option_value("--format-with", &arg, &mut args)? {
+                format_with = Some(command);
+            } else if let Some(file) = Self::option_value("--docs-from", &arg, &mut args)? {
+                docs_from = Some(file);
+            } else if let Some(file) = Self::option_value("--diff", &arg, &mut args)? {
+                diff = Some(file);
+            } else if let Some(header) = Self::option_value("--file-header", &arg, &mut args)? {
+                file_header = Some(header);
+            } else if let Some(name) = Self::option_value("--item-name", &arg, &mut args)? {
+                item_name = Some(name);
+            } else if let Some(value) = Self::option_value("--visibility", &arg, &mut args)? {
+                visibility = Some(value);
+            } else if let Some(attribute) = Self::option_value("--type-attribute", &arg, &mut args)? {
+                type_attributes.push(attribute);
+            } else if let Some(attribute) = Self::option_value("--declaration-attribute", &arg, &mut args)? {
+                declaration_attributes.push(attribute);
+            } else if let Some(key) = Self::option_value("--discriminator", &arg, &mut args)? {
+                parse_options.discriminator = Some(key);
+            } else if let Some(path_type) = Self::option_value("--path-type", &arg, &mut args)? {
+                match path_type.split_once('=') {
+                    Some((path, type_str)) => path_overrides.push((path.trim().to_owned(), type_str.trim().to_owned())),
+                    None => bail!("expected --path-type=path=Type"),
+                }
+            } else if let Some(string_prefix) = Self::option_value("--string-prefix", &arg, &mut args)? {
+                match string_prefix.split_once('=') {
+                    Some((prefix, type_str)) => string_prefixes.push((prefix.to_owned(), type_str.trim().to_owned())),
+                    None => bail!("expected --string-prefix=prefix=Type"),
+                }
+            } else if let Some(acronym) = Self::option_value("--acronym", &arg, &mut args)? {
+                acronyms.push(acronym);
+            } else if let Some(max) = Self::option_value("--max-types", &arg, &mut args)? {
+                match max.parse() {
+                    Ok(max) => max_types = Some(max),
+                    Err(_) => bail!("expected --max-types=number"),
+                }
+            } else if let Some(value) = Self::option_value("--empty-object-as", &arg, &mut args)? {
+                empty_object_as = Some(match value.as_str() {
+                    "struct" => EmptyObject::Struct,
+                    "map" => EmptyObject::Map,
+                    "unit" => EmptyObject::Unit,
+                    _ => bail!("expected --empty-object-as=struct, --empty-object-as=map or --empty-object-as=unit"),
+                });
+            } else if arg == "--ascii-only" {
+                ascii_only = true;
+            } else if let Some(value) = Self::option_value("--underscores", &arg, &mut args)? {
+                underscores = Some(match value.as_str() {
+                    "preserve" => Underscores::Preserve,
+                    "strip" => Underscores::Strip,
+                    _ => bail!("expected --underscores=preserve or --underscores=strip"),
+                });
+            } else if let Some(width) = Self::option_value("--max-line-width", &arg, &mut args)? {
+                match width.parse() {
+                    Ok(width) => max_line_width = Some(width),
+                    Err(_) => bail!("expected --max-line-width=number"),
+                }
+            } else if let Some(type_str) = Self::option_value("--int-type", &arg, &mut args)? {
+                int_type = Some(type_str);
+            } else if let Some(type_str) = Self::option_value("--float-type", &arg, &mut args)? {
+                float_type = Some(type_str);
+            } else if let Some(type_str) = Self::option_value("--string-type", &arg, &mut args)? {
+                string_type = Some(type_str);
+            } else if let Some(type_str) = Self::option_value("--bool-type", &arg, &mut args)? {
+                bool_type = Some(type_str);
+            } else if let Some(type_str) = Self::option_value("--unknown-type", &arg, &mut args)? {
+                unknown_type = Some(type_str);
+            } else if let Some(type_str) = Self::option_value("--empty-array-type", &arg, &mut args)? {
+                empty_array_type = Some(type_str);
+            } else if arg == "--help" || arg == "--help-definition" {
                 help = Some(arg);
+            } else if arg == "--bool-strings" {
+                bool_strings = true;
+            } else if arg == "--base64-bytes" {
+                base64_bytes = true;
+            } else if arg == "--decimal-strings" {
+                decimal_strings = true;
+            } else if arg == "--empty-string-as-null" {
+                empty_strings_as_null = true;
+            } else if arg == "--flatten" {
+                flatten = true;
+            } else if arg == "--constant-enums" {
+                constant_enums = true;
+            } else if arg == "--value-conversions" {
+                value_conversions = true;
+            } else if arg == "--parse-helper" {
+                parse_helper = true;
+            } else if arg == "--nested-types" {
+                nested_types = true;
+            } else if arg == "--readonly" {
+                readonly = true;
+            } else if arg == "--no-annotations" {
+                skip_annotations = true;
+            } else if arg == "--sort-types" {
+                sort_types = true;
+            } else if arg == "--sort-types-by-name" {
+                sort_types_by_name = true;
+            } else if arg == "--print-diff" {
+                print_diff = true;
+            } else if arg == "--modules" {
+                modules = true;
+            } else if arg == "--builder" {
+                builder = true;
+            } else if arg == "--banner" {
+                banner = true;
+            } else if arg == "--deny-unknown-fields" {
+                deny_unknown_fields = true;
+            } else if arg == "--field-sources" {
+                field_sources = true;
+            } else if arg == "--allow-dead-code" {
+                allow_dead_code = true;
+            } else if arg == "--dump-tokens" {
+                dump_tokens = true;
+            } else if arg == "--dump-tree" {
+                dump_tree = true;
+            } else if arg == "--list-keys" {
+                list_keys = true;
+            } else if arg == "--schema" {
+                schema = true;
+            } else if arg == "--fallback" {
+                parse_options.fallback = true;
+            } else if arg == "--concatenated" {
+                parse_options.concatenated = true;
+            } else if arg == "--shallow-arrays" {
+                parse_options.shallow_arrays = true;
+            } else if arg == "--null-elements" {
+                parse_options.null_elements = true;
+            } else if arg == "--null-fields" {
+                parse_options.null_fields = true;
+            } else if arg == "--decimal-comma" {
+                parse_options.decimal_comma = true;
+            } else if arg == "--allow-nan" {
+                parse_options.allow_nan = true;
+            } else if arg == "--integer-maps" {
+                parse_options.integer_maps = true;
+            } else if arg == "--fixed-arrays" {
+                parse_options.fixed_arrays = true;
+            } else if arg == "--tuples" {
+                parse_options.tuples = true;
             } else {
-                filename = Some(arg);
+                filenames.push(arg);
             }
-        });
+        }
 
-        if help.is_some() {
-            println!("{}", HELP_MESSAGE);
-            process::exit(0);
-        }
-
-        let transformer_config = match definition_arg {
-            Some(definition) => {
-                let definition = match definition.split('=').last() {
-                    Some(definition) => definition,
-                    None => bail!("syntax error in definition argument")
-                };
-
-                match definition.as_ref() {
-                    "kotlin" => KOTLIN_DEFINITION,
-                    "rust" => RUST_DEFINITION,
-                    "java" => JAVA_DEFINITION,
-                    "dart" => DART_DEFINITION,
-                    _ => {
-                        if Path::new(definition).exists() {
-                            Self::load_definition(definition)?
-                        } else {
-                            bail!("definition not found")
-                        }
-                    }
-                }
-            },
+        match help.as_deref() {
+            Some("--help-definition") => {
+                println!("{}", definition_help()?);
+                process::exit(0);
+            }
+            Some(_) => {
+                println!("{}", HELP_MESSAGE);
+                process::exit(0);
+            }
+            None => {}
+        }
+
+        let mut definitions = match definition_arg {
+            Some(definition) => definition.split(',').map(|name| Self::definition(name.trim())).collect::<anyhow::Result<Vec<_>>>()?,
             None => bail!("definition not provided")
         };
+        if definitions.len() > 1 && out_dir.is_none() {
+            bail!("several definitions are written to their own files, --out-dir is needed")
+        }
+        if diff.is_some() && out_dir.is_some() {
+            bail!("--diff compares the types with a single file, it can't be used with --out-dir")
+        }
+        if output.is_some() && out_dir.is_some() {
+            bail!("--output-file writes every type to a single file, it can't be used with --out-dir")
+        }
 
-        let filename = match filename {
-            Some(filename) => filename,
-            _ => bail!("filename not provided")
-        };
+        for transformer_config in definitions.iter_mut() {
+            transformer_config.options.bool_strings |= bool_strings;
+            transformer_config.options.base64_bytes |= base64_bytes;
+            transformer_config.options.decimal_strings |= decimal_strings;
+            transformer_config.options.empty_strings_as_null |= empty_strings_as_null;
+            transformer_config.options.flatten |= flatten;
+            transformer_config.options.constant_enums |= constant_enums;
+            transformer_config.options.value_conversions |= value_conversions;
+            transformer_config.options.parse_helper |= parse_helper;
+            transformer_config.options.nested_types |= nested_types;
+            transformer_config.options.readonly |= readonly;
+            transformer_config.options.skip_annotations |= skip_annotations;
+            transformer_config.options.sort_types |= sort_types;
+            transformer_config.options.sort_types_by_name |= sort_types_by_name;
+            transformer_config.options.allow_dead_code |= allow_dead_code;
+            transformer_config.options.banner |= banner;
+            transformer_config.options.builder |= builder;
+            transformer_config.options.modules |= modules;
+            transformer_config.options.field_sources |= field_sources;
+            transformer_config.options.deny_unknown_fields |= deny_unknown_fields;
+            transformer_config.options.ascii_only |= ascii_only;
+            if item_name.is_some() {
+                transformer_config.options.item_name = item_name.clone();
+            }
+            if max_types.is_some() {
+                transformer_config.options.max_types = max_types;
+            }
+            if let Some(empty_object_as) = empty_object_as {
+                transformer_config.options.empty_object_as = empty_object_as;
+            }
+            if let Some(underscores) = underscores {
+                transformer_config.options.underscores = underscores;
+            }
+            if max_line_width.is_some() {
+                transformer_config.options.max_line_width = max_line_width;
+            }
+            if let Some(ref visibility) = visibility {
+                transformer_config.visibility = Some(Cow::Owned(visibility.clone()));
+            }
+            if let Some(ref int_type) = int_type {
+                transformer_config.int_type = Cow::Owned(int_type.clone());
+            }
+            if let Some(ref float_type) = float_type {
+                transformer_config.float_type = Cow::Owned(float_type.clone());
+            }
+            if let Some(ref string_type) = string_type {
+                transformer_config.string_type = Cow::Owned(string_type.clone());
+            }
+            if let Some(ref bool_type) = bool_type {
+                transformer_config.bool_type = Cow::Owned(bool_type.clone());
+            }
+            if let Some(ref unknown_type) = unknown_type {
+                transformer_config.unknown_type = Some(Cow::Owned(unknown_type.clone()));
+            }
+            if let Some(ref empty_array_type) = empty_array_type {
+                transformer_config.empty_array_type = Some(Cow::Owned(empty_array_type.clone()));
+            }
+            if let Some(ref file_header) = file_header {
+                transformer_config.file_header = Some(Cow::Owned(file_header.clone()));
+            }
+            if out_dir.is_some() && transformer_config.file_extension.is_none() && transformer_config.single_file_extension.is_none() {
+                bail!("the definition can't write files, it has no file_extension")
+            }
+            if nested_types && transformer_config.nested_definition.is_none() {
+                bail!("the definition can't declare nested types, it has no nested_definition")
+            }
+            transformer_config.type_attributes.extend(type_attributes.iter().cloned());
+            transformer_config.declaration_attributes.extend(declaration_attributes.iter().cloned());
+            transformer_config.acronyms.extend(acronyms.iter().cloned());
+            transformer_config.options.path_overrides.extend(path_overrides.iter().cloned());
+            transformer_config.options.string_prefixes.extend(string_prefixes.iter().cloned());
+        }
+        let transformer_config = definitions.remove(0);
+        let targets = definitions;
+
+        if filenames.is_empty() {
+            bail!("filename not provided")
+        }
 
         Ok(
             Config {
-                filename,
-                transformer_config
+                filenames,
+                dump_tokens,
+                dump_tree,
+                list_keys,
+                schema,
+                docs_from,
+                out_dir,
+                output,
+                diff,
+                print_diff,
+                format_with,
+                root_name,
+                transformer_config,
+                targets,
+                parse_options,
             }
         )
     }
 
+    /// Reads the value of an option written as `--name=value`, `--name value` or `--name = value`.
+    /// Surrounding whitespace and quotes are removed from the value.
+    /// # Arguments
+    /// * `name` option name, including the leading dashes.
+    /// * `arg` current argument.
+    /// * `args` remaining arguments, advanced when the value is given separately.
+    /// # Returns
+    /// `None` if `arg` is not the option `name`.
+    /// # Errors
+    /// If the option is given without a value.
+    fn option_value<I: Iterator<Item = String>>(name: &str, arg: &str, args: &mut I) -> anyhow::Result<Option<String>> {
+        let rest = match arg.strip_prefix(name) {
+            Some(rest) => rest,
+            None => return Ok(None),
+        };
+
+        let value = if let Some(value) = rest.strip_prefix('=') {
+            value.to_owned()
+        } else if rest.is_empty() {
+            match args.next() {
+                Some(next) if next.trim() == "=" => args.next().unwrap_or_default(),
+                Some(next) => match next.trim_start().strip_prefix('=') {
+                    Some(value) => value.to_owned(),
+                    None => next,
+                },
+                None => String::new(),
+            }
+        } else {
+            return Ok(None);
+        };
+
+        let value = value.trim().trim_matches(|c| c == '"' || c == '\'').trim();
+        if value.is_empty() {
+            bail!("empty value for {}", name)
+        }
+
+        Ok(Some(value.to_owned()))
+    }
+
+    /// Finds a definition by name: a built-in one, the URL of a custom one or the path of a custom one.
+    fn definition(definition: &str) -> anyhow::Result<TransformConfig> {
+        Ok(match definition {
+            "kotlin" => KOTLIN_DEFINITION,
+            "kotlin-serializable" => KOTLIN_SERIALIZABLE_DEFINITION,
+            "rust" => RUST_DEFINITION,
+            "java" => JAVA_DEFINITION,
+            "dart" => DART_DEFINITION,
+            "typescript" => TYPESCRIPT_DEFINITION,
+            "php" => PHP_DEFINITION,
+            _ if definition.starts_with("http://") || definition.starts_with("https://") => {
+                Self::fetch_definition(definition)?
+            }
+            _ => {
+                if Path::new(definition).exists() {
+                    Self::load_definition(definition)?
+                } else {
+                    bail!("definition not found")
+                }
+            }
+        })
+    }
+
     pub fn load_definition(path: &str) -> anyhow::Result<TransformConfig> {
         let definition_file = fs::read_to_string(path)?;
         let config: TransformConfig = toml::from_str(&definition_file)?;
         Ok(config)
     }
+
+    /// Downloads a custom definition, for definitions shared over HTTP.
+    #[cfg(feature = "remote-definition")]
+    pub fn fetch_definition(url: &str) -> anyhow::Result<TransformConfig> {
+        let definition_file = ureq::get(url).call()?.into_string()?;
+        let config: TransformConfig = toml::from_str(&definition_file)?;
+        Ok(config)
+    }
+
+    #[cfg(not(feature = "remote-definition"))]
+    pub fn fetch_definition(url: &str) -> anyhow::Result<TransformConfig> {
+        bail!("can't fetch the definition at {}, json_parser was built without the remote-definition feature", url)
+    }
+}
+
+/// Builds the reference of the placeholders recognized in custom definitions, followed by an example definition.
+fn definition_help() -> anyhow::Result<String> {
+    let mut help = String::from("Placeholders recognized in custom definitions:\n");
+
+    for placeholder in PLACEHOLDERS.iter() {
+        help.push_str(&format!(
+            "    {:<48}{}{}\n",
+            placeholder.field,
+            placeholder.placeholder,
+            if placeholder.required { " (required)" } else { "" }
+        ));
+    }
+
+    help.push_str("\nExample definition (built-in rust):\n");
+    help.push_str(&toml::to_string(&RUST_DEFINITION)?);
+
+    Ok(help)
+}
+
+/// Formats tokens one per line, with their 1 based line and column.
+fn dump_tokens(tokens: &[Token]) -> String {
+    tokens.iter()
+        .map(|token| format!("{}:{} {:?}\n", token.line + 1, token.col + 1, token.value))
+        .collect()
+}
+
+/// Outcome of a [run] that didn't fail.
+#[derive(Debug, PartialEq, Eq)]
+pub enum RunStatus {
+    Done,
+    /// The file given to `--diff` doesn't hold the types generated now.
+    OutOfDate,
+    /// The types were generated, but the input has problems that keep them from compiling, such as duplicate fields.
+    Invalid,
+}
+
+pub fn run(config: Config) -> anyhow::Result<RunStatus> {
+    let mut tokenizer_result = None;
+    let mut sources = FieldSources::new();
+    let mut docs = FieldDocs::new();
+
+    if let Some(ref filename) = config.docs_from {
+        let file = fs::read_to_string(filename)?;
+        docs = SchemaReader::new(lex(&file, &ParseOptions::default())).start_schema()?.1;
+    }
+
+    for filename in &config.filenames {
+        let file = fs::read_to_string(filename)?;
+
+        if config.dump_tokens {
+            print!("{}", dump_tokens(&lex(&file, &config.parse_options)));
+            continue;
+        }
+
+        let root = if config.schema {
+            let (root, schema_docs) = SchemaReader::new(lex(&file, &config.parse_options)).start_schema()?;
+            for (path, doc) in schema_docs {
+                docs.entry(path).or_insert(doc);
+            }
+            root
+        } else {
+            parse_value_with(&file, &config.parse_options)
+                .map_err(|error| match error.position() {
+                    Some((line, col)) => anyhow!("{}\n{}", error, source_snippet(&file, line, col)),
+                    None => error.into(),
+                })?
+        };
+
+        let source = Path::new(filename).file_name().map_or(filename.as_str(), |name| name.to_str().unwrap_or(filename));
+        add_sources(&mut sources, &root, source);
+
+        tokenizer_result = Some(match (tokenizer_result, root) {
+            (None, root) => root,
+            (Some(JsonRoot::Object(mut fields)), JsonRoot::Object(new_fields)) => {
+                Tokenizer::merge_documents(&mut fields, new_fields);
+                JsonRoot::Object(fields)
+            }
+            _ => bail!("only root objects can be merged, {} or a previous file holds an array or a single value", filename),
+        });
+    }
+
+    let tokenizer_result = match tokenizer_result {
+        Some(tokenizer_result) => tokenizer_result,
+        None => return Ok(RunStatus::Done),
+    };
+
+    if config.dump_tree {
+        print!("{}", tokenizer_result);
+        return Ok(RunStatus::Done);
+    }
+
+    if config.list_keys {
+        for name in tokenizer_result.key_names() {
+            println!("{}", name);
+        }
+        return Ok(RunStatus::Done);
+    }
+
+    // Definitions converting names alike find the same problems, each one is printed once.
+    let mut diagnostics: Vec<Diagnostic> = Vec::new();
+    for transformer_config in iter::once(&config.transformer_config).chain(&config.targets) {
+        for diagnostic in validate_root(&tokenizer_result, transformer_config, config.root_name.as_deref()) {
+            if !diagnostics.contains(&diagnostic) {
+                eprintln!("{}", diagnostic);
+                diagnostics.push(diagnostic);
+            }
+        }
+    }
+    // The types are still generated, but won't compile.
+    let status = if diagnostics.iter().any(|diagnostic| diagnostic.severity == Severity::Error) {
+        RunStatus::Invalid
+    } else {
+        RunStatus::Done
+    };
+
+    if let Some(out_dir) = config.out_dir {
+        // The tree is read once, each definition transforms its own copy.
+        for transformer_config in iter::once(config.transformer_config).chain(config.targets) {
+            let file_header = output_header(&transformer_config);
+            let file_extension = transformer_config.file_extension.clone();
+            let single_file_extension = transformer_config.single_file_extension.clone();
+            let mut transformer = Transformer::new(transformer_config, tokenizer_result.clone(), config.root_name.clone())?
+                .with_sources(sources.clone())
+                .with_docs(docs.clone());
+            let types = transformer.start_transform_named();
+            print_warnings(&transformer);
+
+            match (file_extension, single_file_extension) {
+                (Some(file_extension), _) => {
+                    write_types(Path::new(&out_dir), &file_extension, file_header.as_deref(), config.format_with.as_deref(), types)?
+                }
+                (None, Some(file_extension)) => {
+                    let name = convert_case(config.root_name.as_deref().unwrap_or("Root"), &CaseType::SnakeCase);
+                    let path = Path::new(&out_dir).join(format!("{}.{}", name, file_extension));
+                    write_file(&path, file_header.as_deref(), config.format_with.as_deref(), types)?
+                }
+                (None, None) => bail!("the definition can't write files, it has no file_extension"),
+            }
+        }
+        return Ok(status);
+    }
+
+    let file_header = output_header(&config.transformer_config);
+    let mut transformer = Transformer::new(config.transformer_config, tokenizer_result, config.root_name)?.with_sources(sources).with_docs(docs);
+
+    if let Some(diff) = config.diff {
+        let mut generated = Vec::new();
+        if let Some(file_header) = file_header {
+            writeln!(generated, "{}", file_header)?;
+        }
+        transformer.start_transform_to(&mut generated)?;
+        print_warnings(&transformer);
+
+        let mut generated = String::from_utf8(generated)?;
+        if let Some(ref command) = config.format_with {
+            generated = format_output(command, generated);
+        }
+
+        let existing = fs::read_to_string(&diff)?;
+        if let Some((summary, lines)) = diff_lines(&existing, &generated) {
+            eprintln!("{} is out of date: {}", diff, summary);
+            if config.print_diff {
+                lines.iter().for_each(|line| println!("{}", line));
+            }
+            return Ok(RunStatus::OutOfDate);
+        }
+
+        return Ok(status);
+    }
+
+    let mut writer: Box<dyn Write> = match config.output {
+        Some(output) => Box::new(BufWriter::new(File::create(output)?)),
+        None => Box::new(BufWriter::new(io::stdout().lock())),
+    };
+
+    if let Some(ref command) = config.format_with {
+        // The formatter needs the whole text, the types are held until it's done.
+        let mut generated = Vec::new();
+        if let Some(file_header) = file_header {
+            writeln!(generated, "{}", file_header)?;
+        }
+        transformer.start_transform_to(&mut generated)?;
+        print_warnings(&transformer);
+        writer.write_all(format_output(command, String::from_utf8(generated)?).as_bytes())?;
+    } else {
+        // Types are written as they are generated, they are never all held at once.
+        if let Some(file_header) = file_header {
+            writeln!(writer, "{}", file_header)?;
+        }
+        transformer.start_transform_to(writer.as_mut())?;
+        print_warnings(&transformer);
+    }
+    writer.flush()?;
+
+    Ok(status)
+}
+
+/// Prints the warnings met by a transformer, see [Transformer::warnings].
+fn print_warnings(transformer: &Transformer) {
+    for warning in transformer.warnings() {
+        eprintln!("warning: {}", warning);
+    }
+}
+
+/// Shows a line of the source with a caret under a column, as rustc does for errors.
+/// # Arguments
+/// * `source` source JSON.
+/// * `line` 0 based line.
+/// * `col` 0 based column, in characters.
+fn source_snippet(source: &str, line: usize, col: usize) -> String {
+    let line_str = source.lines().nth(line).unwrap_or_default();
+    // Tabs are kept so that the caret lines up with the source whatever their width.
+    let padding: String = line_str.chars().take(col).map(|char| if char == '\t' { '\t' } else { ' ' }).collect();
+
+    format!("  | {}\n  | {}^", line_str, padding)
+}
+
+/// Compares previously generated types with the types generated now. The lines both have at their start and end
+/// are skipped, those in between being reported as removed and added.
+/// # Returns
+/// `None` if the texts are the same, otherwise a summary of the difference and the differing lines,
+/// prefixed by `-` for removed lines and `+` for added ones.
+fn diff_lines(existing: &str, generated: &str) -> Option<(String, Vec<String>)> {
+    let existing: Vec<&str> = existing.lines().collect();
+    let generated: Vec<&str> = generated.lines().collect();
+
+    let prefix = existing.iter().zip(&generated).take_while(|(old, new)| old == new).count();
+    let suffix = existing[prefix..].iter().rev().zip(generated[prefix..].iter().rev()).take_while(|(old, new)| old == new).count();
+    let removed = &existing[prefix..existing.len() - suffix];
+    let added = &generated[prefix..generated.len() - suffix];
+
+    if removed.is_empty() && added.is_empty() {
+        return None;
+    }
+
+    let summary = format!("{} lines removed and {} added from line {}", removed.len(), added.len(), prefix + 1);
+    let lines = removed.iter().map(|line| format!("-{}", line))
+        .chain(added.iter().map(|line| format!("+{}", line)))
+        .collect();
+    Some((summary, lines))
 }
 
-pub fn run(config: Config) -> anyhow::Result<()> {
-    let file = fs::read_to_string(config.filename)?;
+/// Text placed at the top of the output: the banner, if enabled, followed by the file header.
+fn output_header(config: &TransformConfig) -> Option<String> {
+    let banner = config.banner.as_deref().filter(|_| config.options.banner);
+    let lines: Vec<&str> = banner.into_iter().chain(config.file_header.as_deref()).collect();
 
+    if lines.is_empty() {
+        None
+    } else {
+        Some(lines.join("\n"))
+    }
+}
 
-    let lexer = Lexer::new(&file);
-    let lexer_result = lexer.start_lex();
-    let token = Tokenizer::new(lexer_result);
-    let tokenizer_result = token.start_tokenizer()?;
-    let transformer = Transformer::new(config.transformer_config, tokenizer_result, None)?;
-    let result = transformer.start_transform();
+/// Writes every type to its own file named after it, the header being repeated in each file.
+/// Types of the same directory reference each other without imports, as in a Java or Kotlin package.
+/// # Arguments
+/// * `dir` output directory, created if missing.
+/// * `file_extension` extension of the files, without the dot.
+/// * `file_header` text placed at the top of every file, e.g. a package line.
+/// * `format_with` command every file is piped through, see [format_output].
+/// * `types` name and lines of each type.
+fn write_types(dir: &Path, file_extension: &str, file_header: Option<&str>, format_with: Option<&str>, types: Vec<(String, Vec<String>)>) -> anyhow::Result<()> {
+    fs::create_dir_all(dir)?;
 
-    result.iter().rev().for_each(|object| object.iter().for_each(|string| {
-       println!("{}", string)
-    }));
+    for (name, lines) in types {
+        let path = dir.join(format!("{}.{}", name, file_extension));
+        let text = render(file_header, &[lines]);
+        fs::write(path, match format_with {
+            Some(command) => format_output(command, text),
+            None => text,
+        })?;
+    }
 
     Ok(())
 }
+
+/// Writes all the types to a single file, the root type last.
+/// # Arguments
+/// * `path` path of the file, its directory being created if missing.
+/// * `file_header` text placed at the top of the file.
+/// * `format_with` command the file is piped through, see [format_output].
+/// * `types` name and lines of each type.
+fn write_file(path: &Path, file_header: Option<&str>, format_with: Option<&str>, types: Vec<(String, Vec<String>)>) -> anyhow::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    let types: Vec<Vec<String>> = types.into_iter().map(|(_, lines)| lines).collect();
+    let text = render(file_header, &types);
+    fs::write(path, match format_with {
+        Some(command) => format_output(command, text),
+        None => text,
+    })?;
+
+    Ok(())
+}
+
+/// Pipes generated text through an external formatter reading its input from stdin, such as `rustfmt`
+/// or `prettier --parser typescript`. The command is split on whitespace, without any shell quoting.
+/// # Returns
+/// The formatted text, or the text as it was, with a warning, if the formatter can't be run or fails.
+fn format_output(command: &str, text: String) -> String {
+    let mut parts = command.split_whitespace();
+    let program = match parts.next() {
+        Some(program) => program,
+        None => return text,
+    };
+
+    let child = Command::new(program)
+        .args(parts)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .spawn();
+    let mut child = match child {
+        Ok(child) => child,
+        Err(error) => {
+            eprintln!("warning: can't run {}: {}, the output is left unformatted", program, error);
+            return text;
+        }
+    };
+
+    // Written from another thread, the formatter may fill its output before reading all of its input.
+    let mut stdin = child.stdin.take().expect("stdin is piped");
+    let input = text.clone();
+    let writer = thread::spawn(move || stdin.write_all(input.as_bytes()));
+
+    let output = child.wait_with_output();
+    let written = writer.join().is_ok_and(|result| result.is_ok());
+    match output {
+        Ok(output) if written && output.status.success() => String::from_utf8(output.stdout).unwrap_or(text),
+        Ok(output) => {
+            eprintln!("warning: {} failed with {}, the output is left unformatted", program, output.status);
+            text
+        }
+        Err(error) => {
+            eprintln!("warning: can't run {}: {}, the output is left unformatted", program, error);
+            text
+        }
+    }
+}
+
+/// Joins the types produced by the [Transformer] into the text of the output, the root type last.
+/// Types are separated by a blank line, as in [Transformer::transform_to_string].
+/// # Arguments
+/// * `file_header` text placed once before the types.
+/// * `types` lines of each type.
+fn render(file_header: Option<&str>, types: &[Vec<String>]) -> String {
+    let mut output = String::new();
+
+    if let Some(file_header) = file_header {
+        output.push_str(file_header);
+        output.push('\n');
+    }
+
+    let types: Vec<String> = types.iter()
+        .map(|object| object.iter().map(|string| format!("{}\n", string)).collect())
+        .collect();
+    output.push_str(&types.join("\n"));
+
+    output
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs};
+    use crate::HELP_MESSAGE;
+    use std::path::Path;
+    use std::process::Command;
+    use crate::lib::{Config, RunStatus, definition_help, diff_lines, dump_tokens, format_output, output_header, render, run, source_snippet, write_types};
+    use crate::lib::model::parse_options::ParseOptions;
+    use crate::lib::model::transform_config::{RUST_DEFINITION, TransformConfig};
+    use crate::lib::parser::lexer::Lexer;
+    use crate::lib::parser::tokenizer::Tokenizer;
+    use crate::lib::transformer::{PLACEHOLDERS, Transformer};
+    use crate::lib::validation::validate_root;
+
+    /// JSON documents, and the options they are generated with, whose generated Rust types must compile and deserialize them.
+    const ROUND_TRIP_FIXTURES: [(&str, &[&str]); 10] = [
+        (r#"{"f1": "value", "f2": true, "f3": 45.3, "f4": 12}"#, &[]),
+        (r#"{"userId": 1, "profile": {"displayName": "x", "tags": ["a", "b"]}, "scores": [[1.5, 2.0], [3.5]]}"#, &[]),
+        (r#"{"users": [{"name": "a", "age": 3}, {"name": "b", "age": 4}], "total": 2}"#, &[]),
+        (r#"[{"id": 1, "kind": {"name": "x"}}, {"id": 2, "kind": {"name": "y"}}]"#, &[]),
+        (r#"{"events": [{"type": "click", "x": 1, "y": 2}, {"type": "key-press", "key": "a"}, {"type": "click", "x": 3, "y": 4}]}"#, &["--discriminator=type"]),
+        (r#"{"userId": 1, "profile": {"displayName": "x", "tags": ["a"]}}"#, &["--value-conversions"]),
+        (r#"{"items": [{"active": "yes", "name": "a"}, {"active": "no", "name": "b"}]}"#, &["--bool-strings"]),
+        (r#"{"items": [{"price": "$19.99", "tax": "1.5"}, {"price": "5", "tax": "0.25"}]}"#, &["--decimal-strings"]),
+        (r#"{"id": 1, "owner": {"name": "a"}}"#, &["--parse-helper"]),
+        (r#"{"id": 1, "profile": {"displayName": "x", "address": {"city": "y"}}, "tags": [{"name": "a"}]}"#, &["--modules"]),
+    ];
+
+    fn args(args: &[&str]) -> impl Iterator<Item = String> {
+        args.iter().map(|arg| arg.to_string()).collect::<Vec<String>>().into_iter()
+    }
+
+    #[test]
+    fn definition_spellings() {
+        let spellings = [
+            args(&["json_parser", "--definition=rust", "file.json"]),
+            args(&["json_parser", "--definition", "rust", "file.json"]),
+            args(&["json_parser", "--definition", "=", "rust", "file.json"]),
+            args(&["json_parser", "--definition= \"rust\" ", "file.json"]),
+        ];
+
+        for spelling in spellings {
+            let config = Config::new(spelling).unwrap();
+
+            assert_eq!(config.transformer_config.type_definition, RUST_DEFINITION.type_definition);
+            assert_eq!(config.filenames, vec!["file.json"]);
+        }
+    }
+
+    #[test]
+    fn root_and_item_names() {
+        let config = Config::new(args(&["json_parser", "--definition=rust", "--root-name=Users", "--item-name", "User", "file.json"])).unwrap();
+
+        assert_eq!(config.root_name.as_deref(), Some("Users"));
+        assert_eq!(config.transformer_config.options.item_name.as_deref(), Some("User"));
+        assert_eq!(config.filenames, vec!["file.json"]);
+    }
+
+    #[test]
+    fn path_types() {
+        let config = Config::new(args(&["json_parser", "--definition=rust", "--path-type=user.zip=ZipCode", "--path-type", "users[].id=u64", "file.json"])).unwrap();
+        let path_overrides = &config.transformer_config.options.path_overrides;
+
+        assert_eq!(path_overrides.get("user.zip").map(String::as_str), Some("ZipCode"));
+        assert_eq!(path_overrides.get("users[].id").map(String::as_str), Some("u64"));
+        assert!(Config::new(args(&["json_parser", "--definition=rust", "--path-type=user.zip", "file.json"])).is_err());
+    }
+
+    #[test]
+    fn scalar_types() {
+        let config = Config::new(args(&["json_parser", "--definition=rust", "--int-type=i64", "--string-type", "Cow<str>", "file.json"])).unwrap();
+        let json = "{\"f1\": 1, \"f2\": [2], \"f3\": \"a\", \"f4\": true}";
+        let expected_result = vec![
+            vec![
+                "#[derive(Serialize, Deserialize, Debug)]",
+                "struct Root {",
+                "\tf1: i64,",
+                "\tf2: Vec<i64>,",
+                "\tf3: Cow<str>,",
+                "\tf4: bool,",
+                "}",
+            ],
+        ];
+
+        let tree = Tokenizer::new(Lexer::new(json).start_lex(), config.parse_options).start_tokenizer().unwrap();
+        let transformer = Transformer::new(config.transformer_config, tree, None).unwrap();
+
+        assert_eq!(transformer.start_transform(), expected_result);
+    }
+
+    #[test]
+    fn unknown_type() {
+        let config = Config::new(args(&["json_parser", "--definition=rust", "--unknown-type='Box<serde_json::Value>'", "--fallback", "file.json"])).unwrap();
+        let json = "{\"f1\": [1, \"a\"], \"f2\": 1}";
+        let expected_result = vec![
+            vec![
+                "#[derive(Serialize, Deserialize, Debug)]",
+                "struct Root {",
+                "\tf1: Vec<Box<serde_json::Value>>,",
+                "\tf2: i32,",
+                "}",
+            ],
+        ];
+
+        let tree = Tokenizer::new(Lexer::new(json).start_lex(), config.parse_options).start_tokenizer().unwrap();
+        let transformer = Transformer::new(config.transformer_config, tree, None).unwrap();
+
+        assert_eq!(transformer.start_transform(), expected_result);
+    }
+
+    #[test]
+    fn custom_header() {
+        let mut definition = toml::to_string(&RUST_DEFINITION).unwrap();
+        definition.insert_str(0, "header = \"import foo\"\n");
+        let config: TransformConfig = toml::from_str(&definition).unwrap();
+        let file_header = config.file_header.clone();
+
+        let tree = Tokenizer::new(Lexer::new("{\"f1\": {\"f2\": 1}}").start_lex(), ParseOptions::default()).start_tokenizer().unwrap();
+        let output = render(file_header.as_deref(), &Transformer::new(config, tree, None).unwrap().start_transform());
+
+        assert!(output.starts_with("import foo\n#[derive"));
+        assert_eq!(output.matches("import foo").count(), 1);
+    }
+
+    #[test]
+    #[cfg(not(feature = "remote-definition"))]
+    fn definition_url_needs_feature() {
+        let error = Config::new(args(&["json_parser", "--definition=https://example.com/rust.toml", "file.json"])).err().unwrap();
+
+        assert!(error.to_string().contains("remote-definition feature"));
+    }
+
+    #[test]
+    #[cfg(feature = "remote-definition")]
+    fn definition_from_url() {
+        use std::io::{Read, Write};
+        use std::net::TcpListener;
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("--definition=http://{}/rust.toml", listener.local_addr().unwrap());
+        let server = std::thread::spawn(move || {
+            let definition = toml::to_string(&RUST_DEFINITION).unwrap();
+            let (mut stream, _) = listener.accept().unwrap();
+            let _ = stream.read(&mut [0; 1024]).unwrap();
+            write!(stream, "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}", definition.len(), definition).unwrap();
+        });
+
+        let config = Config::new(args(&["json_parser", &url, "file.json"])).unwrap();
+        server.join().unwrap();
+
+        assert_eq!(config.transformer_config.type_definition, RUST_DEFINITION.type_definition);
+    }
+
+    #[test]
+    fn file_per_type() {
+        let config = Config::new(args(&["json_parser", "--definition=java", "--out-dir=out", "--file-header=package com.example;", "file.json"])).unwrap();
+        let dir = env::temp_dir().join("json_parser_file_per_type");
+        let _ = fs::remove_dir_all(&dir);
+
+        let tree = Tokenizer::new(Lexer::new("{\"f1\": 1, \"f4\": {\"f5\": \"a\"}}").start_lex(), config.parse_options).start_tokenizer().unwrap();
+        let file_header = config.transformer_config.file_header.clone();
+        let mut transformer = Transformer::new(config.transformer_config, tree, None).unwrap();
+        write_types(&dir, "java", file_header.as_deref(), None, transformer.start_transform_named()).unwrap();
+
+        let root = fs::read_to_string(dir.join("Root.java")).unwrap();
+        let f4 = fs::read_to_string(dir.join("F4.java")).unwrap();
+
+        assert!(root.starts_with("package com.example;\nclass Root {"));
+        assert!(root.contains("\tprivate final F4 f4;"));
+        assert!(!root.contains("import"));
+        assert!(f4.starts_with("package com.example;\nclass F4 {"));
+    }
+
+    #[test]
+    fn several_definitions() {
+        let dir = env::temp_dir().join("json_parser_several_definitions");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let input = dir.join("input.json");
+        fs::write(&input, "{\"f1\": 1, \"f2\": {\"f3\": true}}").unwrap();
+
+        let out_dir = format!("--out-dir={}", dir.join("out").display());
+        run(Config::new(args(&["json_parser", "--definition=rust,typescript,java", &out_dir, input.to_str().unwrap()])).unwrap()).unwrap();
+
+        let rust = fs::read_to_string(dir.join("out").join("root.rs")).unwrap();
+        let typescript = fs::read_to_string(dir.join("out").join("root.ts")).unwrap();
+
+        assert!(rust.contains("struct F2 {\n\tf3: bool,\n}\n\n#[derive(Serialize, Deserialize, Debug)]\nstruct Root {"));
+        assert!(typescript.contains("interface Root {"));
+        assert!(dir.join("out").join("Root.java").exists());
+        assert!(dir.join("out").join("F2.java").exists());
+        assert!(Config::new(args(&["json_parser", "--definition=rust,typescript", "file.json"])).is_err());
+    }
+
+    #[test]
+    fn output_dir() {
+        let dir = env::temp_dir().join("json_parser_output_dir");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let input = dir.join("input.json");
+        fs::write(&input, "{\"f1\": 1, \"f2\": {\"f3\": true}}").unwrap();
+
+        let output_arg = format!("--output={}", dir.join("out").display());
+        run(Config::new(args(&["json_parser", "--definition=java", &output_arg, input.to_str().unwrap()])).unwrap()).unwrap();
+
+        assert!(fs::read_to_string(dir.join("out").join("Root.java")).unwrap().contains("class Root {"));
+        assert!(fs::read_to_string(dir.join("out").join("F2.java")).unwrap().contains("class F2 {"));
+    }
+
+    #[test]
+    fn output_file() {
+        let dir = env::temp_dir().join("json_parser_output_file");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let input = dir.join("input.json");
+        let output = dir.join("types.rs");
+        fs::write(&input, "{\"f1\": 1, \"f2\": {\"f3\": true}}").unwrap();
+
+        let output_arg = format!("--output-file={}", output.display());
+        run(Config::new(args(&["json_parser", "--definition=rust", &output_arg, input.to_str().unwrap()])).unwrap()).unwrap();
+
+        assert_eq!(fs::read_to_string(&output).unwrap(), concat!(
+            "#[derive(Serialize, Deserialize, Debug)]\nstruct F2 {\n\tf3: bool,\n}\n\n",
+            "#[derive(Serialize, Deserialize, Debug)]\nstruct Root {\n\tf1: i32,\n\tf2: F2,\n}\n",
+        ));
+    }
+
+    #[test]
+    fn output_file_and_dir() {
+        assert!(Config::new(args(&["json_parser", "--definition=java", "--output-file=types.java", "--out-dir=out", "file.json"])).is_err());
+        assert!(Config::new(args(&["json_parser", "--definition=java", "--output=out", "--output-file=types.java", "file.json"])).is_err());
+    }
+
+    #[test]
+    fn nested_order_items() {
+        let dir = env::temp_dir().join("json_parser_nested_object_arrays");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let input = dir.join("orders.json");
+        let output = dir.join("types.rs");
+        fs::write(&input, concat!(
+            "{\"orders\": [",
+            "{\"id\": 1, \"order_items\": [{\"sku\": \"a\", \"qty\": 2}]},",
+            "{\"id\": 2, \"order_items\": [{\"sku\": \"b\", \"qty\": 1}, {\"sku\": \"c\", \"qty\": 3}]}",
+            "]}",
+        )).unwrap();
+
+        let output_arg = format!("--output-file={}", output.display());
+        run(Config::new(args(&["json_parser", "--definition=rust", &output_arg, input.to_str().unwrap()])).unwrap()).unwrap();
+
+        assert_eq!(fs::read_to_string(&output).unwrap(), concat!(
+            "#[derive(Serialize, Deserialize, Debug)]\nstruct OrderItem {\n\tsku: String,\n\tqty: i32,\n}\n\n",
+            "#[derive(Serialize, Deserialize, Debug)]\nstruct Order {\n\tid: i32,\n\torder_items: Vec<OrderItem>,\n}\n\n",
+            "#[derive(Serialize, Deserialize, Debug)]\nstruct Root {\n\torders: Vec<Order>,\n}\n",
+        ));
+
+        run(Config::new(args(&["json_parser", "--definition=rust", "--modules", &output_arg, input.to_str().unwrap()])).unwrap()).unwrap();
+
+        assert_eq!(fs::read_to_string(&output).unwrap(), concat!(
+            "mod order {\n\tuse super::*;\n",
+            "\tmod order_item {\n\t\tuse super::*;\n",
+            "\t\t#[derive(Serialize, Deserialize, Debug)]\n\t\tpub struct OrderItem {\n\t\t\tsku: String,\n\t\t\tqty: i32,\n\t\t}\n",
+            "\t}\n",
+            "\t#[derive(Serialize, Deserialize, Debug)]\n\tpub struct Order {\n\t\tid: i32,\n\t\torder_items: Vec<order_item::OrderItem>,\n\t}\n",
+            "}\n\n",
+            "#[derive(Serialize, Deserialize, Debug)]\nstruct Root {\n\torders: Vec<order::Order>,\n}\n",
+        ));
+    }
+
+    #[test]
+    fn syntax_error_snippet() {
+        let json = "{\n\t\"a\": 1,\n\t\"b\": true, false\n}";
+        let error = Tokenizer::new(Lexer::new(json).start_lex(), ParseOptions::default()).start_tokenizer().unwrap_err();
+        let (line, col) = error.position().unwrap();
+
+        assert_eq!(source_snippet(json, line, col), "  | \t\"b\": true, false\n  | \t           ^");
+    }
+
+    #[test]
+    fn stale_model_diff() {
+        let existing = "struct Root {\n\tf1: i32,\n\tf2: String,\n}\n";
+        let generated = "struct Root {\n\tf1: i32,\n\tf2: bool,\n\tf3: i32,\n}\n";
+
+        assert_eq!(diff_lines(existing, existing), None);
+
+        let (summary, lines) = diff_lines(existing, generated).unwrap();
+        assert_eq!(summary, "1 lines removed and 2 added from line 3");
+        assert_eq!(lines, vec!["-\tf2: String,", "+\tf2: bool,", "+\tf3: i32,"]);
+    }
+
+    #[test]
+    fn stale_model_status() {
+        let dir = env::temp_dir().join("json_parser_stale_model_status");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let input = dir.join("input.json");
+        let existing = dir.join("types.rs");
+        fs::write(&input, "{\"f1\": 1}").unwrap();
+        fs::write(&existing, "#[derive(Serialize, Deserialize, Debug)]\nstruct Root {\n\tf1: i32,\n}\n").unwrap();
+
+        let diff_arg = format!("--diff={}", existing.display());
+        let status = run(Config::new(args(&["json_parser", "--definition=rust", &diff_arg, input.to_str().unwrap()])).unwrap()).unwrap();
+        assert_eq!(status, RunStatus::Done);
+
+        fs::write(&input, "{\"f1\": 1, \"f2\": true}").unwrap();
+        let status = run(Config::new(args(&["json_parser", "--definition=rust", &diff_arg, input.to_str().unwrap()])).unwrap()).unwrap();
+        assert_eq!(status, RunStatus::OutOfDate);
+    }
+
+    #[test]
+    fn invalid_input_status() {
+        let dir = env::temp_dir().join("json_parser_invalid_input_status");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let input = dir.join("input.json");
+        let output = dir.join("types.rs");
+        let output_arg = format!("--output-file={}", output.display());
+
+        fs::write(&input, "{\"userID\": 1, \"user_id\": 2}").unwrap();
+        let status = run(Config::new(args(&["json_parser", "--definition=rust", &output_arg, input.to_str().unwrap()])).unwrap()).unwrap();
+        assert_eq!(status, RunStatus::Invalid);
+        assert!(output.exists());
+
+        fs::write(&input, "{\"userID\": 1}").unwrap();
+        let status = run(Config::new(args(&["json_parser", "--definition=rust", &output_arg, input.to_str().unwrap()])).unwrap()).unwrap();
+        assert_eq!(status, RunStatus::Done);
+    }
+
+    #[test]
+    fn diff_needs_single_output() {
+        assert!(Config::new(args(&["json_parser", "--definition=rust", "--diff=types.rs", "--out-dir=out", "file.json"])).is_err());
+        assert!(Config::new(args(&["json_parser", "--definition=rust", "--diff=types.rs", "--output=out", "file.json"])).is_err());
+        assert!(Config::new(args(&["json_parser", "--definition=rust,typescript", "--diff=types.rs", "--out-dir=out", "file.json"])).is_err());
+    }
+
+    #[test]
+    fn banner() {
+        let config = Config::new(args(&["json_parser", "--definition=kotlin", "--banner", "--file-header=package com.example", "file.json"])).unwrap();
+        let dir = env::temp_dir().join("json_parser_banner");
+        let _ = fs::remove_dir_all(&dir);
+
+        let file_header = output_header(&config.transformer_config);
+        let tree = Tokenizer::new(Lexer::new("{\"f1\": {\"f2\": 1}}").start_lex(), config.parse_options).start_tokenizer().unwrap();
+        let mut transformer = Transformer::new(config.transformer_config, tree, None).unwrap();
+        write_types(&dir, "kt", file_header.as_deref(), None, transformer.start_transform_named()).unwrap();
+
+        for file in ["Root.kt", "F1.kt"] {
+            let output = fs::read_to_string(dir.join(file)).unwrap();
+            assert!(output.starts_with("// Generated by json_parser, do not edit.\npackage com.example\n"));
+        }
+
+        let config = Config::new(args(&["json_parser", "--definition=kotlin", "file.json"])).unwrap();
+        assert_eq!(output_header(&config.transformer_config), None);
+    }
+
+    #[test]
+    fn token_dump() {
+        let tokens = Lexer::new("{\n\"a\": \"b\"}").start_lex();
+        let expected_result = "1:1 ObjectStart\n2:1 Name(\"a\")\n2:4 Colon\n2:6 Value(String(\"b\"))\n2:9 ObjectEnd\n";
+
+        assert_eq!(dump_tokens(&tokens), expected_result);
+    }
+
+    #[test]
+    fn token_dump_name_column() {
+        let tokens = Lexer::new("{\"a\": 1,\n\t\"bc\": true}").start_lex();
+        let expected_result = "1:1 ObjectStart\n1:2 Name(\"a\")\n1:5 Colon\n1:7 Value(Int)\n1:8 Comma\n2:2 Name(\"bc\")\n2:6 Colon\n2:8 Value(Bool)\n2:12 ObjectEnd\n";
+
+        assert_eq!(dump_tokens(&tokens), expected_result);
+    }
+
+    #[test]
+    fn token_dump_string_column() {
+        let tokens = Lexer::new("{\"a\":[1],\"b\":\"x\"}").start_lex();
+        let dump = dump_tokens(&tokens);
+
+        assert!(dump.contains("1:14 Value(String(\"x\"))\n"), "{}", dump);
+    }
+
+    #[test]
+    fn empty_field_name() {
+        let tree = Tokenizer::new(Lexer::new("{\"\": 1}").start_lex(), ParseOptions::default()).start_tokenizer().unwrap();
+        let diagnostics = validate_root(&tree, &RUST_DEFINITION, None);
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].to_string(), "error: (root): empty field name");
+    }
+
+    #[test]
+    fn tree_dump() {
+        let json = "{\"f1\": \"a\", \"f2\": {\"f3\": 1.5, \"f4\": [{\"f5\": true}]}, \"f6\": [[1]]}";
+        let tree = Tokenizer::new(Lexer::new(json).start_lex(), ParseOptions::default()).start_tokenizer().unwrap();
+        let expected_result = concat!(
+            "object\n",
+            "  f1: string [\"a\"]\n",
+            "  f2: object\n",
+            "    f3: float\n",
+            "    f4: array of object\n",
+            "      f5: bool\n",
+            "  f6: array of array of int\n",
+        );
+
+        assert_eq!(tree.to_string(), expected_result);
+    }
+
+    #[test]
+    fn key_list() {
+        let json = "{\"b\": 1, \"a\": {\"c\": true, \"b\": [{\"d\": 1.5, \"a\": \"x\"}]}, \"e\": [[{\"c\": 2}]]}";
+        let tree = Tokenizer::new(Lexer::new(json).start_lex(), ParseOptions::default()).start_tokenizer().unwrap();
+
+        assert_eq!(tree.key_names(), vec!["a", "b", "c", "d", "e"]);
+    }
+
+    #[test]
+    fn escaped_key_list() {
+        let json = r#"{"a\"b": 1, "c": 2, "d\u0041": {"e\\f": true}}"#;
+        let tree = Tokenizer::new(Lexer::new(json).start_lex(), ParseOptions::default()).start_tokenizer().unwrap();
+
+        assert_eq!(tree.key_names(), vec!["a\"b", "c", "dA", "e\\f"]);
+    }
+
+    #[test]
+    fn formatted_output() {
+        let json = "{\"f1\": 1, \"f2\": [\"a\"]}";
+        let tree = Tokenizer::new(Lexer::new(json).start_lex(), ParseOptions::default()).start_tokenizer().unwrap();
+        let output = render(None, &Transformer::new(RUST_DEFINITION, tree, None).unwrap().start_transform());
+
+        assert_eq!(format_output("json_parser_missing_formatter", output.clone()), output);
+
+        if Command::new("rustfmt").arg("--version").output().is_ok() {
+            let expected_result = "#[derive(Serialize, Deserialize, Debug)]\nstruct Root {\n    f1: i32,\n    f2: Vec<String>,\n}\n";
+            assert_eq!(format_output("rustfmt --edition 2021", output), expected_result);
+        }
+    }
+
+    #[test]
+    fn empty_definition() {
+        let error = Config::new(args(&["json_parser", "--definition=", "file.json"])).err().unwrap();
+
+        assert_eq!(error.to_string(), "empty value for --definition");
+    }
+
+    #[test]
+    fn definition_help_lists_placeholders() {
+        let help = definition_help().unwrap();
+
+        for placeholder in PLACEHOLDERS.iter() {
+            assert!(help.contains(&format!("{:<48}{}", placeholder.field, placeholder.placeholder)));
+        }
+
+        let example = help.split_once("(built-in rust):\n").unwrap().1;
+        assert!(toml::from_str::<TransformConfig>(example).is_ok());
+    }
+
+    #[test]
+    fn help_options_sorted() {
+        let options: Vec<&str> = HELP_MESSAGE.lines()
+            .filter_map(|line| line.strip_prefix("    --"))
+            .map(|line| line.split(|c: char| c == '=' || c.is_whitespace()).next().unwrap())
+            .collect();
+        let mut sorted = options.clone();
+        sorted.sort();
+
+        assert_eq!(options, sorted);
+    }
+
+    #[test]
+    fn missing_definition_value() {
+        assert!(Config::new(args(&["json_parser", "file.json", "--definition"])).is_err());
+    }
+
+    fn generate_rust(json: &str, options: &[&str]) -> String {
+        let config = Config::new(args(&[&["json_parser", "--definition=rust"], options, &["file.json"]].concat())).unwrap();
+        let tokenizer = Tokenizer::new(Lexer::new(json).start_lex(), config.parse_options);
+        let transformer = Transformer::new(config.transformer_config, tokenizer.start_tokenizer().unwrap(), None).unwrap();
+
+        transformer.start_transform().iter().flatten().map(|line| format!("{}\n", line)).collect()
+    }
+
+    #[test]
+    #[ignore = "builds the generated types with cargo, which needs the serde crates, run with --ignored"]
+    fn generated_rust_round_trips() {
+        let crate_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("target").join("round_trip");
+        fs::create_dir_all(crate_dir.join("src")).unwrap();
+        fs::write(crate_dir.join("Cargo.toml"), concat!(
+            "[package]\nname = \"round_trip\"\nversion = \"0.1.0\"\nedition = \"2021\"\n\n",
+            "[dependencies]\nserde = { version = \"1.0\", features = [\"derive\"] }\nserde_json = \"1.0\"\nrust_decimal = \"1\"\n\n",
+            "[workspace]\n",
+        )).unwrap();
+
+        let mut main = String::from("#![allow(dead_code)]\n\n");
+        for (i, (json, options)) in ROUND_TRIP_FIXTURES.iter().enumerate() {
+            main.push_str(&format!("mod fixture_{} {{\nuse serde::{{Deserialize, Serialize}};\n\n{}\n", i, generate_rust(json, options)));
+            main.push_str(&format!("pub fn check() {{\n\tserde_json::from_str::<Root>(r##\"{}\"##).unwrap();\n}}\n}}\n\n", json));
+        }
+        main.push_str("fn main() {\n");
+        for i in 0..ROUND_TRIP_FIXTURES.len() {
+            main.push_str(&format!("\tfixture_{}::check();\n", i));
+        }
+        main.push_str("}\n");
+        fs::write(crate_dir.join("src").join("main.rs"), main).unwrap();
+
+        let output = Command::new(env::var("CARGO").unwrap_or_else(|_| "cargo".to_owned()))
+            .args(["run", "--quiet"])
+            .current_dir(&crate_dir)
+            .output()
+            .unwrap();
+
+        assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    }
+}
@@ -1,3 +1,4 @@
 pub mod token;
 pub mod tree;
-pub mod transform_config;
\ No newline at end of file
+pub mod transform_config;
+pub mod parse_options;
\ No newline at end of file
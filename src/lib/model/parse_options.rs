@@ -0,0 +1,34 @@
+/// Options changing how the JSON source is read by the lexer and the tokenizer.
+#[derive(Debug, Default, Clone)]
+pub struct ParseOptions {
+    /// Arrays mixing incompatible element types get an unknown element type instead of failing.
+    pub fallback: bool,
+    /// The source may hold several root objects back to back, which are merged into one.
+    /// Fields missing from some of them become optional.
+    pub concatenated: bool,
+    /// Field telling apart the kinds of objects held by an array. Objects are merged by the value of this field
+    /// instead of all together.
+    pub discriminator: Option<String>,
+    /// Arrays are skipped and get an unknown element type, without inferring the type of their elements.
+    pub shallow_arrays: bool,
+    /// Arrays may hold nulls among values of another type, their element type becomes optional.
+    pub null_elements: bool,
+    /// Fields may hold null, they become optional and take the type of their values in the other objects merged with them.
+    /// Fields only seen holding null get the unknown type.
+    pub null_fields: bool,
+    /// The number of elements of arrays is kept, arrays always holding the same number of elements
+    /// get a fixed-size array type.
+    pub fixed_arrays: bool,
+    /// Arrays whose elements don't share a type are kept by position and become tuples,
+    /// as long as every array of the field has the same types in the same positions.
+    pub tuples: bool,
+    /// The `Infinity`, `-Infinity` and `NaN` literals written by some encoders, such as Python's `json.dumps`,
+    /// are read as floats.
+    pub allow_nan: bool,
+    /// A comma directly between two digits of an object value is read as a decimal point, as in `{"x":1,5}`
+    /// written by some locales. Ambiguous, since the comma also separates fields.
+    pub decimal_comma: bool,
+    /// Objects whose keys are all integers become maps from integers to their merged values.
+    /// Objects keyed by `0, 1, 2...` are left as they are.
+    pub integer_maps: bool,
+}
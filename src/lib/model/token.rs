@@ -1,3 +1,5 @@
+use std::ops::Range;
+
 #[derive(Debug, Eq, PartialEq)]
 pub enum JsonToken {
     ObjectStart,
@@ -8,6 +10,8 @@ pub enum JsonToken {
     Comma,
     Name(String),
     Value(JsonType),
+    /// Character found after the root value that can't start any other token, kept to report trailing content.
+    Unexpected(char),
 }
 
 #[derive(Debug, Eq, PartialEq)]
@@ -15,7 +19,8 @@ pub enum JsonType {
     Int,
     Float,
     Bool,
-    String,
+    /// Holds the unescaped content of the string.
+    String(String),
     Null
 }
 
@@ -24,4 +29,6 @@ pub struct Token {
     pub line: usize,
     pub col: usize,
     pub value: JsonToken,
+    /// Byte range of the literal in the source, for number values.
+    pub span: Option<Range<usize>>,
 }
@@ -1,31 +1,130 @@
 use std::borrow::Cow;
+use std::collections::BTreeMap;
 use serde::{Serialize, Deserialize};
 
 pub const RUST_DEFINITION: TransformConfig = TransformConfig {
-    type_definition: Cow::Borrowed("#[derive(Serialize, Deserialize, Debug)]\nstruct {object_name} {"),
+    type_definition: Cow::Borrowed("#[derive(Serialize, Deserialize, Debug)]\n{visibility}struct {object_name} {"),
+    unit_definition: Some(Cow::Borrowed("#[derive(Serialize, Deserialize, Debug)]\n{visibility}struct {object_name};")),
+    type_attributes: Vec::new(),
+    declaration_attributes: Vec::new(),
+    root_attributes: Vec::new(),
+    banner: Some(Cow::Borrowed("// Generated by json_parser, do not edit.")),
+    file_header: None,
+    dead_code_attribute: Some(Cow::Borrowed("#[allow(dead_code)]")),
+    deny_unknown_fields_attribute: Some(Cow::Borrowed("#[serde(deny_unknown_fields)]")),
+    file_extension: None,
+    single_file_extension: Some(Cow::Borrowed("rs")),
+    acronyms: Vec::new(),
+    visibility: None,
+    readonly_modifier: None,
     field_definition: Cow::Borrowed("\t{field_name}: {field_type},"),
+    optional_field_definition: None,
+    readonly_optional_field_definition: None,
     name_change_annotation: Cow::Borrowed("\t#[serde(rename = \"{name}\")]"),
+    source_comment: Some(Cow::Borrowed("\t// from: {sources}")),
+    doc_comment: Some(Cow::Borrowed("\t/// {doc}")),
     array_definition: Cow::Borrowed("Vec<{field_type}>"),
+    untyped_arrays: false,
+    fixed_array_definition: Some(Cow::Borrowed("[{field_type}; {len}]")),
+    map_definition: Some(Cow::Borrowed("HashMap<{key_type}, {field_type}>")),
+    tuple_definition: Some(Cow::Borrowed("({field_types})")),
+    alias_definition: Some(Cow::Borrowed("type {object_name} = {field_type};")),
+    parse_helper: Some(Cow::Borrowed("impl {object_name} {\n\tpub fn from_json(s: &str) -> serde_json::Result<Self> {\n\t\tserde_json::from_str(s)\n\t}\n}")),
     block_end: Cow::Borrowed("}"),
     int_type: Cow::Borrowed("i32"),
     float_type: Cow::Borrowed("f32"),
     bool_type: Cow::Borrowed("bool"),
     string_type: Cow::Borrowed("String"),
+    bytes_type: Some(Cow::Borrowed("Vec<u8>")),
+    bytes_annotation: Some(Cow::Borrowed("\t#[serde(with = \"base64\")]")),
+    bool_string_annotation: Some(Cow::Borrowed("\t#[serde(default, deserialize_with = \"bool_from_string\")]")),
+    bool_string_helper: Some(Cow::Borrowed("fn bool_from_string<'de, D: serde::Deserializer<'de>, T: From<bool> + Default>(deserializer: D) -> Result<T, D::Error> {\n\tmatch Option::<String>::deserialize(deserializer)? {\n\t\tNone => Ok(T::default()),\n\t\tSome(value) => match value.to_ascii_lowercase().as_str() {\n\t\t\t\"true\" | \"yes\" | \"on\" | \"y\" => Ok(true.into()),\n\t\t\t\"false\" | \"no\" | \"off\" | \"n\" => Ok(false.into()),\n\t\t\t_ => Err(serde::de::Error::custom(format!(\"expected a boolean string, found {}\", value))),\n\t\t},\n\t}\n}")),
+    decimal_type: Some(Cow::Borrowed("rust_decimal::Decimal")),
+    decimal_string_annotation: Some(Cow::Borrowed("\t#[serde(default, deserialize_with = \"decimal_from_string\")]")),
+    decimal_string_helper: Some(Cow::Borrowed("fn decimal_from_string<'de, D: serde::Deserializer<'de>, T: From<rust_decimal::Decimal> + Default>(deserializer: D) -> Result<T, D::Error> {\n\tmatch Option::<String>::deserialize(deserializer)? {\n\t\tNone => Ok(T::default()),\n\t\tSome(value) => {\n\t\t\tlet number = value.strip_prefix(['$', '€', '£', '¥']).unwrap_or(&value);\n\t\t\tnumber.parse::<rust_decimal::Decimal>().map(T::from).map_err(serde::de::Error::custom)\n\t\t}\n\t}\n}")),
+    unknown_type: Some(Cow::Borrowed("serde_json::Value")),
+    nullable_unknown_type: false,
+    empty_array_type: None,
+    object_map_type: Some(Cow::Borrowed("HashMap<String, serde_json::Value>")),
+    optional_definition: Some(Cow::Borrowed("Option<{field_type}>")),
     constructor: None,
     case_type: CaseType::SnakeCase,
     object_case_type: CaseType::UpperCamelCase,
+    enum_definition: Some(EnumConfig {
+        definition: Cow::Borrowed("#[derive(Serialize, Deserialize, Debug)]\n{visibility}enum {object_name} {"),
+        variant_definition: Cow::Borrowed("\t#[serde(rename = \"{name}\")]\n\t{variant},"),
+        end: Cow::Borrowed("}"),
+    }),
+    conversion: Some(ConversionConfig {
+        definition: Cow::Borrowed("impl TryFrom<serde_json::Value> for {object_name} {\n\ttype Error = serde_json::Error;\n\n\tfn try_from(value: serde_json::Value) -> Result<Self, Self::Error> {\n\t\tOk(Self {"),
+        field_definition: Cow::Borrowed("\t\t\t{field_name}: serde_json::from_value(value[\"{name}\"].clone())?,"),
+        end: Cow::Borrowed("\t\t})\n\t}\n}"),
+    }),
+    sealed_definition: Some(SealedConfig {
+        definition: Cow::Borrowed("#[derive(Serialize, Deserialize, Debug)]\n#[serde(tag = \"{name}\")]\n{visibility}enum {object_name} {"),
+        variant_definition: Some(Cow::Borrowed("\t#[serde(rename = \"{name}\")]\n\t{variant}({variant}),")),
+        end: Some(Cow::Borrowed("}")),
+        variant_end: None,
+    }),
+    builder: Some(BuilderConfig {
+        attribute: Cow::Borrowed("#[derive(Default, Builder)]\n#[builder(default)]"),
+        field_definition: Cow::Borrowed("\tpub {field_name}: {field_type},"),
+    }),
+    module_definition: Some(ModuleConfig {
+        definition: Cow::Borrowed("mod {name} {\n\tuse super::*;"),
+        end: Cow::Borrowed("}"),
+        reference: Cow::Borrowed("{name}::{object_name}"),
+        visibility: Cow::Borrowed("pub"),
+    }),
+    nested_definition: None,
+    options: TransformOptions::DEFAULT,
 };
 
 pub const JAVA_DEFINITION: TransformConfig = TransformConfig {
-    type_definition: Cow::Borrowed("class {object_name} {"),
+    type_definition: Cow::Borrowed("{visibility}class {object_name} {"),
+    unit_definition: None,
+    type_attributes: Vec::new(),
+    declaration_attributes: Vec::new(),
+    root_attributes: Vec::new(),
+    banner: Some(Cow::Borrowed("// Generated by json_parser, do not edit.")),
+    file_header: None,
+    dead_code_attribute: None,
+    deny_unknown_fields_attribute: None,
+    file_extension: Some(Cow::Borrowed("java")),
+    single_file_extension: None,
+    acronyms: Vec::new(),
+    visibility: None,
+    readonly_modifier: None,
     field_definition: Cow::Borrowed("\tprivate final {field_type} {field_name};"),
+    optional_field_definition: None,
+    readonly_optional_field_definition: None,
     name_change_annotation: Cow::Borrowed("\t@SerializedName(value = \"{name}\")"),
+    source_comment: Some(Cow::Borrowed("\t// from: {sources}")),
+    doc_comment: Some(Cow::Borrowed("\t/** {doc} */")),
     array_definition: Cow::Borrowed("{field_type}[]"),
+    untyped_arrays: false,
+    fixed_array_definition: None,
+    map_definition: Some(Cow::Borrowed("Map<Integer, {field_type}>")),
+    tuple_definition: None,
+    alias_definition: None,
+    parse_helper: None,
     block_end: Cow::Borrowed("}"),
     int_type: Cow::Borrowed("int"),
     float_type: Cow::Borrowed("double"),
     bool_type: Cow::Borrowed("boolean"),
     string_type: Cow::Borrowed("String"),
+    bytes_type: Some(Cow::Borrowed("byte[]")),
+    bytes_annotation: None,
+    bool_string_annotation: None,
+    bool_string_helper: None,
+    decimal_type: None,
+    decimal_string_annotation: None,
+    decimal_string_helper: None,
+    unknown_type: Some(Cow::Borrowed("Object")),
+    nullable_unknown_type: false,
+    empty_array_type: None,
+    object_map_type: Some(Cow::Borrowed("Map<String, Object>")),
+    optional_definition: None,
     case_type: CaseType::CamelCase,
     object_case_type: CaseType::UpperCamelCase,
     constructor: Some(
@@ -40,66 +139,521 @@ pub const JAVA_DEFINITION: TransformConfig = TransformConfig {
             })
         }
     ),
+    enum_definition: Some(EnumConfig {
+        definition: Cow::Borrowed("{visibility}enum {object_name} {"),
+        variant_definition: Cow::Borrowed("\t@SerializedName(\"{name}\")\n\t{variant},"),
+        end: Cow::Borrowed("}"),
+    }),
+    conversion: None,
+    sealed_definition: None,
+    builder: None,
+    module_definition: None,
+    nested_definition: Some(NestedConfig {
+        type_definition: Some(Cow::Borrowed("{visibility}static class {object_name} {")),
+        body_start: None,
+        end: None,
+    }),
+    options: TransformOptions::DEFAULT,
 };
 
 pub const DART_DEFINITION: TransformConfig = TransformConfig {
     type_definition: Cow::Borrowed("class {object_name} {"),
+    unit_definition: None,
+    type_attributes: Vec::new(),
+    declaration_attributes: Vec::new(),
+    root_attributes: Vec::new(),
+    banner: Some(Cow::Borrowed("// Generated by json_parser, do not edit.")),
+    file_header: None,
+    dead_code_attribute: None,
+    deny_unknown_fields_attribute: None,
+    file_extension: None,
+    single_file_extension: Some(Cow::Borrowed("dart")),
+    acronyms: Vec::new(),
+    visibility: None,
+    readonly_modifier: None,
     field_definition: Cow::Borrowed("\tfinal {field_type}? {field_name};"),
+    optional_field_definition: None,
+    readonly_optional_field_definition: None,
     name_change_annotation: Cow::Borrowed("\t@JsonKey(name: '{name}')"),
+    source_comment: Some(Cow::Borrowed("\t// from: {sources}")),
+    doc_comment: Some(Cow::Borrowed("\t/// {doc}")),
     array_definition: Cow::Borrowed("List<{field_type}>"),
+    untyped_arrays: false,
+    fixed_array_definition: None,
+    map_definition: Some(Cow::Borrowed("Map<{key_type}, {field_type}>")),
+    tuple_definition: None,
+    alias_definition: Some(Cow::Borrowed("typedef {object_name} = {field_type};")),
+    parse_helper: None,
     block_end: Cow::Borrowed("}"),
     int_type: Cow::Borrowed("int"),
     float_type: Cow::Borrowed("double"),
     bool_type: Cow::Borrowed("bool"),
     string_type: Cow::Borrowed("String"),
+    bytes_type: Some(Cow::Borrowed("List<int>")),
+    bytes_annotation: None,
+    bool_string_annotation: None,
+    bool_string_helper: None,
+    decimal_type: None,
+    decimal_string_annotation: None,
+    decimal_string_helper: None,
+    unknown_type: Some(Cow::Borrowed("dynamic")),
+    nullable_unknown_type: false,
+    empty_array_type: None,
+    object_map_type: Some(Cow::Borrowed("Map<String, dynamic>")),
+    optional_definition: None,
     case_type: CaseType::CamelCase,
     object_case_type: CaseType::UpperCamelCase,
     constructor: Some(
         ConstructorConfig {
         definition: Cow::Borrowed("\t{object_name}({{arguments}\n\t});"),
         argument_definition: Cow::Borrowed("\n\t\tthis.{name}"),
-        separator: Cow::Borrowed("), "),
+        separator: Cow::Borrowed(","),
         separator_at_end: true,
         field_definition: None,
-    })
+    }),
+    enum_definition: Some(EnumConfig {
+        definition: Cow::Borrowed("enum {object_name} {"),
+        variant_definition: Cow::Borrowed("\t@JsonValue('{name}')\n\t{variant},"),
+        end: Cow::Borrowed("}"),
+    }),
+    conversion: None,
+    sealed_definition: None,
+    builder: None,
+    module_definition: None,
+    nested_definition: None,
+    options: TransformOptions::DEFAULT,
 };
 
 pub const KOTLIN_DEFINITION: TransformConfig = TransformConfig {
-    type_definition: Cow::Borrowed("data class {object_name} ("),
+    type_definition: Cow::Borrowed("{visibility}data class {object_name} ("),
+    unit_definition: None,
+    type_attributes: Vec::new(),
+    declaration_attributes: Vec::new(),
+    root_attributes: Vec::new(),
+    banner: Some(Cow::Borrowed("// Generated by json_parser, do not edit.")),
+    file_header: None,
+    dead_code_attribute: None,
+    deny_unknown_fields_attribute: None,
+    file_extension: Some(Cow::Borrowed("kt")),
+    single_file_extension: None,
+    acronyms: Vec::new(),
+    visibility: None,
+    readonly_modifier: None,
     field_definition: Cow::Borrowed("\tval {field_name}: {field_type},"),
+    optional_field_definition: None,
+    readonly_optional_field_definition: None,
     name_change_annotation: Cow::Borrowed("\t@JsonKey(name: '{name}')"),
+    source_comment: Some(Cow::Borrowed("\t// from: {sources}")),
+    doc_comment: Some(Cow::Borrowed("\t/** {doc} */")),
     array_definition: Cow::Borrowed("{field_type}[]"),
+    untyped_arrays: false,
+    fixed_array_definition: None,
+    map_definition: Some(Cow::Borrowed("Map<{key_type}, {field_type}>")),
+    tuple_definition: None,
+    alias_definition: Some(Cow::Borrowed("typealias {object_name} = {field_type}")),
+    parse_helper: None,
     block_end: Cow::Borrowed(");"),
     int_type: Cow::Borrowed("int"),
     float_type: Cow::Borrowed("double"),
     bool_type: Cow::Borrowed("bool"),
     string_type: Cow::Borrowed("String"),
+    bytes_type: Some(Cow::Borrowed("ByteArray")),
+    bytes_annotation: None,
+    bool_string_annotation: None,
+    bool_string_helper: None,
+    decimal_type: None,
+    decimal_string_annotation: None,
+    decimal_string_helper: None,
+    unknown_type: Some(Cow::Borrowed("Any")),
+    nullable_unknown_type: false,
+    empty_array_type: None,
+    object_map_type: Some(Cow::Borrowed("Map<String, Any>")),
+    optional_definition: Some(Cow::Borrowed("{field_type}?")),
+    case_type: CaseType::CamelCase,
+    object_case_type: CaseType::UpperCamelCase,
+    constructor: None,
+    enum_definition: Some(EnumConfig {
+        definition: Cow::Borrowed("{visibility}enum class {object_name} {"),
+        variant_definition: Cow::Borrowed("\t@SerializedName(\"{name}\")\n\t{variant},"),
+        end: Cow::Borrowed("}"),
+    }),
+    conversion: None,
+    sealed_definition: Some(SealedConfig {
+        definition: Cow::Borrowed("{visibility}sealed interface {object_name}"),
+        variant_definition: None,
+        end: None,
+        variant_end: Some(Cow::Borrowed(") : {object_name}")),
+    }),
+    builder: None,
+    module_definition: None,
+    nested_definition: Some(NestedConfig {
+        type_definition: None,
+        body_start: Some(Cow::Borrowed(") {")),
+        end: Some(Cow::Borrowed("}")),
+    }),
+    options: TransformOptions::DEFAULT,
+};
+
+pub const KOTLIN_SERIALIZABLE_DEFINITION: TransformConfig = TransformConfig {
+    type_definition: Cow::Borrowed("@Serializable\n{visibility}data class {object_name} ("),
+    unit_definition: None,
+    type_attributes: Vec::new(),
+    declaration_attributes: Vec::new(),
+    root_attributes: Vec::new(),
+    banner: Some(Cow::Borrowed("// Generated by json_parser, do not edit.")),
+    file_header: Some(Cow::Borrowed("import kotlinx.serialization.SerialName\nimport kotlinx.serialization.Serializable\nimport kotlinx.serialization.json.JsonElement")),
+    dead_code_attribute: None,
+    deny_unknown_fields_attribute: None,
+    file_extension: Some(Cow::Borrowed("kt")),
+    single_file_extension: None,
+    acronyms: Vec::new(),
+    visibility: None,
+    readonly_modifier: None,
+    field_definition: Cow::Borrowed("\tval {field_name}: {field_type},"),
+    optional_field_definition: Some(Cow::Borrowed("\tval {field_name}: {field_type} = null,")),
+    readonly_optional_field_definition: None,
+    name_change_annotation: Cow::Borrowed("\t@SerialName(\"{name}\")"),
+    source_comment: Some(Cow::Borrowed("\t// from: {sources}")),
+    doc_comment: Some(Cow::Borrowed("\t/** {doc} */")),
+    array_definition: Cow::Borrowed("List<{field_type}>"),
+    untyped_arrays: false,
+    fixed_array_definition: None,
+    map_definition: Some(Cow::Borrowed("Map<{key_type}, {field_type}>")),
+    tuple_definition: None,
+    alias_definition: Some(Cow::Borrowed("typealias {object_name} = {field_type}")),
+    parse_helper: None,
+    block_end: Cow::Borrowed(")"),
+    int_type: Cow::Borrowed("Long"),
+    float_type: Cow::Borrowed("Double"),
+    bool_type: Cow::Borrowed("Boolean"),
+    string_type: Cow::Borrowed("String"),
+    bytes_type: None,
+    bytes_annotation: None,
+    bool_string_annotation: None,
+    bool_string_helper: None,
+    decimal_type: None,
+    decimal_string_annotation: None,
+    decimal_string_helper: None,
+    unknown_type: Some(Cow::Borrowed("JsonElement")),
+    nullable_unknown_type: false,
+    empty_array_type: None,
+    object_map_type: Some(Cow::Borrowed("Map<String, JsonElement>")),
+    optional_definition: Some(Cow::Borrowed("{field_type}?")),
+    case_type: CaseType::CamelCase,
+    object_case_type: CaseType::UpperCamelCase,
+    constructor: None,
+    enum_definition: Some(EnumConfig {
+        definition: Cow::Borrowed("@Serializable\n{visibility}enum class {object_name} {"),
+        variant_definition: Cow::Borrowed("\t@SerialName(\"{name}\")\n\t{variant},"),
+        end: Cow::Borrowed("}"),
+    }),
+    conversion: None,
+    sealed_definition: Some(SealedConfig {
+        definition: Cow::Borrowed("@Serializable\n{visibility}sealed interface {object_name}"),
+        variant_definition: None,
+        end: None,
+        variant_end: Some(Cow::Borrowed(") : {object_name}")),
+    }),
+    builder: None,
+    module_definition: None,
+    nested_definition: Some(NestedConfig {
+        type_definition: None,
+        body_start: Some(Cow::Borrowed(") {")),
+        end: Some(Cow::Borrowed("}")),
+    }),
+    options: TransformOptions::DEFAULT,
+};
+
+pub const TYPESCRIPT_DEFINITION: TransformConfig = TransformConfig {
+    type_definition: Cow::Borrowed("{visibility}interface {object_name} {"),
+    unit_definition: None,
+    type_attributes: Vec::new(),
+    declaration_attributes: Vec::new(),
+    root_attributes: Vec::new(),
+    banner: Some(Cow::Borrowed("// Generated by json_parser, do not edit.")),
+    file_header: None,
+    dead_code_attribute: None,
+    deny_unknown_fields_attribute: None,
+    file_extension: None,
+    single_file_extension: Some(Cow::Borrowed("ts")),
+    acronyms: Vec::new(),
+    visibility: Some(Cow::Borrowed("export")),
+    readonly_modifier: Some(Cow::Borrowed("readonly")),
+    field_definition: Cow::Borrowed("\t{modifier}{field_name}: {field_type};"),
+    optional_field_definition: Some(Cow::Borrowed("\t{modifier}{field_name}?: {field_type};")),
+    readonly_optional_field_definition: None,
+    name_change_annotation: Cow::Borrowed("\t/** {name} */"),
+    source_comment: Some(Cow::Borrowed("\t// from: {sources}")),
+    doc_comment: Some(Cow::Borrowed("\t/** {doc} */")),
+    array_definition: Cow::Borrowed("{field_type}[]"),
+    untyped_arrays: false,
+    fixed_array_definition: None,
+    map_definition: Some(Cow::Borrowed("Record<{key_type}, {field_type}>")),
+    tuple_definition: Some(Cow::Borrowed("[{field_types}]")),
+    alias_definition: Some(Cow::Borrowed("{visibility}type {object_name} = {field_type};")),
+    parse_helper: None,
+    block_end: Cow::Borrowed("}"),
+    int_type: Cow::Borrowed("number"),
+    float_type: Cow::Borrowed("number"),
+    bool_type: Cow::Borrowed("boolean"),
+    string_type: Cow::Borrowed("string"),
+    bytes_type: None,
+    bytes_annotation: None,
+    bool_string_annotation: None,
+    bool_string_helper: None,
+    decimal_type: None,
+    decimal_string_annotation: None,
+    decimal_string_helper: None,
+    unknown_type: Some(Cow::Borrowed("unknown")),
+    nullable_unknown_type: false,
+    empty_array_type: None,
+    object_map_type: Some(Cow::Borrowed("Record<string, unknown>")),
+    optional_definition: None,
+    case_type: CaseType::Preserve,
+    object_case_type: CaseType::UpperCamelCase,
+    constructor: None,
+    enum_definition: Some(EnumConfig {
+        definition: Cow::Borrowed("{visibility}enum {object_name} {"),
+        variant_definition: Cow::Borrowed("\t{variant} = \"{name}\","),
+        end: Cow::Borrowed("}"),
+    }),
+    conversion: None,
+    sealed_definition: None,
+    builder: None,
+    module_definition: None,
+    nested_definition: None,
+    options: TransformOptions::DEFAULT,
+};
+
+pub const PHP_DEFINITION: TransformConfig = TransformConfig {
+    type_definition: Cow::Borrowed("class {object_name} {"),
+    unit_definition: None,
+    type_attributes: Vec::new(),
+    declaration_attributes: Vec::new(),
+    root_attributes: Vec::new(),
+    // Nothing may come before the opening tag, the banner would be printed as text.
+    banner: None,
+    file_header: Some(Cow::Borrowed("<?php")),
+    dead_code_attribute: None,
+    deny_unknown_fields_attribute: None,
+    file_extension: Some(Cow::Borrowed("php")),
+    single_file_extension: None,
+    acronyms: Vec::new(),
+    visibility: None,
+    readonly_modifier: Some(Cow::Borrowed("readonly")),
+    field_definition: Cow::Borrowed("\tpublic {modifier}{field_type} ${field_name};"),
+    optional_field_definition: Some(Cow::Borrowed("\tpublic {modifier}{field_type} ${field_name} = null;")),
+    readonly_optional_field_definition: Some(Cow::Borrowed("\tpublic {modifier}{field_type} ${field_name};")),
+    name_change_annotation: Cow::Borrowed("\t#[SerializedName('{name}')]"),
+    source_comment: Some(Cow::Borrowed("\t// from: {sources}")),
+    doc_comment: Some(Cow::Borrowed("\t/** {doc} */")),
+    array_definition: Cow::Borrowed("array"),
+    untyped_arrays: true,
+    fixed_array_definition: None,
+    map_definition: None,
+    tuple_definition: None,
+    alias_definition: None,
+    parse_helper: None,
+    block_end: Cow::Borrowed("}"),
+    int_type: Cow::Borrowed("int"),
+    float_type: Cow::Borrowed("float"),
+    bool_type: Cow::Borrowed("bool"),
+    string_type: Cow::Borrowed("string"),
+    bytes_type: None,
+    bytes_annotation: None,
+    bool_string_annotation: None,
+    bool_string_helper: None,
+    decimal_type: None,
+    decimal_string_annotation: None,
+    decimal_string_helper: None,
+    unknown_type: Some(Cow::Borrowed("mixed")),
+    nullable_unknown_type: true,
+    empty_array_type: None,
+    object_map_type: Some(Cow::Borrowed("array")),
+    optional_definition: Some(Cow::Borrowed("?{field_type}")),
     case_type: CaseType::CamelCase,
     object_case_type: CaseType::UpperCamelCase,
     constructor: None,
+    enum_definition: Some(EnumConfig {
+        definition: Cow::Borrowed("enum {object_name}: string {"),
+        variant_definition: Cow::Borrowed("\tcase {variant} = '{name}';"),
+        end: Cow::Borrowed("}"),
+    }),
+    conversion: None,
+    sealed_definition: None,
+    builder: None,
+    module_definition: None,
+    nested_definition: None,
+    options: TransformOptions::DEFAULT,
 };
 
 #[derive(Eq, PartialEq, Debug, Serialize, Deserialize)]
+#[allow(clippy::enum_variant_names)]
 pub enum CaseType {
     SnakeCase,
     UpperCamelCase,
-    CamelCase
+    CamelCase,
+    /// Snake case in upper case, such as `FIELD_NAME`, for constants.
+    ScreamingSnakeCase,
+    /// Names are kept as written in the JSON.
+    Preserve,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct TransformConfig {
     pub type_definition: Cow<'static, str>,
+    /// Declaration of a type without fields, such as a Rust unit struct, given to empty objects when
+    /// [TransformOptions::empty_object_as] is [EmptyObject::Unit]. Empty objects use `type_definition` when missing.
+    #[serde(default)]
+    pub unit_definition: Option<Cow<'static, str>>,
+    /// Lines placed above every generated type.
+    #[serde(default)]
+    pub type_attributes: Vec<String>,
+    /// Lines placed between the attribute lines of `type_definition` and its declaration line, such as
+    /// further attributes for the derives of rust, or an empty line.
+    #[serde(default)]
+    pub declaration_attributes: Vec<String>,
+    /// Lines placed above the root type only.
+    #[serde(default)]
+    pub root_attributes: Vec<String>,
+    /// Lines placed above the header of the output, and of every file when writing a file per type,
+    /// when [TransformOptions::banner] is enabled. Usually a comment holding a license or a warning against editing.
+    #[serde(default)]
+    pub banner: Option<Cow<'static, str>>,
+    /// Text emitted once at the top of the output, e.g. imports. Written as `header` in definition files.
+    #[serde(default, rename = "header")]
+    pub file_header: Option<Cow<'static, str>>,
+    /// Attribute silencing unused field warnings, placed above every type when [TransformOptions::allow_dead_code] is enabled.
+    #[serde(default)]
+    pub dead_code_attribute: Option<Cow<'static, str>>,
+    /// Attribute rejecting unknown keys when deserializing, placed right above the line declaring every struct,
+    /// after the attributes of `type_definition`, when [TransformOptions::deny_unknown_fields] is enabled.
+    #[serde(default)]
+    pub deny_unknown_fields_attribute: Option<Cow<'static, str>>,
+    /// Extension of the files written when every type gets its own file, for languages where types of
+    /// the same package don't need to import each other.
+    #[serde(default)]
+    pub file_extension: Option<Cow<'static, str>>,
+    /// Extension of the file holding all the types, named after the root, written instead when there's no
+    /// `file_extension`.
+    #[serde(default)]
+    pub single_file_extension: Option<Cow<'static, str>>,
+    /// Upper case words kept together when converting names, e.g. `ID` so that `userID` becomes `user_id`.
+    #[serde(default)]
+    pub acronyms: Vec<String>,
+    /// Replaces `{visibility}` in `type_definition`, followed by a space. Removed when not set.
+    #[serde(default)]
+    pub visibility: Option<Cow<'static, str>>,
+    /// Replaces `{modifier}` in field definitions when [TransformOptions::readonly] is enabled, followed by a space.
+    /// Removed otherwise.
+    #[serde(default)]
+    pub readonly_modifier: Option<Cow<'static, str>>,
     pub field_definition: Cow<'static, str>,
+    /// Definition of fields missing from some objects. Their type is still wrapped in `optional_definition`.
+    #[serde(default)]
+    pub optional_field_definition: Option<Cow<'static, str>>,
+    /// Definition of optional fields marked with the `readonly_modifier`. Defaults to `optional_field_definition`.
+    #[serde(default)]
+    pub readonly_optional_field_definition: Option<Cow<'static, str>>,
     pub name_change_annotation: Cow<'static, str>,
+    /// Comment placed above every field when [TransformOptions::field_sources] is enabled,
+    /// `{sources}` being replaced by the files the field appeared in.
+    #[serde(default)]
+    pub source_comment: Option<Cow<'static, str>>,
+    /// Comment placed above fields described in a JSON Schema, `{doc}` being replaced by every line of the description.
+    #[serde(default)]
+    pub doc_comment: Option<Cow<'static, str>>,
+    /// Type of arrays, `{field_type}` being replaced by the type of their elements.
     pub array_definition: Cow<'static, str>,
+    /// Whether `array_definition` leaves out `{field_type}`, for languages whose arrays are untyped such as PHP.
+    #[serde(default)]
+    pub untyped_arrays: bool,
+    /// Type of arrays always holding the same number of elements, see `fixed_arrays` in [ParseOptions](crate::lib::model::parse_options::ParseOptions).
+    /// Arrays use `array_definition` when missing.
+    #[serde(default)]
+    pub fixed_array_definition: Option<Cow<'static, str>>,
+    /// Type of objects whose keys are all integers, see `integer_maps` in [ParseOptions](crate::lib::model::parse_options::ParseOptions).
+    /// `{key_type}` is replaced by `int_type`. Maps get the unknown type when missing.
+    #[serde(default)]
+    pub map_definition: Option<Cow<'static, str>>,
+    /// Type of arrays holding elements of different types, see `tuples` in [ParseOptions](crate::lib::model::parse_options::ParseOptions).
+    /// `{field_types}` is replaced by the types of the elements, separated by commas. Tuples get an array of the unknown type when missing.
+    #[serde(default)]
+    pub tuple_definition: Option<Cow<'static, str>>,
+    /// Type alias emitted for a root array, for languages supporting them.
+    #[serde(default)]
+    pub alias_definition: Option<Cow<'static, str>>,
+    /// Function parsing the root type from a JSON string, emitted after it when [TransformOptions::parse_helper] is enabled.
+    #[serde(default)]
+    pub parse_helper: Option<Cow<'static, str>>,
     pub block_end: Cow<'static, str>,
     pub int_type: Cow<'static, str>,
     pub float_type: Cow<'static, str>,
     pub bool_type: Cow<'static, str>,
     pub string_type: Cow<'static, str>,
+    /// Type of binary data, used for base64 strings when [TransformOptions::base64_bytes] is enabled.
+    #[serde(default)]
+    pub bytes_type: Option<Cow<'static, str>>,
+    /// Annotation placed above fields using `bytes_type`, e.g. to decode the base64 string.
+    #[serde(default)]
+    pub bytes_annotation: Option<Cow<'static, str>>,
+    /// Annotation placed above string fields mapped to `bool_type` when [TransformOptions::bool_strings] is enabled,
+    /// reading their `"yes"`/`"no"` values. Without it, those fields keep `string_type`.
+    #[serde(default)]
+    pub bool_string_annotation: Option<Cow<'static, str>>,
+    /// Function the `bool_string_annotation` calls, emitted once after the types when a field uses it.
+    #[serde(default)]
+    pub bool_string_helper: Option<Cow<'static, str>>,
+    /// Type of decimal numbers, used for strings such as prices when [TransformOptions::decimal_strings] is enabled.
+    #[serde(default)]
+    pub decimal_type: Option<Cow<'static, str>>,
+    /// Annotation placed above decimal fields whose values start with a currency symbol, reading the number after it.
+    /// Without it, those fields keep `string_type`.
+    #[serde(default)]
+    pub decimal_string_annotation: Option<Cow<'static, str>>,
+    /// Function the `decimal_string_annotation` calls, emitted once after the types when a field uses it.
+    #[serde(default)]
+    pub decimal_string_helper: Option<Cow<'static, str>>,
+    /// Type of values whose type can't be inferred. Defaults to `string_type`.
+    #[serde(default)]
+    pub unknown_type: Option<Cow<'static, str>>,
+    /// Whether `unknown_type` already accepts null, so optional fields of it are not wrapped in `optional_definition`.
+    #[serde(default)]
+    pub nullable_unknown_type: bool,
+    /// Type of the elements of arrays only seen empty, wrapped in `array_definition`. Defaults to `unknown_type`.
+    #[serde(default)]
+    pub empty_array_type: Option<Cow<'static, str>>,
+    /// Type of objects holding values of any type by name, given to empty objects when
+    /// [TransformOptions::empty_object_as] is [EmptyObject::Map]. Defaults to `unknown_type`.
+    #[serde(default)]
+    pub object_map_type: Option<Cow<'static, str>>,
+    /// Type of fields that may be missing, wrapping `{field_type}`. Such fields keep their type when not set.
+    #[serde(default)]
+    pub optional_definition: Option<Cow<'static, str>>,
     pub constructor: Option<ConstructorConfig>,
     pub case_type: CaseType,
     pub object_case_type: CaseType,
+    /// Enum emitted for string fields that always hold the same value, see [TransformOptions::constant_enums].
+    #[serde(default)]
+    pub enum_definition: Option<EnumConfig>,
+    /// Conversion from a dynamic value emitted after every struct, see [TransformOptions::value_conversions].
+    #[serde(default)]
+    pub conversion: Option<ConversionConfig>,
+    /// Type wrapping the kinds of objects of an array told apart by a discriminator field.
+    #[serde(default)]
+    pub sealed_definition: Option<SealedConfig>,
+    /// Attribute and field definition of types built field by field, see [TransformOptions::builder].
+    #[serde(default)]
+    pub builder: Option<BuilderConfig>,
+    /// Module wrapping every nested type along with the types it needs, see [TransformOptions::modules].
+    #[serde(default)]
+    pub module_definition: Option<ModuleConfig>,
+    /// Placement of the types declared inside their parent, see [TransformOptions::nested_types].
+    /// Definitions without it don't support nested types.
+    #[serde(default)]
+    pub nested_definition: Option<NestedConfig>,
+    #[serde(default)]
+    pub options: TransformOptions,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -115,4 +669,189 @@ pub struct ConstructorConfig {
 pub struct ConstructorField {
     pub field_definition: Cow<'static, str>,
     pub end: Cow<'static, str>,
-}
\ No newline at end of file
+}
+/// Definition of an enum inferred from string values.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct EnumConfig {
+    pub definition: Cow<'static, str>,
+    /// Line of a single variant. `{variant}` is the value in `object_case_type`, `{name}` the original value.
+    pub variant_definition: Cow<'static, str>,
+    pub end: Cow<'static, str>,
+}
+
+/// Conversion from a dynamic value, emitted after a type with a line per field.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ConversionConfig {
+    pub definition: Cow<'static, str>,
+    /// Line of a single field. `{name}` is the original name of the field.
+    pub field_definition: Cow<'static, str>,
+    pub end: Cow<'static, str>,
+}
+
+/// Type wrapping the kinds of objects held by a discriminated array, each kind being its own type.
+/// `{name}` is replaced by the discriminator field in `definition` and by its value in `variant_definition`.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SealedConfig {
+    pub definition: Cow<'static, str>,
+    /// Line of each kind, `{variant}` being the name of its type.
+    pub variant_definition: Option<Cow<'static, str>>,
+    pub end: Option<Cow<'static, str>>,
+    /// Replaces `block_end` in the types of each kind, `{object_name}` being the name of the wrapper.
+    pub variant_end: Option<Cow<'static, str>>,
+}
+
+/// Definition of types meant to be built field by field, such as Rust DTOs with a derived builder.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct BuilderConfig {
+    /// Lines placed above every struct.
+    pub attribute: Cow<'static, str>,
+    /// Replaces `field_definition`, usually to make the fields public.
+    pub field_definition: Cow<'static, str>,
+}
+
+/// Module declaring a nested type and the types it needs, named after the type in snake case.
+/// `{name}` is replaced by the name of the module.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ModuleConfig {
+    pub definition: Cow<'static, str>,
+    pub end: Cow<'static, str>,
+    /// Type of fields holding the nested type, `{object_name}` being the name of the type.
+    pub reference: Cow<'static, str>,
+    /// Replaces `{visibility}` in the types declared inside modules, so that their parent can use them.
+    pub visibility: Cow<'static, str>,
+}
+
+/// Placement of the types declared inside their parent, see [TransformOptions::nested_types].
+/// By default they are placed before the parent's `block_end`.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct NestedConfig {
+    /// Replaces `type_definition` in the nested types, such as a static class in Java.
+    pub type_definition: Option<Cow<'static, str>>,
+    /// Replaces `block_end` in the types holding nested types, which are placed after it and followed by `end`.
+    /// For languages whose fields can't hold declarations, such as the constructor of a Kotlin data class.
+    pub body_start: Option<Cow<'static, str>>,
+    pub end: Option<Cow<'static, str>>,
+}
+
+/// Type given to objects without fields.
+#[derive(Eq, PartialEq, Debug, Default, Clone, Copy, Serialize, Deserialize)]
+pub enum EmptyObject {
+    /// A type without fields, named after the object like any other.
+    #[default]
+    Struct,
+    /// The definition's `object_map_type`, no type being generated for the object.
+    Map,
+    /// The definition's `unit_definition`, a type declared without a body.
+    Unit,
+}
+
+/// Handling of the underscores a name starts or ends with, such as `_internal`, when converting its case.
+#[derive(Eq, PartialEq, Debug, Default, Clone, Copy, Serialize, Deserialize)]
+pub enum Underscores {
+    /// Kept as they are, around the converted name.
+    #[default]
+    Preserve,
+    /// Removed from the converted name.
+    Strip,
+}
+
+/// Opt-in inference rules, independent of the output language.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(default)]
+pub struct TransformOptions {
+    /// Map string fields whose values are only a boolean pair (`"yes"`/`"no"`, `"true"`/`"false"`...) to `bool_type`.
+    pub bool_strings: bool,
+    /// Map long string fields holding base64 data to `bytes_type`.
+    pub base64_bytes: bool,
+    /// Map string fields holding decimal numbers, optionally after a currency symbol, to `decimal_type`.
+    pub decimal_strings: bool,
+    /// Make string fields that are empty in every sample optional, as if they were `null`.
+    pub empty_strings_as_null: bool,
+    /// Name of the elements of a root array. Defaults to the root name followed by `Item`.
+    pub item_name: Option<String>,
+    /// Inline the fields of nested objects into their parent, prefixed by the object's name.
+    pub flatten: bool,
+    /// Map string fields that always hold the same value to a single-variant enum.
+    pub constant_enums: bool,
+    /// Emit the definition's `conversion` after every struct.
+    pub value_conversions: bool,
+    /// Emit the definition's `parse_helper` after the root object.
+    pub parse_helper: bool,
+    /// Declare the types needed by an object inside it, indented one level, instead of next to it.
+    pub nested_types: bool,
+    /// Mark fields with the definition's `readonly_modifier`.
+    pub readonly: bool,
+    /// Leave out the definition's `name_change_annotation` above fields whose name was converted.
+    pub skip_annotations: bool,
+    /// Emit types with the definition's `builder` attribute and field definition.
+    pub builder: bool,
+    /// Order the types so that every type comes after the types it references, for languages needing
+    /// declarations before their use. Types are no longer written as soon as they are generated.
+    pub sort_types: bool,
+    /// Order the types alphabetically by name, for a layout that doesn't change with the order of the input.
+    /// Along with `sort_types`, types still come after the types they reference, in alphabetical order otherwise.
+    pub sort_types_by_name: bool,
+    /// Declare every nested type inside a module named after it, with the types it needs, instead of next to the root.
+    pub modules: bool,
+    /// Place the definition's `banner` at the top of the output.
+    pub banner: bool,
+    /// Place the definition's `dead_code_attribute` above every type.
+    pub allow_dead_code: bool,
+    /// Mark every struct with the definition's `deny_unknown_fields_attribute`.
+    pub deny_unknown_fields: bool,
+    /// Note the input files every field appeared in with the definition's `source_comment`.
+    pub field_sources: bool,
+    /// Type given to objects without fields, see [EmptyObject].
+    pub empty_object_as: EmptyObject,
+    /// Handling of leading and trailing underscores in the names of fields and types, see [Underscores].
+    pub underscores: Underscores,
+    /// Transliterate the non-ASCII letters of the names of fields and types to ASCII, `café` becoming `cafe`.
+    pub ascii_only: bool,
+    /// Maximum number of generated types, objects beyond it get the unknown type instead of their own.
+    pub max_types: Option<usize>,
+    /// Width, in columns, above which the arguments of a constructor are wrapped one per line.
+    /// Tabs count as [TAB_WIDTH](crate::lib::transformer::TAB_WIDTH) columns.
+    pub max_line_width: Option<usize>,
+    /// Types given to fields by path, such as `user.address.zip` or `users[].zip` for the objects of an array.
+    pub path_overrides: BTreeMap<String, String>,
+    /// Types given to string fields whose values all start with a prefix, such as `usr_` for a `UserId` type.
+    pub string_prefixes: BTreeMap<String, String>,
+}
+
+impl TransformOptions {
+    pub const DEFAULT: TransformOptions = TransformOptions {
+        bool_strings: false,
+        base64_bytes: false,
+        decimal_strings: false,
+        empty_strings_as_null: false,
+        item_name: None,
+        flatten: false,
+        constant_enums: false,
+        value_conversions: false,
+        parse_helper: false,
+        nested_types: false,
+        readonly: false,
+        skip_annotations: false,
+        builder: false,
+        sort_types: false,
+        sort_types_by_name: false,
+        modules: false,
+        banner: false,
+        allow_dead_code: false,
+        deny_unknown_fields: false,
+        field_sources: false,
+        empty_object_as: EmptyObject::Struct,
+        underscores: Underscores::Preserve,
+        ascii_only: false,
+        max_types: None,
+        max_line_width: None,
+        path_overrides: BTreeMap::new(),
+        string_prefixes: BTreeMap::new(),
+    };
+}
+
+impl Default for TransformOptions {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
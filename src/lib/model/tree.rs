@@ -1,21 +1,307 @@
+use std::collections::BTreeSet;
+use std::{fmt, mem, slice};
+
+/// Root value of a JSON document.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum JsonRoot {
+    /// Fields of the root object.
+    Object(Vec<JsonTree>),
+    /// Type of the elements of the root array.
+    Array(JsonArrayType),
+    /// Value of a document holding neither an object nor an array, as a field named `root`.
+    Value(JsonTree),
+}
+
 /// Holds the possible types of a JSON object, with a String as field name
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Debug, Clone, Eq, PartialEq)]
 pub enum JsonTree {
     Int(String),
     Float(String),
-    String(String),
+    /// Field name and the values seen for the field.
+    String(String, Samples),
     Bool(String),
     JsonObject(String, Vec<JsonTree>),
     JsonArray(String, JsonArrayType),
+    /// Object whose keys are all integers, with the type of its values,
+    /// see [ParseOptions::integer_maps](crate::lib::model::parse_options::ParseOptions::integer_maps).
+    Map(String, JsonArrayType),
+    /// Field only seen holding null, whose type can't be inferred. Always wrapped in [JsonTree::Optional],
+    /// see [ParseOptions::null_fields](crate::lib::model::parse_options::ParseOptions::null_fields).
+    Null(String),
+    /// Field missing from some of the merged objects.
+    Optional(Box<JsonTree>),
 }
 
 /// Holds the possible types of a Json array (no field name).
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Debug, Clone, Eq, PartialEq)]
 pub enum JsonArrayType {
     Int,
     Float,
-    String,
+    /// Values seen in the array.
+    String(Samples),
     Bool,
     JsonObject(Vec<JsonTree>),
-    JsonArray(Box<JsonArrayType>)
-}
\ No newline at end of file
+    JsonArray(Box<JsonArrayType>),
+    /// Objects told apart by a discriminator field: the field name and, for each of its values,
+    /// the fields of the objects holding it (the discriminator excluded).
+    Tagged(String, Vec<(String, Vec<JsonTree>)>),
+    /// Elements of the inner type mixed with nulls, see [ParseOptions::null_elements](crate::lib::model::parse_options::ParseOptions::null_elements).
+    Optional(Box<JsonArrayType>),
+    /// Arrays always holding the same number of elements of the inner type,
+    /// see [ParseOptions::fixed_arrays](crate::lib::model::parse_options::ParseOptions::fixed_arrays).
+    Fixed(usize, Box<JsonArrayType>),
+    /// Arrays holding elements of different types, always in the same positions, with the type of each position,
+    /// see [ParseOptions::tuples](crate::lib::model::parse_options::ParseOptions::tuples).
+    Tuple(Vec<JsonArrayType>),
+    /// Elements whose type can't be inferred.
+    Unknown,
+    /// Elements of an array only seen empty. Unlike [JsonArrayType::Unknown], it takes the type of the arrays it's merged with.
+    Empty,
+}
+
+/// Values seen for a string, kept for value based inference.
+#[derive(Debug, Clone, Eq, PartialEq, Default)]
+pub struct Samples {
+    /// Distinct values, in the order they were seen.
+    pub values: Vec<String>,
+    /// Number of values seen, repeated ones included.
+    pub count: usize,
+    /// Distinct values were left out of `values` once it was full. Checks needing every value to agree
+    /// can't be trusted, see [Samples::complete].
+    pub truncated: bool,
+}
+
+impl Samples {
+    /// Creates the samples of a single value.
+    pub fn new(value: String) -> Self {
+        Self {
+            values: vec![value],
+            count: 1,
+            truncated: false,
+        }
+    }
+
+    /// Tells whether every distinct value seen is kept in `values`.
+    pub fn complete(&self) -> bool {
+        !self.truncated
+    }
+}
+
+impl JsonTree {
+    /// Name of the field represented by the tree.
+    pub fn name(&self) -> &str {
+        match self {
+            JsonTree::Int(name)
+            | JsonTree::Float(name)
+            | JsonTree::String(name, _)
+            | JsonTree::Bool(name)
+            | JsonTree::JsonObject(name, _)
+            | JsonTree::JsonArray(name, _)
+            | JsonTree::Map(name, _)
+            | JsonTree::Null(name) => name,
+            JsonTree::Optional(tree) => tree.name(),
+        }
+    }
+
+    /// Mutable name of the field represented by the tree.
+    pub fn name_mut(&mut self) -> &mut String {
+        match self {
+            JsonTree::Int(name)
+            | JsonTree::Float(name)
+            | JsonTree::String(name, _)
+            | JsonTree::Bool(name)
+            | JsonTree::JsonObject(name, _)
+            | JsonTree::JsonArray(name, _)
+            | JsonTree::Map(name, _)
+            | JsonTree::Null(name) => name,
+            JsonTree::Optional(tree) => tree.name_mut(),
+        }
+    }
+
+    /// Field wrapped by [JsonTree::Optional], or the field itself.
+    pub fn required(&self) -> &JsonTree {
+        match self {
+            JsonTree::Optional(tree) => tree,
+            tree => tree,
+        }
+    }
+
+    /// Mutable field wrapped by [JsonTree::Optional], or the field itself.
+    pub fn required_mut(&mut self) -> &mut JsonTree {
+        match self {
+            JsonTree::Optional(tree) => tree,
+            tree => tree,
+        }
+    }
+
+    /// Consumes the tree, giving back the field wrapped by [JsonTree::Optional] or the field itself.
+    pub fn into_required(self) -> JsonTree {
+        match self {
+            JsonTree::Optional(tree) => *tree,
+            tree => tree,
+        }
+    }
+
+    /// Wraps the field in [JsonTree::Optional], unless it already is.
+    pub fn set_optional(&mut self) {
+        if !matches!(self, JsonTree::Optional(_)) {
+            let tree = mem::replace(self, JsonTree::Bool(String::new()));
+            *self = JsonTree::Optional(Box::new(tree));
+        }
+    }
+
+    /// Consumes the tree, giving back the type of arrays holding values of the field.
+    /// Maps have no array counterpart, `None` is given back for them.
+    pub fn into_array_type(self) -> Option<JsonArrayType> {
+        match self {
+            JsonTree::Int(_) => Some(JsonArrayType::Int),
+            JsonTree::Float(_) => Some(JsonArrayType::Float),
+            JsonTree::String(_, samples) => Some(JsonArrayType::String(samples)),
+            JsonTree::Bool(_) => Some(JsonArrayType::Bool),
+            JsonTree::JsonObject(_, fields) => Some(JsonArrayType::JsonObject(fields)),
+            JsonTree::JsonArray(_, array_type) => Some(JsonArrayType::JsonArray(Box::new(array_type))),
+            JsonTree::Map(_, _) => None,
+            JsonTree::Null(_) => Some(JsonArrayType::Unknown),
+            JsonTree::Optional(tree) => tree.into_array_type().map(|array_type| JsonArrayType::Optional(Box::new(array_type))),
+        }
+    }
+}
+
+impl JsonRoot {
+    /// Every field name of the document, nested objects and the objects of arrays and maps included,
+    /// sorted and without duplicates.
+    pub fn key_names(&self) -> Vec<String> {
+        let mut names = BTreeSet::new();
+        match self {
+            JsonRoot::Object(fields) => add_field_names(&mut names, fields),
+            JsonRoot::Array(array_type) => add_element_names(&mut names, array_type),
+            JsonRoot::Value(_) => (),
+        }
+        names.into_iter().map(str::to_owned).collect()
+    }
+}
+
+fn add_field_names<'a>(names: &mut BTreeSet<&'a str>, fields: &'a [JsonTree]) {
+    for field in fields {
+        names.insert(field.name());
+
+        match field.required() {
+            JsonTree::JsonObject(_, fields) => add_field_names(names, fields),
+            JsonTree::JsonArray(_, array_type) | JsonTree::Map(_, array_type) => add_element_names(names, array_type),
+            _ => (),
+        }
+    }
+}
+
+fn add_element_names<'a>(names: &mut BTreeSet<&'a str>, array_type: &'a JsonArrayType) {
+    match array_type {
+        JsonArrayType::JsonObject(fields) => add_field_names(names, fields),
+        JsonArrayType::JsonArray(array_type) | JsonArrayType::Optional(array_type) | JsonArrayType::Fixed(_, array_type) => {
+            add_element_names(names, array_type)
+        }
+        JsonArrayType::Tuple(positions) => {
+            for array_type in positions {
+                add_element_names(names, array_type);
+            }
+        }
+        JsonArrayType::Tagged(key, variants) => {
+            names.insert(key);
+            for (_, fields) in variants {
+                add_field_names(names, fields);
+            }
+        }
+        _ => (),
+    }
+}
+
+/// Renders the inferred shape of the document, one field per line, nested fields being indented.
+impl fmt::Display for JsonRoot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JsonRoot::Object(fields) => {
+                writeln!(f, "object")?;
+                write_fields(f, fields, 1)
+            }
+            JsonRoot::Array(array_type) => write_array_type(f, array_type, 0),
+            JsonRoot::Value(tree) => write_fields(f, slice::from_ref(tree), 0),
+        }
+    }
+}
+
+fn write_fields(f: &mut fmt::Formatter<'_>, fields: &[JsonTree], depth: usize) -> fmt::Result {
+    for field in fields {
+        let (field, optional) = match field {
+            JsonTree::Optional(field) => (field.as_ref(), "?"),
+            field => (field, ""),
+        };
+
+        write!(f, "{}{}{}: ", "  ".repeat(depth), field.name(), optional)?;
+
+        match field {
+            JsonTree::Int(_) => writeln!(f, "int")?,
+            JsonTree::Float(_) => writeln!(f, "float")?,
+            JsonTree::String(_, samples) => writeln!(f, "string {:?}", samples.values)?,
+            JsonTree::Bool(_) => writeln!(f, "bool")?,
+            JsonTree::JsonObject(_, fields) => {
+                writeln!(f, "object")?;
+                write_fields(f, fields, depth + 1)?;
+            }
+            JsonTree::JsonArray(_, array_type) => write_array_type(f, array_type, depth)?,
+            JsonTree::Map(_, value_type) => {
+                write!(f, "map of int to ")?;
+                write_element_type(f, value_type, depth)?;
+            }
+            JsonTree::Null(_) => writeln!(f, "null")?,
+            JsonTree::Optional(_) => writeln!(f, "optional")?,
+        }
+    }
+
+    Ok(())
+}
+
+fn write_array_type(f: &mut fmt::Formatter<'_>, array_type: &JsonArrayType, depth: usize) -> fmt::Result {
+    match array_type {
+        JsonArrayType::Fixed(len, _) => write!(f, "array of {} ", len)?,
+        JsonArrayType::Tuple(_) => (),
+        _ => write!(f, "array of ")?,
+    }
+
+    write_element_type(f, array_type, depth)
+}
+
+fn write_element_type(f: &mut fmt::Formatter<'_>, array_type: &JsonArrayType, depth: usize) -> fmt::Result {
+    match array_type {
+        JsonArrayType::Int => writeln!(f, "int"),
+        JsonArrayType::Float => writeln!(f, "float"),
+        JsonArrayType::String(samples) => writeln!(f, "string {:?}", samples.values),
+        JsonArrayType::Bool => writeln!(f, "bool"),
+        JsonArrayType::JsonObject(fields) => {
+            writeln!(f, "object")?;
+            write_fields(f, fields, depth + 1)
+        }
+        JsonArrayType::JsonArray(array_type) => write_array_type(f, array_type, depth),
+        JsonArrayType::Optional(array_type) => {
+            write!(f, "optional ")?;
+            write_element_type(f, array_type, depth)
+        }
+        JsonArrayType::Fixed(_, array_type) => write_element_type(f, array_type, depth),
+        JsonArrayType::Tuple(positions) => {
+            writeln!(f, "tuple")?;
+            for (i, position) in positions.iter().enumerate() {
+                write!(f, "{}{}: ", "  ".repeat(depth + 1), i)?;
+                write_element_type(f, position, depth + 1)?;
+            }
+            Ok(())
+        }
+        JsonArrayType::Tagged(key, variants) => {
+            writeln!(f, "objects tagged by {}", key)?;
+            for (value, fields) in variants {
+                writeln!(f, "{}{:?}: object", "  ".repeat(depth + 1), value)?;
+                write_fields(f, fields, depth + 2)?;
+            }
+            Ok(())
+        }
+        JsonArrayType::Unknown => writeln!(f, "unknown"),
+        JsonArrayType::Empty => writeln!(f, "empty"),
+    }
+}
@@ -1,5 +1,5 @@
 use std::iter::{Enumerate, Peekable};
-use std::str::{Chars, Lines};
+use std::str::{CharIndices, Lines};
 use crate::lib::parser::lexer::NextStep::{LexCharacter};
 use crate::lib::model::token::{JsonToken, JsonType, Token};
 
@@ -7,11 +7,14 @@ use crate::lib::model::token::{JsonToken, JsonType, Token};
 /// Next step for the character lexer.
 #[derive(Debug, PartialEq, Eq)]
 enum NextStep {
-    LexNumberType,
+    /// Holds the column and the byte index of the first digit, or of the minus sign, in the current line.
+    LexNumberType(usize, usize),
     LexCharacter,
     LexName,
     LexString,
-    LexBooleanOrNull,
+    /// Holds the first character of the literal, already consumed.
+    LexBooleanOrNull(char),
+    LexNonFinite,
     Done,
 }
 
@@ -21,15 +24,32 @@ enum NextStep {
 enum NextLexStep {
     Done,
     Advance,
-    Skip,
+}
+
+/// Kind of the value being lexed, used to tell field names apart from string values.
+#[derive(Debug, PartialEq, Eq)]
+enum Container {
+    Object,
+    Array,
 }
 
 pub struct Lexer<'a> {
+    /// Source JSON, used to locate the lines in it.
+    json: &'a str,
     lines: Enumerate<Lines<'a>>,
     current_line: usize,
     current_line_str: Option<&'a str>,
-    char_iter: Option<Peekable<Enumerate<Chars<'a>>>>,
+    /// Byte index of the current line in the source.
+    line_offset: usize,
+    /// Characters of the current line, with their byte index in it.
+    char_iter: Option<Peekable<Enumerate<CharIndices<'a>>>>,
     tokens: Vec<Token>,
+    /// Objects and arrays opened and not closed yet.
+    containers: Vec<Container>,
+    /// Whether `Infinity`, `-Infinity` and `NaN` are read as floats.
+    allow_nan: bool,
+    /// Whether a comma between two digits of an object value is read as a decimal point.
+    decimal_comma: bool,
 }
 
 impl<'a> Lexer<'a> {
@@ -39,78 +59,167 @@ impl<'a> Lexer<'a> {
     pub fn new(json: &'a str) -> Self {
         let lines = json.lines().enumerate();
         Self {
+            json,
             lines,
             current_line: 0,
             current_line_str: None,
+            line_offset: 0,
             char_iter: None,
             tokens: vec![],
+            containers: vec![],
+            allow_nan: false,
+            decimal_comma: false,
         }
     }
 
+    /// Reads the `Infinity`, `-Infinity` and `NaN` literals written by some encoders as floats,
+    /// see [ParseOptions::allow_nan](crate::lib::model::parse_options::ParseOptions::allow_nan).
+    pub fn allow_nan(mut self, allow_nan: bool) -> Self {
+        self.allow_nan = allow_nan;
+        self
+    }
+
     /// Processes basic tokens. Delegates to other functions for primitive types.
     fn lex_character(&mut self) -> NextStep {
         if let Some(char_iter) = &mut self.char_iter {
-            while let Some((i, char)) = char_iter.next() {
+            while let Some((i, (byte, char))) = char_iter.next() {
                 match char {
-                    '{' => self.tokens.push(Token {
-                        value: JsonToken::ObjectStart,
-                        col: i,
-                        line: self.current_line,
-                    }),
-                    '}' => self.tokens.push(Token {
-                        value: JsonToken::ObjectEnd,
-                        col: i,
-                        line: self.current_line,
-                    }),
-                    '[' => self.tokens.push(Token {
-                        value: JsonToken::ArrayStart,
-                        col: i,
-                        line: self.current_line,
-                    }),
-                    ']' => self.tokens.push(Token {
-                        value: JsonToken::ArrayEnd,
-                        col: i,
-                        line: self.current_line,
-                    }),
+                    '{' => {
+                        self.containers.push(Container::Object);
+                        self.tokens.push(Token {
+                            value: JsonToken::ObjectStart,
+                            col: i,
+                            line: self.current_line,
+                            span: None,
+                        })
+                    }
+                    '}' => {
+                        self.containers.pop();
+                        self.tokens.push(Token {
+                            value: JsonToken::ObjectEnd,
+                            col: i,
+                            line: self.current_line,
+                            span: None,
+                        })
+                    }
+                    '[' => {
+                        self.containers.push(Container::Array);
+                        self.tokens.push(Token {
+                            value: JsonToken::ArrayStart,
+                            col: i,
+                            line: self.current_line,
+                            span: None,
+                        })
+                    }
+                    ']' => {
+                        self.containers.pop();
+                        self.tokens.push(Token {
+                            value: JsonToken::ArrayEnd,
+                            col: i,
+                            line: self.current_line,
+                            span: None,
+                        })
+                    }
                     ':' => self.tokens.push(Token {
                         value: JsonToken::Colon,
                         col: i,
                         line: self.current_line,
+                        span: None,
                     }),
                     ',' => self.tokens.push(Token {
                         value: JsonToken::Comma,
                         col: i,
                         line: self.current_line,
+                        span: None,
                     }),
+                    _ if Self::after_root(&self.containers, &self.tokens) && !char.is_whitespace() && char != '"' => {
+                        self.tokens.push(Token {
+                            value: JsonToken::Unexpected(char),
+                            col: i,
+                            line: self.current_line,
+                            span: None,
+                        })
+                    }
                     '0'..='9' => {
-                        return NextStep::LexNumberType;
+                        return NextStep::LexNumberType(i, byte);
+                    }
+                    // The sign of -Infinity, skipped by the non-finite lexer.
+                    '-' if self.allow_nan && matches!(char_iter.peek(), Some((_, (_, 'I')))) => (),
+                    '-' if matches!(char_iter.peek(), Some((_, (_, '0'..='9')))) => {
+                        return NextStep::LexNumberType(i, byte);
                     }
                     't' | 'f' | 'n' => {
-                        return NextStep::LexBooleanOrNull;
+                        return NextStep::LexBooleanOrNull(char);
+                    }
+                    'I' | 'N' if self.allow_nan => {
+                        return NextStep::LexNonFinite;
+                    }
+                    // A string holding the whole document.
+                    '"' if self.containers.is_empty() && self.tokens.is_empty() => {
+                        return NextStep::LexString;
+                    }
+                    '"' if Self::after_root(&self.containers, &self.tokens) => {
+                        self.tokens.push(Token {
+                            value: JsonToken::Unexpected(char),
+                            col: i,
+                            line: self.current_line,
+                            span: None,
+                        })
                     }
                     '"' => {
                         if let Some(last_token) = &self.tokens.last() {
                             let last_added = &last_token.value;
-                            if last_added == &JsonToken::Comma || last_added == &JsonToken::ObjectStart {
-                                return NextStep::LexName;
-                            } else if last_added == &JsonToken::Colon {
+                            let in_array = self.containers.last() == Some(&Container::Array);
+                            if last_added == &JsonToken::Colon
+                                || (in_array && (last_added == &JsonToken::Comma || last_added == &JsonToken::ArrayStart)) {
                                 return NextStep::LexString;
+                            } else if last_added == &JsonToken::Comma || last_added == &JsonToken::ObjectStart {
+                                return NextStep::LexName;
                             }
                         };
                     }
+                    // Characters that can't start a value, such as the `x` of `[xyz]`, for the tokenizer to report.
+                    _ if !char.is_whitespace() => self.tokens.push(Token {
+                        value: JsonToken::Unexpected(char),
+                        col: i,
+                        line: self.current_line,
+                        span: None,
+                    }),
                     _ => ()
                 }
             }
         }
 
-        if let Some((i, line)) = self.lines.next() {
-            self.current_line_str = Some(line);
-            self.char_iter = Some(line.chars().enumerate().peekable());
-            self.current_line = i;
+        if self.next_line() {
             return NextStep::LexCharacter;
         }
 
-        return NextStep::Done;
+        NextStep::Done
+    }
+
+    /// Checks whether the root value is complete, anything but whitespace being unexpected after it.
+    /// Another object may still follow, for concatenated documents.
+    fn after_root(containers: &[Container], tokens: &[Token]) -> bool {
+        containers.is_empty() && matches!(
+            tokens.last().map(|token| &token.value),
+            Some(JsonToken::Value(_) | JsonToken::ObjectEnd | JsonToken::ArrayEnd | JsonToken::Unexpected(_))
+        )
+    }
+
+    /// Moves the character iterator to the start of the next line.
+    /// # Returns
+    /// `false` if there are no lines left.
+    fn next_line(&mut self) -> bool {
+        match self.lines.next() {
+            Some((i, line)) => {
+                self.current_line_str = Some(line);
+                self.line_offset = line.as_ptr() as usize - self.json.as_ptr() as usize;
+                self.char_iter = Some(line.char_indices().enumerate().peekable());
+                self.current_line = i;
+                true
+            }
+            None => false,
+        }
     }
 
     /// Basic lexer for primitive types. Runs a closure which returns the next step for the lexer (advance the iterator, skip a character or end the lexer).
@@ -122,19 +231,15 @@ impl<'a> Lexer<'a> {
         let mut token_start = None;
 
         if let Some(char_iter) = &mut self.char_iter {
-            while let Some((i, next_char)) = char_iter.peek() {
+            while let Some((i, (_, next_char))) = char_iter.peek() {
                 if token_start.is_none() {
-                    token_start = Some(i.clone());
+                    token_start = Some(*i);
                 }
 
                 match f((i, next_char)) {
                     NextLexStep::Advance => {
                         char_iter.next();
                     }
-                    NextLexStep::Skip => {
-                        char_iter.next();
-                        char_iter.next();
-                    }
                     NextLexStep::Done => break,
                 }
             }
@@ -143,115 +248,316 @@ impl<'a> Lexer<'a> {
         token_start
     }
 
-    /// Processes a boolean datatype.
-    fn lex_boolean_or_null(&mut self) {
-        let mut is_null = false;
+    /// Processes a `true`, `false` or `null` literal.
+    /// # Arguments
+    /// * `first` first character of the literal, already consumed.
+    fn lex_boolean_or_null(&mut self, first: char) {
+        let mut literal = String::from(first);
 
         let token_start = self.lex(|(_, next_char)| {
             match next_char {
-                'l' => {
-                    is_null = true;
-                    NextLexStep::Advance
-                }
-                's' => {
-                    is_null = false;
+                ',' | '}' | ']' => NextLexStep::Done,
+                next_char if next_char.is_whitespace() => NextLexStep::Done,
+                next_char => {
+                    literal.push(*next_char);
                     NextLexStep::Advance
                 }
-                ',' | '}' => NextLexStep::Done,
-                _ => NextLexStep::Advance,
+            }
+        });
+
+        // Literals are matched exactly, anything else is reported by the tokenizer at its first character.
+        let value = match literal.as_str() {
+            "true" | "false" => JsonToken::Value(JsonType::Bool),
+            "null" => JsonToken::Value(JsonType::Null),
+            _ => JsonToken::Unexpected(first),
+        };
+
+        // The first character of the literal was consumed before lexing the rest.
+        let col = match token_start {
+            Some(token_start) => token_start - 1,
+            None => self.current_line_str.map_or(0, |line| line.chars().count() - 1),
+        };
+        self.tokens.push(
+            Token {
+                value,
+                col,
+                line: self.current_line,
+                span: None,
+            }
+        )
+    }
+
+    /// Reads `1,5` as `1.5` in object values, see [ParseOptions::decimal_comma](crate::lib::model::parse_options::ParseOptions::decimal_comma).
+    /// Arrays are left alone, `[1,5]` holding two numbers.
+    pub fn decimal_comma(mut self, decimal_comma: bool) -> Self {
+        self.decimal_comma = decimal_comma;
+        self
+    }
+
+    /// Processes an `Infinity` or `NaN` literal, the sign of `-Infinity` being skipped.
+    fn lex_non_finite(&mut self) {
+        let token_start = self.lex(|(_, next_char)| {
+            if next_char.is_ascii_alphabetic() {
+                NextLexStep::Advance
+            } else {
+                NextLexStep::Done
             }
         });
 
         if let Some(token_start) = token_start {
             self.tokens.push(
                 Token {
-                    value: JsonToken::Value(if is_null { JsonType::Null } else { JsonType::Bool }),
+                    value: JsonToken::Value(JsonType::Float),
                     col: token_start,
                     line: self.current_line,
+                    span: None,
                 }
             )
         }
     }
 
-    /// Processes a field name.
+    /// Processes a field name. Escapes are read as in string values.
     fn lex_name(&mut self) {
-        let mut start_index = 0;
-        let mut name = String::new();
-
-        if let Some(char_iter) = &mut self.char_iter {
-            while let Some((i, char)) = char_iter.next() {
-                if i == 0 {
-                    start_index = i;
-                }
-                if let Some((_, next_char)) = char_iter.peek() {
-                    name.push(char);
-
-                    if next_char == &'"' {
-                        break;
-                    }
-                }
-            }
-        }
-
+        // The opening quote was consumed before lexing the name.
+        let start_index = match self.char_iter.as_mut().and_then(|char_iter| char_iter.peek()) {
+            Some((i, _)) => i - 1,
+            None => self.current_line_str.map_or(0, |line| line.chars().count() - 1),
+        };
+        let name = self.lex_quoted(false);
 
         self.tokens.push(
             Token {
                 value: JsonToken::Name(name),
                 col: start_index,
                 line: self.current_line,
+                span: None,
             }
         )
     }
 
 
-    /// Processes a String value.
+    /// Processes a String value. The unescaped content is kept in the token for value based inference.
+    /// A string whose closing quote is on a later line continues on the following lines, the line breaks being kept in it.
     fn lex_string(&mut self) {
-        let token_start = self.lex(|(_, next_char)| {
-            match next_char {
-                '\\' => NextLexStep::Skip,
-                '"' => NextLexStep::Done,
-                _ => NextLexStep::Advance,
+        let line = self.current_line;
+        // The opening quote was consumed before lexing the string.
+        let col = match self.char_iter.as_mut().and_then(|char_iter| char_iter.peek()) {
+            Some((i, _)) => i - 1,
+            None => self.current_line_str.map_or(0, |line| line.chars().count() - 1),
+        };
+        let value = self.lex_quoted(true);
+
+        self.tokens.push(
+            Token {
+                value: JsonToken::Value(JsonType::String(value)),
+                line,
+                col,
+                span: None,
             }
-        });
+        );
+    }
 
-        if let Some(token_start) = token_start {
-            self.tokens.push(
-                Token {
-                    value: JsonToken::Value(JsonType::String),
-                    line: self.current_line,
-                    col: token_start,
+    /// Reads the text of a field name or a string value up to its closing quote, which is consumed.
+    /// # Arguments
+    /// * `across_lines` whether the text continues on the following lines when its closing quote is not on the current one.
+    /// # Returns
+    /// The text with its escapes replaced by the characters they stand for.
+    fn lex_quoted(&mut self, across_lines: bool) -> String {
+        let mut value = String::new();
+        let mut escaped = false;
+        let mut closed = false;
+        // Hex digits of a `\uXXXX` escape read so far, and the first half of a surrogate pair.
+        let mut unicode: Option<String> = None;
+        let mut high_surrogate = None;
+
+        loop {
+            self.lex(|(_, next_char)| {
+                if let Some(ref mut digits) = unicode {
+                    if next_char.is_ascii_hexdigit() {
+                        digits.push(*next_char);
+                        if digits.len() == 4 {
+                            let code = u32::from_str_radix(digits, 16).unwrap_or_default();
+                            Self::push_code_point(&mut value, &mut high_surrogate, code);
+                            unicode = None;
+                        }
+                        return NextLexStep::Advance;
+                    }
+
+                    // Not an escape after all, kept as written.
+                    value.push_str("\\u");
+                    value.push_str(digits);
+                    unicode = None;
+                }
+
+                // A surrogate pair is two escapes in a row, anything else leaves its first half alone.
+                let pair_continues = if escaped { *next_char == 'u' } else { *next_char == '\\' };
+                if !pair_continues && high_surrogate.take().is_some() {
+                    value.push(char::REPLACEMENT_CHARACTER);
+                }
+
+                if escaped {
+                    escaped = false;
+                    match next_char {
+                        'u' => unicode = Some(String::with_capacity(4)),
+                        'n' => value.push('\n'),
+                        't' => value.push('\t'),
+                        'r' => value.push('\r'),
+                        'b' => value.push('\u{8}'),
+                        'f' => value.push('\u{c}'),
+                        _ => value.push(*next_char),
+                    }
+                    return NextLexStep::Advance;
+                }
+
+                match next_char {
+                    '\\' => {
+                        escaped = true;
+                        NextLexStep::Advance
+                    }
+                    '"' => {
+                        closed = true;
+                        NextLexStep::Done
+                    }
+                    _ => {
+                        value.push(*next_char);
+                        NextLexStep::Advance
+                    }
+                }
+            });
+
+            if let Some(digits) = unicode.take() {
+                value.push_str("\\u");
+                value.push_str(&digits);
+            }
+            if closed {
+                // The closing quote is consumed, it would otherwise be read as the opening quote of another string.
+                if let Some(char_iter) = self.char_iter.as_mut() {
+                    char_iter.next();
+                }
+                break;
+            }
+            if !across_lines || !self.next_line() {
+                break;
+            }
+            // A backslash at the end of the line escapes the line break.
+            escaped = false;
+            value.push('\n');
+        }
+
+        if high_surrogate.is_some() {
+            value.push(char::REPLACEMENT_CHARACTER);
+        }
+
+        value
+    }
+
+    /// Adds the character of a `\uXXXX` escape to a string. The first half of a surrogate pair is held
+    /// until the second one, halves without their pair become the replacement character.
+    /// # Arguments
+    /// * `value` string being read.
+    /// * `high_surrogate` first half of a surrogate pair, waiting for the second one.
+    /// * `code` code unit of the escape.
+    fn push_code_point(value: &mut String, high_surrogate: &mut Option<u32>, code: u32) {
+        match (high_surrogate.take(), code) {
+            (None, 0xD800..=0xDBFF) => *high_surrogate = Some(code),
+            (Some(high), 0xDC00..=0xDFFF) => {
+                let code = 0x10000 + ((high - 0xD800) << 10) + (code - 0xDC00);
+                value.push(char::from_u32(code).unwrap_or(char::REPLACEMENT_CHARACTER));
+            }
+            (high, code) => {
+                if high.is_some() {
+                    value.push(char::REPLACEMENT_CHARACTER);
                 }
-            );
+                if (0xD800..=0xDBFF).contains(&code) {
+                    *high_surrogate = Some(code);
+                } else {
+                    value.push(char::from_u32(code).unwrap_or(char::REPLACEMENT_CHARACTER));
+                }
+            }
         }
     }
 
     /// Processes a number value. Defaults to adding a int token, will add a float token if it encounters a point(`.`) character.
-    fn lex_number(&mut self) {
+    /// The token holds the span of the literal in the source.
+    /// # Arguments
+    /// * `col` column of the first digit, or of the minus sign of a negative number, in the current line.
+    /// * `start` byte index of the first digit or minus sign in the current line, already consumed.
+    fn lex_number(&mut self, col: usize, start: usize) {
         let mut is_float = false;
 
-        let token_start = self.lex(|(_, next_char)| {
+        let mut lex_digits = |lexer: &mut Self| lexer.lex(|(_, next_char)| {
             match next_char {
                 '0'..='9' => NextLexStep::Advance,
                 '.' => {
                     is_float = true;
-                    return NextLexStep::Advance;
+                    NextLexStep::Advance
                 }
                 _ => NextLexStep::Done,
             }
         });
 
-        if let Some(token_start) = token_start {
-            self.tokens.push(
-                Token {
-                    value: JsonToken::Value(if is_float { JsonType::Float } else { JsonType::Int }),
-                    col: token_start,
-                    line: self.current_line,
+        lex_digits(self);
+
+        if self.decimal_comma && self.containers.last() == Some(&Container::Object) {
+            if let Some(char_iter) = &mut self.char_iter {
+                let mut ahead = char_iter.clone();
+                let comma = matches!(ahead.next(), Some((_, (_, ','))));
+                if comma && matches!(ahead.peek(), Some((_, (_, '0'..='9')))) {
+                    char_iter.next();
+                    lex_digits(self);
+                    is_float = true;
                 }
-            );
+            }
         }
+
+        if self.lex_exponent() {
+            is_float = true;
+        }
+
+        let end = match self.char_iter.as_mut().and_then(|char_iter| char_iter.peek()) {
+            Some((_, (byte, _))) => *byte,
+            None => self.current_line_str.map_or(start + 1, |line| line.len()),
+        };
+
+        self.tokens.push(
+            Token {
+                value: JsonToken::Value(if is_float { JsonType::Float } else { JsonType::Int }),
+                col,
+                line: self.current_line,
+                span: Some(self.line_offset + start..self.line_offset + end),
+            }
+        );
     }
 
 
+    /// Processes the exponent of a number, an `e` or `E` followed by an optional sign and digits.
+    /// Nothing is consumed if the characters following the number aren't an exponent.
+    /// # Returns
+    /// `true` if an exponent was consumed.
+    fn lex_exponent(&mut self) -> bool {
+        if let Some(char_iter) = &mut self.char_iter {
+            let mut ahead = char_iter.clone();
+            if !matches!(ahead.next(), Some((_, (_, 'e' | 'E')))) {
+                return false;
+            }
+            if matches!(ahead.peek(), Some((_, (_, '+' | '-')))) {
+                ahead.next();
+            }
+            if !matches!(ahead.peek(), Some((_, (_, '0'..='9')))) {
+                return false;
+            }
+            *char_iter = ahead;
+        } else {
+            return false;
+        }
+
+        self.lex(|(_, next_char)| match next_char {
+            '0'..='9' => NextLexStep::Advance,
+            _ => NextLexStep::Done,
+        });
+        true
+    }
+
     /// Consumes the structure and start the lexing process.
     /// # Returns
     /// Vec of Token structures.
@@ -260,9 +566,9 @@ impl<'a> Lexer<'a> {
         while step != NextStep::Done {
             match step {
                 NextStep::LexCharacter => step = self.lex_character(),
-                NextStep::LexNumberType => {
+                NextStep::LexNumberType(col, start) => {
                     step = LexCharacter;
-                    self.lex_number();
+                    self.lex_number(col, start);
                 }
                 NextStep::LexName => {
                     step = LexCharacter;
@@ -272,9 +578,13 @@ impl<'a> Lexer<'a> {
                     step = LexCharacter;
                     self.lex_string();
                 }
-                NextStep::LexBooleanOrNull => {
+                NextStep::LexBooleanOrNull(first) => {
                     step = LexCharacter;
-                    self.lex_boolean_or_null();
+                    self.lex_boolean_or_null(first);
+                }
+                NextStep::LexNonFinite => {
+                    step = LexCharacter;
+                    self.lex_non_finite();
                 }
                 _ => (),
             }
@@ -295,7 +605,7 @@ mod tests {
         let json = "{\"f1\": \"value\", \"f2\": true, \"f3\": 45.3, \"f4\": 12}";
 
         let expected_result = vec![
-            JsonToken::ObjectStart, JsonToken::Name("f1".to_owned()), JsonToken::Colon, JsonToken::Value(JsonType::String),
+            JsonToken::ObjectStart, JsonToken::Name("f1".to_owned()), JsonToken::Colon, JsonToken::Value(JsonType::String("value".to_owned())),
             JsonToken::Comma, JsonToken::Name("f2".to_owned()), JsonToken::Colon, JsonToken::Value(JsonType::Bool),
             JsonToken::Comma, JsonToken::Name("f3".to_owned()), JsonToken::Colon, JsonToken::Value(JsonType::Float),
             JsonToken::Comma, JsonToken::Name("f4".to_owned()), JsonToken::Colon, JsonToken::Value(JsonType::Int),
@@ -349,14 +659,74 @@ mod tests {
         assert_eq!(expected_result, tokens);
     }
 
+    #[test]
+    fn lex_exponent() {
+        for json in ["{\"f1\": 1.5e10}", "{\"f2\": 3E-4}", "{\"f3\": 6.02e23}", "{\"f4\": 1e5}", "{\"f5\": 2.5e+3}"] {
+            let tokens = Lexer::new(json).start_lex();
+
+            assert_eq!(tokens.len(), 5, "{}", json);
+            assert_eq!(tokens[3].value, JsonToken::Value(JsonType::Float), "{}", json);
+            assert_eq!(tokens[3].col, 7, "{}", json);
+            assert_eq!(&json[tokens[3].span.clone().unwrap()], &json[7..json.len() - 1]);
+        }
+    }
+
+    #[test]
+    fn lex_int_before_letter() {
+        let json = "[1e]";
+        let expected_result = vec![
+            JsonToken::ArrayStart,
+            JsonToken::Value(JsonType::Int),
+            JsonToken::Unexpected('e'),
+            JsonToken::ArrayEnd,
+        ];
+
+        let lexer = Lexer::new(json);
+        let tokens: Vec<JsonToken> = lexer.start_lex().into_iter().map(|token| token.value).collect();
+
+        assert_eq!(expected_result, tokens);
+    }
+
+    #[test]
+    fn lex_negative_numbers() {
+        let json = "{\"a\": -5, \"b\": -5.2, \"c\": [-0]}";
+        let expected_result = vec![
+            JsonToken::ObjectStart,
+            JsonToken::Name("a".to_owned()), JsonToken::Colon, JsonToken::Value(JsonType::Int), JsonToken::Comma,
+            JsonToken::Name("b".to_owned()), JsonToken::Colon, JsonToken::Value(JsonType::Float), JsonToken::Comma,
+            JsonToken::Name("c".to_owned()), JsonToken::Colon, JsonToken::ArrayStart, JsonToken::Value(JsonType::Int), JsonToken::ArrayEnd,
+            JsonToken::ObjectEnd,
+        ];
+
+        let tokens = Lexer::new(json).start_lex();
+        let literals: Vec<&str> = tokens.iter().filter_map(|token| token.span.clone()).map(|span| &json[span]).collect();
+        let values: Vec<JsonToken> = tokens.into_iter().map(|token| token.value).collect();
+
+        assert_eq!(values, expected_result);
+        assert_eq!(literals, vec!["-5", "-5.2", "-0"]);
+    }
+
+    #[test]
+    fn number_span() {
+        let json = "{\"º\": 123456789012345678901234567890,\n\"b\": 42.25}";
+
+        let lexer = Lexer::new(json);
+        let literals: Vec<&str> = lexer.start_lex().into_iter()
+            .filter_map(|token| token.span)
+            .map(|span| &json[span])
+            .collect();
+
+        assert_eq!(literals, vec!["123456789012345678901234567890", "42.25"]);
+    }
+
     #[test]
     fn skip_number() {
         let json = "5423234,{";
 
         let mut lexer = Lexer::new(json);
-        lexer.char_iter = Some(lexer.lines.next().unwrap().1.chars().enumerate().peekable());
-        lexer.lex_number();
-        let char = lexer.char_iter.unwrap().next().unwrap().1;
+        lexer.char_iter = Some(lexer.lines.next().unwrap().1.char_indices().enumerate().peekable());
+        lexer.lex_number(0, 0);
+        let char = lexer.char_iter.unwrap().next().unwrap().1.1;
 
         assert_eq!(char, ',');
     }
@@ -366,9 +736,9 @@ mod tests {
         let json = "542.3234,{";
 
         let mut lexer = Lexer::new(json);
-        lexer.char_iter = Some(lexer.lines.next().unwrap().1.chars().enumerate().peekable());
-        lexer.lex_number();
-        let char = lexer.char_iter.unwrap().next().unwrap().1;
+        lexer.char_iter = Some(lexer.lines.next().unwrap().1.char_indices().enumerate().peekable());
+        lexer.lex_number(0, 0);
+        let char = lexer.char_iter.unwrap().next().unwrap().1.1;
 
         assert_eq!(char, ',');
     }
@@ -387,12 +757,125 @@ mod tests {
         assert_eq!(tokens, expected_result);
     }
 
+    #[test]
+    fn lex_empty_field_name() {
+        let json = "{\"b\":1,\"\":2,\"c\":3}";
+        let expected_result = vec![
+            JsonToken::ObjectStart, JsonToken::Name("b".to_owned()), JsonToken::Colon, JsonToken::Value(JsonType::Int),
+            JsonToken::Comma, JsonToken::Name("".to_owned()), JsonToken::Colon, JsonToken::Value(JsonType::Int),
+            JsonToken::Comma, JsonToken::Name("c".to_owned()), JsonToken::Colon, JsonToken::Value(JsonType::Int),
+            JsonToken::ObjectEnd,
+        ];
+
+        let lexer = Lexer::new(json);
+        let tokens: Vec<JsonToken> = lexer.start_lex().into_iter().map(|token| token.value).collect();
+
+        assert_eq!(tokens, expected_result);
+    }
+
+    #[test]
+    fn lex_escaped_field_names() {
+        let json = r#"{"a\"b":1,"c":2,"a\u0041":3}"#;
+        let expected_result = vec![
+            JsonToken::ObjectStart, JsonToken::Name("a\"b".to_owned()), JsonToken::Colon, JsonToken::Value(JsonType::Int),
+            JsonToken::Comma, JsonToken::Name("c".to_owned()), JsonToken::Colon, JsonToken::Value(JsonType::Int),
+            JsonToken::Comma, JsonToken::Name("aA".to_owned()), JsonToken::Colon, JsonToken::Value(JsonType::Int),
+            JsonToken::ObjectEnd,
+        ];
+
+        let lexer = Lexer::new(json);
+        let tokens: Vec<JsonToken> = lexer.start_lex().into_iter().map(|token| token.value).collect();
+
+        assert_eq!(tokens, expected_result);
+    }
+
     #[test]
     fn lex_string() {
         let json = ":\"hola\"";
         let expected_result = vec![
             JsonToken::Colon,
-            JsonToken::Value(JsonType::String),
+            JsonToken::Value(JsonType::String("hola".to_owned())),
+        ];
+
+        let lexer = Lexer::new(json);
+        let tokens: Vec<JsonToken> = lexer.start_lex().into_iter().map(|token| token.value).collect();
+
+        assert_eq!(tokens, expected_result);
+    }
+
+    #[test]
+    fn lex_string_with_escapes() {
+        let json = r#":"a\"b\\c""#;
+        let expected_result = vec![
+            JsonToken::Colon,
+            JsonToken::Value(JsonType::String("a\"b\\c".to_owned())),
+        ];
+
+        let lexer = Lexer::new(json);
+        let tokens: Vec<JsonToken> = lexer.start_lex().into_iter().map(|token| token.value).collect();
+
+        assert_eq!(tokens, expected_result);
+    }
+
+    #[test]
+    fn lex_unicode_escapes() {
+        let json = r#"["a\u0041b", "quote\"inside", "\u00e9\"", "\ud83d\ude00", "\uzz"]"#;
+        let expected_result = vec![
+            JsonToken::ArrayStart,
+            JsonToken::Value(JsonType::String("aAb".to_owned())),
+            JsonToken::Comma,
+            JsonToken::Value(JsonType::String("quote\"inside".to_owned())),
+            JsonToken::Comma,
+            JsonToken::Value(JsonType::String("é\"".to_owned())),
+            JsonToken::Comma,
+            JsonToken::Value(JsonType::String("😀".to_owned())),
+            JsonToken::Comma,
+            JsonToken::Value(JsonType::String("\\uzz".to_owned())),
+            JsonToken::ArrayEnd,
+        ];
+
+        let lexer = Lexer::new(json);
+        let tokens: Vec<JsonToken> = lexer.start_lex().into_iter().map(|token| token.value).collect();
+
+        assert_eq!(tokens, expected_result);
+    }
+
+    #[test]
+    fn lex_root_values() {
+        let lex = |json: &str| -> Vec<JsonToken> { Lexer::new(json).start_lex().into_iter().map(|token| token.value).collect() };
+
+        assert_eq!(lex(" \"root\" "), vec![JsonToken::Value(JsonType::String("root".to_owned()))]);
+        assert_eq!(lex("12"), vec![JsonToken::Value(JsonType::Int)]);
+        assert_eq!(lex("\"a\" \"b\"")[1], JsonToken::Unexpected('"'));
+        assert_eq!(lex("true 1").last(), Some(&JsonToken::Unexpected('1')));
+    }
+
+    #[test]
+    fn lex_string_across_lines() {
+        let json = "{\"f1\": \"first\nsecond\", \"f2\": \"a\\\nb\",\n\"f3\": 1}";
+        let expected_result = vec![
+            JsonToken::ObjectStart,
+            JsonToken::Name("f1".to_owned()), JsonToken::Colon, JsonToken::Value(JsonType::String("first\nsecond".to_owned())), JsonToken::Comma,
+            JsonToken::Name("f2".to_owned()), JsonToken::Colon, JsonToken::Value(JsonType::String("a\nb".to_owned())), JsonToken::Comma,
+            JsonToken::Name("f3".to_owned()), JsonToken::Colon, JsonToken::Value(JsonType::Int),
+            JsonToken::ObjectEnd,
+        ];
+
+        let tokens = Lexer::new(json).start_lex();
+
+        assert_eq!((tokens[3].line, tokens[3].col), (0, 7));
+        assert_eq!(tokens[11].line, 3);
+        assert_eq!(tokens.into_iter().map(|token| token.value).collect::<Vec<JsonToken>>(), expected_result);
+    }
+
+    #[test]
+    fn lex_string_array() {
+        let json = "{\"f1\": [\"a\", \"b\"], \"f2\": 1}";
+        let expected_result = vec![
+            JsonToken::ObjectStart, JsonToken::Name("f1".to_owned()), JsonToken::Colon, JsonToken::ArrayStart,
+            JsonToken::Value(JsonType::String("a".to_owned())), JsonToken::Comma,
+            JsonToken::Value(JsonType::String("b".to_owned())), JsonToken::ArrayEnd, JsonToken::Comma,
+            JsonToken::Name("f2".to_owned()), JsonToken::Colon, JsonToken::Value(JsonType::Int), JsonToken::ObjectEnd,
         ];
 
         let lexer = Lexer::new(json);
@@ -416,13 +899,35 @@ mod tests {
     }
 
 
+    #[test]
+    fn lex_literals_exactly() {
+        let json = "[false, null, nul, fals, true]";
+
+        let expected_result = vec![
+            JsonToken::ArrayStart, JsonToken::Value(JsonType::Bool), JsonToken::Comma, JsonToken::Value(JsonType::Null),
+            JsonToken::Comma, JsonToken::Unexpected('n'), JsonToken::Comma, JsonToken::Unexpected('f'), JsonToken::Comma,
+            JsonToken::Value(JsonType::Bool), JsonToken::ArrayEnd,
+        ];
+
+        let lexer = Lexer::new(json);
+        let tokens: Vec<JsonToken> = lexer.start_lex().into_iter().map(|token| token.value).collect();
+
+        assert_eq!(tokens, expected_result);
+
+        let lexer = Lexer::new("{\"a\":[true, nul],\n\"b\":[xyz]}");
+        let tokens: Vec<(usize, usize, JsonToken)> = lexer.start_lex().into_iter().map(|token| (token.line, token.col, token.value)).collect();
+
+        assert_eq!(tokens[6], (0, 12, JsonToken::Unexpected('n')));
+        assert_eq!(tokens[12], (1, 5, JsonToken::Unexpected('x')));
+    }
+
     #[test]
     fn lex_bad_name_after_degree_symbol() {
         let json = "{\"2\":\"aº\", \"ab\": 32}";
 
         let expected_result = vec![
             JsonToken::ObjectStart, JsonToken::Name("2".to_owned()), JsonToken::Colon,
-            JsonToken::Value(JsonType::String), JsonToken::Comma, JsonToken::Name("ab".to_owned()),
+            JsonToken::Value(JsonType::String("aº".to_owned())), JsonToken::Comma, JsonToken::Name("ab".to_owned()),
             JsonToken::Colon, JsonToken::Value(JsonType::Int), JsonToken::ObjectEnd,
         ];
 
@@ -443,6 +948,65 @@ mod tests {
         assert_eq!(tokens, expected_result)
     }
 
+    #[test]
+    fn lex_null_end_on_right_bracket() {
+        let json = "[null]";
+        let expected_result = vec![
+            JsonToken::ArrayStart, JsonToken::Value(JsonType::Null), JsonToken::ArrayEnd,
+        ];
+
+        let lexer = Lexer::new(json);
+        let tokens: Vec<JsonToken> = lexer.start_lex().into_iter().map(|token| token.value).collect();
+        assert_eq!(tokens, expected_result)
+    }
+
+    #[test]
+    fn lex_name_after_array() {
+        let json = "{\"a\":[1],\"b\":\"x\"}";
+        let expected_result = vec![
+            JsonToken::ObjectStart, JsonToken::Name("a".to_owned()), JsonToken::Colon,
+            JsonToken::ArrayStart, JsonToken::Value(JsonType::Int), JsonToken::ArrayEnd, JsonToken::Comma,
+            JsonToken::Name("b".to_owned()), JsonToken::Colon, JsonToken::Value(JsonType::String("x".to_owned())),
+            JsonToken::ObjectEnd,
+        ];
+
+        let lexer = Lexer::new(json);
+        let tokens: Vec<JsonToken> = lexer.start_lex().into_iter().map(|token| token.value).collect();
+        assert_eq!(tokens, expected_result)
+    }
+
+    #[test]
+    fn lex_non_finite_floats() {
+        let json = "{\"x\":Infinity,\"y\":NaN,\"z\":[-Infinity]}";
+        let expected_result = vec![
+            JsonToken::ObjectStart,
+            JsonToken::Name("x".to_owned()), JsonToken::Colon, JsonToken::Value(JsonType::Float), JsonToken::Comma,
+            JsonToken::Name("y".to_owned()), JsonToken::Colon, JsonToken::Value(JsonType::Float), JsonToken::Comma,
+            JsonToken::Name("z".to_owned()), JsonToken::Colon, JsonToken::ArrayStart, JsonToken::Value(JsonType::Float), JsonToken::ArrayEnd,
+            JsonToken::ObjectEnd,
+        ];
+
+        let lexer = Lexer::new(json).allow_nan(true);
+        let tokens: Vec<JsonToken> = lexer.start_lex().into_iter().map(|token| token.value).collect();
+        assert_eq!(tokens, expected_result)
+    }
+
+    #[test]
+    fn lex_decimal_comma() {
+        let json = "{\"x\":1,5,\"y\":[1,5]}";
+        let expected_result = vec![
+            JsonToken::ObjectStart,
+            JsonToken::Name("x".to_owned()), JsonToken::Colon, JsonToken::Value(JsonType::Float), JsonToken::Comma,
+            JsonToken::Name("y".to_owned()), JsonToken::Colon, JsonToken::ArrayStart,
+            JsonToken::Value(JsonType::Int), JsonToken::Comma, JsonToken::Value(JsonType::Int), JsonToken::ArrayEnd,
+            JsonToken::ObjectEnd,
+        ];
+
+        let lexer = Lexer::new(json).decimal_comma(true);
+        let tokens: Vec<JsonToken> = lexer.start_lex().into_iter().map(|token| token.value).collect();
+        assert_eq!(tokens, expected_result)
+    }
+
     #[test]
     fn null_token() {
         let json = "null";
@@ -1,2 +1,54 @@
+use crate::lib::model::parse_options::ParseOptions;
+use crate::lib::model::token::Token;
+use crate::lib::model::tree::JsonRoot;
+use crate::lib::parser::lexer::Lexer;
+use crate::lib::parser::tokenizer::{Tokenizer, TokenizerError};
+
 pub mod tokenizer;
-pub mod lexer;
\ No newline at end of file
+pub mod lexer;
+pub mod schema;
+
+/// Parses a JSON document whatever its root holds: an object, an array or a single value.
+/// # Arguments
+/// * `json` JSON document.
+/// # Returns
+/// Fields of the root object, type of the root array or the value of the document, see [JsonRoot].
+pub fn parse_value(json: &str) -> Result<JsonRoot, TokenizerError> {
+    parse_value_with(json, &ParseOptions::default())
+}
+
+/// Same as [parse_value], reading the document according to `options`.
+pub fn parse_value_with(json: &str, options: &ParseOptions) -> Result<JsonRoot, TokenizerError> {
+    Tokenizer::new(lex(json, options), options.clone()).start_tokenizer()
+}
+
+/// Splits a JSON document into tokens, reading the literals `options` allows.
+pub fn lex(json: &str, options: &ParseOptions) -> Vec<Token> {
+    Lexer::new(json)
+        .allow_nan(options.allow_nan)
+        .decimal_comma(options.decimal_comma)
+        .start_lex()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::lib::model::tree::{JsonArrayType, JsonRoot, JsonTree, Samples};
+    use crate::lib::parser::parse_value;
+
+    #[test]
+    fn any_root_value() {
+        assert_eq!(parse_value("{\"a\": 1}").unwrap(), JsonRoot::Object(vec![JsonTree::Int("a".to_owned())]));
+        assert_eq!(parse_value("[true, false]").unwrap(), JsonRoot::Array(JsonArrayType::Bool));
+        assert_eq!(parse_value("42").unwrap(), JsonRoot::Value(JsonTree::Int("root".to_owned())));
+        assert_eq!(parse_value(" -1.5e3 ").unwrap(), JsonRoot::Value(JsonTree::Float("root".to_owned())));
+        assert_eq!(parse_value("\"hi\"").unwrap(), JsonRoot::Value(JsonTree::String("root".to_owned(), Samples::new("hi".to_owned()))));
+        assert_eq!(parse_value("false").unwrap(), JsonRoot::Value(JsonTree::Bool("root".to_owned())));
+    }
+
+    #[test]
+    fn fail_on_content_after_value() {
+        for json in ["42 43", "\"a\" \"b\"", "true x", "null"] {
+            assert!(parse_value(json).is_err(), "{}", json);
+        }
+    }
+}
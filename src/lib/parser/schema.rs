@@ -0,0 +1,305 @@
+use std::collections::BTreeMap;
+use std::iter::Peekable;
+use std::vec::IntoIter;
+use thiserror::Error;
+use crate::lib::model::token::{JsonToken, JsonType, Token};
+use crate::lib::model::tree::{JsonArrayType, JsonRoot, JsonTree, Samples};
+
+#[derive(Error, Debug)]
+pub enum SchemaError {
+    #[error("syntax error detected near line {} column {1}", .0 + 1)]
+    SyntaxError(usize, usize),
+    #[error("unexpected end of the schema")]
+    UnexpectedEnd,
+    #[error("empty input, expected a JSON schema")]
+    EmptyInput,
+    #[error("the schema of {0} is not an object")]
+    NotAnObject(String),
+    #[error("the schema of {0} has no type")]
+    MissingType(String),
+    #[error("unsupported type {1} in the schema of {0}")]
+    UnsupportedType(String, String),
+    #[error("the root of the schema must be an object or an array")]
+    UnsupportedRoot,
+}
+
+/// Descriptions of the properties of a schema, by field path as written in the `path_overrides` option.
+pub type FieldDocs = BTreeMap<String, String>;
+
+/// JSON value of a schema. Numbers and booleans are never needed to read a schema, they are not kept.
+#[derive(Debug)]
+enum Value {
+    Object(Vec<(String, Value)>),
+    Array(Vec<Value>),
+    String(String),
+    Other,
+}
+
+/// Reads a JSON Schema instead of a JSON sample. Properties missing from `required` become optional,
+/// as do those whose type includes `null`.
+#[derive(Debug)]
+pub struct SchemaReader {
+    token_iter: Peekable<IntoIter<Token>>,
+}
+
+impl SchemaReader {
+    /// Creates a new schema reader.
+    /// # Arguments
+    /// * `tokens` tokens produced by the lexer.
+    pub fn new(tokens: Vec<Token>) -> Self {
+        Self {
+            token_iter: tokens.into_iter().peekable(),
+        }
+    }
+
+    /// Parses the next value of the schema.
+    fn parse_value(&mut self) -> Result<Value, SchemaError> {
+        match self.token_iter.next() {
+            Some(Token { value: JsonToken::ObjectStart, .. }) => self.parse_object(),
+            Some(Token { value: JsonToken::ArrayStart, .. }) => self.parse_array(),
+            Some(Token { value: JsonToken::Value(JsonType::String(value)), .. }) => Ok(Value::String(value)),
+            Some(Token { value: JsonToken::Value(_), .. }) => Ok(Value::Other),
+            Some(token) => Err(SchemaError::SyntaxError(token.line, token.col)),
+            None => Err(SchemaError::UnexpectedEnd),
+        }
+    }
+
+    /// Parses the members of an object, its start being already consumed.
+    fn parse_object(&mut self) -> Result<Value, SchemaError> {
+        let mut members = Vec::new();
+
+        while let Some(token) = self.token_iter.next() {
+            match token.value {
+                JsonToken::ObjectEnd => return Ok(Value::Object(members)),
+                JsonToken::Comma => (),
+                JsonToken::Name(name) => {
+                    match self.token_iter.next() {
+                        Some(Token { value: JsonToken::Colon, .. }) => (),
+                        Some(token) => return Err(SchemaError::SyntaxError(token.line, token.col)),
+                        None => return Err(SchemaError::UnexpectedEnd),
+                    }
+                    members.push((name, self.parse_value()?));
+                }
+                _ => return Err(SchemaError::SyntaxError(token.line, token.col)),
+            }
+        }
+
+        Err(SchemaError::UnexpectedEnd)
+    }
+
+    /// Parses the elements of an array, its start being already consumed.
+    fn parse_array(&mut self) -> Result<Value, SchemaError> {
+        let mut elements = Vec::new();
+
+        while let Some(token) = self.token_iter.peek() {
+            match token.value {
+                JsonToken::ArrayEnd => {
+                    self.token_iter.next();
+                    return Ok(Value::Array(elements));
+                }
+                JsonToken::Comma => {
+                    self.token_iter.next();
+                }
+                _ => elements.push(self.parse_value()?),
+            }
+        }
+
+        Err(SchemaError::UnexpectedEnd)
+    }
+
+    /// Starts the conversion from the tokens of a schema to a [JsonRoot].
+    /// # Returns
+    /// Fields of the root object or type of the root array described by the schema,
+    /// along with the `description` of its properties.
+    pub fn start_schema(mut self) -> Result<(JsonRoot, FieldDocs), SchemaError> {
+        if self.token_iter.peek().is_none() {
+            return Err(SchemaError::EmptyInput);
+        }
+
+        let schema = self.parse_value()?;
+        let mut docs = FieldDocs::new();
+        let root = match field_tree(String::from("root"), "", &schema, &mut docs)?.into_required() {
+            JsonTree::JsonObject(_, fields) => JsonRoot::Object(fields),
+            JsonTree::JsonArray(_, array_type) => JsonRoot::Array(array_type),
+            _ => return Err(SchemaError::UnsupportedRoot),
+        };
+
+        Ok((root, docs))
+    }
+}
+
+/// Member `key` of a schema object.
+fn member<'a>(members: &'a [(String, Value)], key: &str) -> Option<&'a Value> {
+    members.iter().find(|(name, _)| name == key).map(|(_, value)| value)
+}
+
+/// Strings held by an array member of a schema object, such as `required` or `enum`.
+fn strings<'a>(members: &'a [(String, Value)], key: &str) -> Vec<&'a str> {
+    match member(members, key) {
+        Some(Value::Array(values)) => values.iter().filter_map(|value| match value {
+            Value::String(value) => Some(value.as_str()),
+            _ => None,
+        }).collect(),
+        _ => vec![],
+    }
+}
+
+/// Builds the tree of a field from its schema.
+/// # Arguments
+/// * `name` name of the field.
+/// * `path` path of the field, see [FieldDocs].
+/// * `schema` schema of the field.
+/// * `docs` descriptions found so far.
+fn field_tree(name: String, path: &str, schema: &Value, docs: &mut FieldDocs) -> Result<JsonTree, SchemaError> {
+    let members = match schema {
+        Value::Object(members) => members,
+        _ => return Err(SchemaError::NotAnObject(name)),
+    };
+
+    if let Some(Value::String(description)) = member(members, "description") {
+        if !path.is_empty() {
+            docs.insert(path.to_owned(), description.clone());
+        }
+    }
+
+    let (type_str, nullable) = match member(members, "type") {
+        Some(Value::String(type_str)) => (Some(type_str.as_str()), false),
+        Some(Value::Array(_)) => {
+            let types = strings(members, "type");
+            (types.iter().copied().find(|type_str| *type_str != "null"), types.contains(&"null"))
+        }
+        _ if member(members, "properties").is_some() => (Some("object"), false),
+        _ if member(members, "items").is_some() => (Some("array"), false),
+        _ => (None, false),
+    };
+
+    let mut tree = match type_str {
+        Some("integer") => JsonTree::Int(name),
+        Some("number") => JsonTree::Float(name),
+        Some("boolean") => JsonTree::Bool(name),
+        Some("string") => {
+            let values: Vec<String> = strings(members, "enum").into_iter().map(str::to_owned).collect();
+            let count = values.len();
+            JsonTree::String(name, Samples { values, count, truncated: false })
+        }
+        Some("object") => JsonTree::JsonObject(name, object_fields(members, path, docs)?),
+        Some("array") => {
+            let array_type = match member(members, "items") {
+                Some(items) => {
+                    field_tree(name.clone(), &format!("{}[]", path), items, docs)?.into_array_type().unwrap_or(JsonArrayType::Unknown)
+                }
+                None => JsonArrayType::Unknown,
+            };
+            JsonTree::JsonArray(name, array_type)
+        }
+        Some(type_str) => return Err(SchemaError::UnsupportedType(name, type_str.to_owned())),
+        None => return Err(SchemaError::MissingType(name)),
+    };
+
+    if nullable {
+        tree.set_optional();
+    }
+
+    Ok(tree)
+}
+
+/// Builds the fields of an object schema, those missing from `required` being optional.
+fn object_fields(members: &[(String, Value)], path: &str, docs: &mut FieldDocs) -> Result<Vec<JsonTree>, SchemaError> {
+    let required = strings(members, "required");
+    let properties = match member(members, "properties") {
+        Some(Value::Object(properties)) => properties.as_slice(),
+        Some(_) => return Err(SchemaError::NotAnObject(String::from("properties"))),
+        None => &[],
+    };
+
+    let mut fields = Vec::new();
+    for (name, schema) in properties {
+        let field_path = if path.is_empty() { name.clone() } else { format!("{}.{}", path, name) };
+        let mut tree = field_tree(name.clone(), &field_path, schema, docs)?;
+        if !required.contains(&name.as_str()) {
+            tree.set_optional();
+        }
+        fields.push(tree);
+    }
+
+    Ok(fields)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::lib::model::tree::{JsonArrayType, JsonRoot, JsonTree, Samples};
+    use crate::lib::parser::lexer::Lexer;
+    use crate::lib::parser::schema::SchemaReader;
+
+    #[test]
+    fn required_properties() {
+        let json = "{\"type\": \"object\", \"properties\": {\"a\": {\"type\": \"integer\"}, \"b\": {\"type\": \"string\"}}, \"required\": [\"a\"]}";
+        let expected_result = vec![
+            JsonTree::Int("a".to_owned()),
+            JsonTree::Optional(Box::new(JsonTree::String("b".to_owned(), Samples::default()))),
+        ];
+
+        let lexer = Lexer::new(json);
+        let tree = SchemaReader::new(lexer.start_lex()).start_schema().unwrap().0;
+
+        assert_eq!(tree, JsonRoot::Object(expected_result));
+    }
+
+    #[test]
+    fn nested_schema() {
+        let json = r#"{
+            "type": "object",
+            "required": ["items", "kind"],
+            "properties": {
+                "kind": {"type": "string", "enum": ["a", "b"]},
+                "items": {
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "required": ["x"],
+                        "properties": {"x": {"type": "number"}, "y": {"type": ["boolean", "null"]}}
+                    }
+                }
+            }
+        }"#;
+        let expected_result = vec![
+            JsonTree::String("kind".to_owned(), Samples { values: vec!["a".to_owned(), "b".to_owned()], count: 2, truncated: false }),
+            JsonTree::JsonArray("items".to_owned(), JsonArrayType::JsonObject(vec![
+                JsonTree::Float("x".to_owned()),
+                JsonTree::Optional(Box::new(JsonTree::Bool("y".to_owned()))),
+            ])),
+        ];
+
+        let lexer = Lexer::new(json);
+        let tree = SchemaReader::new(lexer.start_lex()).start_schema().unwrap().0;
+
+        assert_eq!(tree, JsonRoot::Object(expected_result));
+    }
+
+    #[test]
+    fn property_descriptions() {
+        let json = r#"{
+            "type": "object",
+            "properties": {
+                "id": {"type": "integer", "description": "Unique identifier."},
+                "tags": {"type": "array", "items": {"type": "object", "properties": {"name": {"type": "string", "description": "Tag name."}}}}
+            }
+        }"#;
+
+        let lexer = Lexer::new(json);
+        let (_, docs) = SchemaReader::new(lexer.start_lex()).start_schema().unwrap();
+
+        assert_eq!(docs.len(), 2);
+        assert_eq!(docs["id"], "Unique identifier.");
+        assert_eq!(docs["tags[].name"], "Tag name.");
+    }
+
+    #[test]
+    #[should_panic(expected = "has no type")]
+    fn fail_on_missing_type() {
+        let json = "{\"type\": \"object\", \"properties\": {\"a\": {}}}";
+
+        let lexer = Lexer::new(json);
+        SchemaReader::new(lexer.start_lex()).start_schema().unwrap_or_else(|e| panic!("{}", e));
+    }
+}
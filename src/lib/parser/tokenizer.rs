@@ -1,11 +1,18 @@
 use std::iter::{Enumerate, Peekable};
+use std::mem;
 use std::vec::IntoIter;
-use crate::lib::model::tree::{JsonArrayType, JsonTree};
+use crate::lib::model::tree::{JsonArrayType, JsonRoot, JsonTree, Samples};
 use thiserror::Error;
+use crate::lib::model::parse_options::ParseOptions;
 use crate::lib::model::token::{JsonToken, JsonType, Token};
 use crate::lib::parser::tokenizer::TokenizerError::{NullNotSupportedError, SyntaxError};
 
+/// Maximum number of distinct values kept for a string field.
+/// Value based inference only needs to tell small sets of values apart.
+const MAX_SAMPLES: usize = 32;
+
 #[derive(Error, Debug)]
+#[allow(clippy::enum_variant_names)]
 pub enum TokenizerError {
     #[error("syntax error detected near line {} column {1}", .0 + 1)]
     SyntaxError(usize, usize),
@@ -13,19 +20,45 @@ pub enum TokenizerError {
     UnknownSyntaxError,
     #[error("null values are not supported. Near line {} column {1}", .0 + 1)]
     NullNotSupportedError(usize, usize),
-    #[error("empty arrays are not supported. Near line {} column {1}", .0 + 1)]
-    EmptyArrayNotSupportedError(usize, usize),
+    #[error("empty input, expected a JSON value")]
+    EmptyInputError,
+    #[error("missing field name before ':' near line {} column {1}", .0 + 1)]
+    MissingNameError(usize, usize),
+    #[error("value without field name near line {} column {1}", .0 + 1)]
+    ValueWithoutNameError(usize, usize),
+    #[error("unexpected trailing content after the root value near line {} column {1}", .0 + 1)]
+    TrailingContentError(usize, usize),
+}
+
+impl TokenizerError {
+    /// Line and column of the source the error was detected at, both 0 based, if known.
+    pub fn position(&self) -> Option<(usize, usize)> {
+        match self {
+            SyntaxError(line, col)
+            | NullNotSupportedError(line, col)
+            | TokenizerError::MissingNameError(line, col)
+            | TokenizerError::ValueWithoutNameError(line, col)
+            | TokenizerError::TrailingContentError(line, col) => Some((*line, *col)),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Debug)]
 pub struct Tokenizer {
     token_iter: Peekable<Enumerate<IntoIter<Token>>>,
+    options: ParseOptions,
 }
 
 impl Tokenizer {
-    pub fn new(tokens: Vec<Token>) -> Self {
+    /// Creates a new tokenizer.
+    /// # Arguments
+    /// * `tokens` tokens produced by the lexer.
+    /// * `options` see [ParseOptions].
+    pub fn new(tokens: Vec<Token>, options: ParseOptions) -> Self {
         Self {
             token_iter: tokens.into_iter().enumerate().peekable(),
+            options,
         }
     }
 
@@ -36,72 +69,356 @@ impl Tokenizer {
     /// # Returns
     /// New array type
     /// # Errors
-    /// If the old type is not the same as the new type, an error will be returned.
-    fn parse_new_array_type(old_type: Option<JsonArrayType>, new_type: JsonArrayType, line: usize, col: usize) -> Result<JsonArrayType, TokenizerError> {
-        if let Some(old_type) = old_type {
-            if old_type == new_type {
-                return Ok(new_type);
+    /// If the old type is not the same as the new type, an error will be returned,
+    /// unless [ParseOptions::fallback] is enabled, in which case the array type becomes [JsonArrayType::Unknown].
+    fn parse_new_array_type(&self, old_type: Option<JsonArrayType>, new_type: JsonArrayType, line: usize, col: usize) -> Result<JsonArrayType, TokenizerError> {
+        if let Some(mut old_type) = old_type {
+            return match Self::merge_array_types(&mut old_type, new_type) {
+                Ok(()) => Ok(old_type),
+                Err(_) if self.options.fallback => Ok(JsonArrayType::Unknown),
+                Err(_) => Err(SyntaxError(line, col)),
+            };
+        }
+
+        Ok(new_type)
+    }
+
+    /// Merges two array types of the same kind. Object fields and string samples are joined.
+    /// # Errors
+    /// If the types are not of the same kind, `new_type` is given back.
+    fn merge_array_types(old_type: &mut JsonArrayType, new_type: JsonArrayType) -> Result<(), JsonArrayType> {
+        if matches!(old_type, JsonArrayType::Fixed(..)) || matches!(new_type, JsonArrayType::Fixed(..)) {
+            return Self::merge_fixed_array_types(old_type, new_type);
+        }
+
+        match (old_type, new_type) {
+            (JsonArrayType::String(old_samples), JsonArrayType::String(new_samples)) => {
+                Self::merge_samples(old_samples, new_samples);
+                Ok(())
+            }
+            (JsonArrayType::JsonObject(old_tree), JsonArrayType::JsonObject(new_tree)) => {
+                Self::merge_object_fields(old_tree, new_tree, false);
+                Ok(())
+            }
+            (JsonArrayType::JsonArray(old_inner), JsonArrayType::JsonArray(new_inner)) => {
+                Self::merge_array_types(old_inner, *new_inner)
+                    .map_err(|new_inner| JsonArrayType::JsonArray(Box::new(new_inner)))
+            }
+            (JsonArrayType::Tagged(_, old_variants), JsonArrayType::Tagged(_, new_variants)) => {
+                for (value, new_tree) in new_variants {
+                    match old_variants.iter_mut().find(|(old_value, _)| *old_value == value) {
+                        Some((_, old_tree)) => Self::merge_object_fields(old_tree, new_tree, false),
+                        None => old_variants.push((value, new_tree)),
+                    }
+                }
+                Ok(())
+            }
+            (JsonArrayType::Tuple(old_positions), JsonArrayType::Tuple(new_positions)) => Self::merge_tuple_types(old_positions, new_positions)
+                .map_err(JsonArrayType::Tuple),
+            (JsonArrayType::Optional(old_inner), JsonArrayType::Optional(new_inner)) => Self::merge_array_types(old_inner, *new_inner)
+                .map_err(|new_inner| JsonArrayType::Optional(Box::new(new_inner))),
+            (JsonArrayType::Optional(old_inner), new_type) => Self::merge_array_types(old_inner, new_type),
+            (old_type, JsonArrayType::Optional(new_inner)) => {
+                Self::merge_array_types(old_type, *new_inner).map_err(|new_inner| JsonArrayType::Optional(Box::new(new_inner)))?;
+                let inner = mem::replace(old_type, JsonArrayType::Unknown);
+                *old_type = JsonArrayType::Optional(Box::new(inner));
+                Ok(())
+            }
+            (old_type @ JsonArrayType::Empty, new_type) => {
+                *old_type = new_type;
+                Ok(())
+            }
+            (_, JsonArrayType::Empty) => Ok(()),
+            (JsonArrayType::Unknown, _) => Ok(()),
+            (old_type, JsonArrayType::Unknown) => {
+                *old_type = JsonArrayType::Unknown;
+                Ok(())
+            }
+            (old_type, new_type) if *old_type == new_type => Ok(()),
+            (_, new_type) => Err(new_type),
+        }
+    }
+
+    /// Merges two array types, one of them at least being [JsonArrayType::Fixed].
+    /// The merged type stays fixed only if both arrays have the same number of elements.
+    fn merge_fixed_array_types(old_type: &mut JsonArrayType, new_type: JsonArrayType) -> Result<(), JsonArrayType> {
+        let (old_len, mut old_inner) = match mem::replace(old_type, JsonArrayType::Unknown) {
+            JsonArrayType::Fixed(len, inner) => (Some(len), *inner),
+            old_inner => (None, old_inner),
+        };
+        let (new_len, new_inner) = match new_type {
+            JsonArrayType::Fixed(len, inner) => (Some(len), *inner),
+            new_inner => (None, new_inner),
+        };
+
+        let result = Self::merge_array_types(&mut old_inner, new_inner).map_err(|new_inner| match new_len {
+            Some(len) => JsonArrayType::Fixed(len, Box::new(new_inner)),
+            None => new_inner,
+        });
+
+        *old_type = match old_len {
+            Some(len) if result.is_err() || old_len == new_len => JsonArrayType::Fixed(len, Box::new(old_inner)),
+            _ => old_inner,
+        };
+
+        result
+    }
+
+    /// Merges the positions of two tuples. Tuples of different lengths, or with different kinds in a position,
+    /// can't be merged. Positions of the same kind that still can't be merged, such as arrays of different types,
+    /// get the unknown type.
+    /// # Errors
+    /// If the tuples can't be merged, `new_positions` is given back.
+    fn merge_tuple_types(old_positions: &mut [JsonArrayType], new_positions: Vec<JsonArrayType>) -> Result<(), Vec<JsonArrayType>> {
+        let same_kinds = old_positions.len() == new_positions.len()
+            && old_positions.iter().zip(&new_positions).all(|(old_type, new_type)| mem::discriminant(old_type) == mem::discriminant(new_type));
+        if !same_kinds {
+            return Err(new_positions);
+        }
+
+        for (old_type, new_type) in old_positions.iter_mut().zip(new_positions) {
+            if Self::merge_array_types(old_type, new_type).is_err() {
+                *old_type = JsonArrayType::Unknown;
             }
+        }
+
+        Ok(())
+    }
+
+    /// Joins the fields of an object with those of another object of the same array.
+    /// Fields with the same name and kind are merged, the rest are appended.
+    /// A field holding null merges with a field of any kind, the merged field taking the other kind and becoming optional.
+    /// # Arguments
+    /// * `missing_optional` fields missing from either object become optional, in nested objects too.
+    fn merge_object_fields(old_tree: &mut Vec<JsonTree>, new_tree: Vec<JsonTree>, missing_optional: bool) {
+        // Only needed to find the missing fields.
+        let new_names: Vec<String> = if missing_optional {
+            new_tree.iter().map(|field| field.name().to_owned()).collect()
+        } else {
+            Vec::new()
+        };
+        let old_len = old_tree.len();
 
-            if let JsonArrayType::JsonObject(mut old_tree) = old_type {
-                if let JsonArrayType::JsonObject(new_tree) = new_type {
-                    new_tree.into_iter().for_each(|json_type| {
-                        if !old_tree.contains(&json_type) {
-                            old_tree.push(json_type)
+        for json_type in new_tree {
+            let old_field = old_tree.iter_mut().find(|old_field| {
+                old_field.name() == json_type.name() && (
+                    mem::discriminant(old_field.required()) == mem::discriminant(json_type.required())
+                        || matches!(old_field.required(), JsonTree::Null(_))
+                        || matches!(json_type.required(), JsonTree::Null(_))
+                )
+            });
+
+            let rejected = match old_field {
+                Some(old_field) => {
+                    if let JsonTree::Optional(_) = json_type {
+                        old_field.set_optional();
+                    }
+
+                    let old_null = matches!(old_field.required(), JsonTree::Null(_));
+                    match json_type.into_required() {
+                        JsonTree::Null(_) => None,
+                        json_type if old_null => {
+                            *old_field = json_type;
+                            old_field.set_optional();
+                            None
                         }
-                    });
+                        json_type => Self::merge_fields(old_field.required_mut(), json_type, missing_optional).err(),
+                    }
+                }
+                None => Some(json_type),
+            };
 
-                    return Ok(JsonArrayType::JsonObject(old_tree));
+            if let Some(json_type) = rejected {
+                old_tree.push(json_type);
+            }
+        }
+
+        if missing_optional {
+            for (i, field) in old_tree.iter_mut().enumerate() {
+                if i >= old_len || !new_names.iter().any(|name| name == field.name()) {
+                    field.set_optional();
                 }
+            }
+        }
+    }
+
+    /// Type of an object held by an array. If the object holds the [ParseOptions::discriminator] field,
+    /// it is tagged by the field's value.
+    /// # Arguments
+    /// * `object` fields of the object.
+    fn object_array_type(&self, mut object: Vec<JsonTree>) -> JsonArrayType {
+        if let Some(ref key) = self.options.discriminator {
+            let position = object.iter().position(|field| matches!(field, JsonTree::String(name, _) if name == key));
 
-                return Err(SyntaxError(line, col));
+            if let Some(position) = position {
+                if let JsonTree::String(_, samples) = object.remove(position) {
+                    let value = samples.values.into_iter().next().unwrap_or_default();
+                    return JsonArrayType::Tagged(key.clone(), vec![(value, object)]);
+                }
             }
+        }
 
-            return Err(TokenizerError::SyntaxError(line, col));
+        JsonArrayType::JsonObject(object)
+    }
+
+    /// Tree of a field holding an object. With [ParseOptions::integer_maps], objects whose keys are all integers,
+    /// but not `0, 1, 2...`, become maps whose value type merges the types of their values, as arrays do.
+    /// # Arguments
+    /// * `name` name of the field.
+    /// * `object` fields of the object.
+    fn object_field(&self, name: String, object: Vec<JsonTree>, line: usize, col: usize) -> Result<JsonTree, TokenizerError> {
+        if !self.options.integer_maps || !Self::is_integer_map(&object) {
+            return Ok(JsonTree::JsonObject(name, object));
         }
 
-        Ok(new_type)
+        let mut value_type = None;
+        for field in object {
+            let new_type = field.into_array_type().unwrap_or(JsonArrayType::Unknown);
+            value_type = Some(self.parse_new_array_type(value_type, new_type, line, col)?);
+        }
+
+        Ok(JsonTree::Map(name, value_type.unwrap_or(JsonArrayType::Unknown)))
+    }
+
+    /// Checks whether the keys of an object are all integers, not contiguous from 0, and its values aren't maps.
+    fn is_integer_map(object: &[JsonTree]) -> bool {
+        let mut keys = Vec::new();
+        for field in object {
+            match field.name().parse::<i32>() {
+                Ok(key) if !matches!(field, JsonTree::Map(..)) => keys.push(key),
+                _ => return false,
+            }
+        }
+
+        keys.sort_unstable();
+        !keys.is_empty() && !keys.iter().copied().eq(0..keys.len() as i32)
+    }
+
+    /// Merges a root object into the previous ones. Fields missing from either of them become optional,
+    /// those of nested objects included. The objects of arrays are merged as within a single document.
+    pub fn merge_documents(old_tree: &mut Vec<JsonTree>, new_tree: Vec<JsonTree>) {
+        Self::merge_object_fields(old_tree, new_tree, true);
+    }
+
+    /// Merges two fields with the same name and kind.
+    /// # Arguments
+    /// * `missing_optional` see [Tokenizer::merge_object_fields].
+    /// # Errors
+    /// If the fields can't be merged (arrays of different types), `new_field` is given back.
+    fn merge_fields(old_field: &mut JsonTree, new_field: JsonTree, missing_optional: bool) -> Result<(), JsonTree> {
+        match (old_field, new_field) {
+            (JsonTree::String(_, old_samples), JsonTree::String(_, new_samples)) => {
+                Self::merge_samples(old_samples, new_samples);
+                Ok(())
+            }
+            (JsonTree::JsonObject(_, old_tree), JsonTree::JsonObject(_, new_tree)) => {
+                Self::merge_object_fields(old_tree, new_tree, missing_optional);
+                Ok(())
+            }
+            (JsonTree::JsonArray(_, old_type), JsonTree::JsonArray(name, new_type)) => {
+                Self::merge_array_types(old_type, new_type)
+                    .map_err(|new_type| JsonTree::JsonArray(name, new_type))
+            }
+            (JsonTree::Map(_, old_type), JsonTree::Map(name, new_type)) => {
+                Self::merge_array_types(old_type, new_type)
+                    .map_err(|new_type| JsonTree::Map(name, new_type))
+            }
+            (old_field, new_field) if *old_field == new_field => Ok(()),
+            (_, new_field) => Err(new_field),
+        }
+    }
+
+    /// Adds the values not seen yet to `old_samples`, up to [MAX_SAMPLES] distinct values.
+    /// Once full, the samples are marked as truncated if a new value is left out.
+    fn merge_samples(old_samples: &mut Samples, new_samples: Samples) {
+        old_samples.count += new_samples.count;
+        old_samples.truncated |= new_samples.truncated;
+
+        for sample in new_samples.values {
+            if old_samples.values.contains(&sample) {
+                continue;
+            }
+
+            if old_samples.values.len() >= MAX_SAMPLES {
+                old_samples.truncated = true;
+                break;
+            }
+            old_samples.values.push(sample);
+        }
     }
 
     /// Parses an array token
     /// # Arguments
     /// * `name` name of the array's field
     fn parse_array_token(&mut self, name: String) -> Result<JsonTree, TokenizerError> {
+        if self.options.shallow_arrays {
+            return self.skip_array_token(name);
+        }
+
         let mut array_type = None;
+        let mut elements = Vec::new();
+        let mut null = None;
+        let mut len = 0;
 
         while let Some((_, token)) = self.token_iter.next() {
+            if let JsonToken::ArrayStart | JsonToken::ObjectStart | JsonToken::Value(_) = token.value {
+                len += 1;
+            }
+
             match token.value {
                 JsonToken::ArrayEnd => {
-                    if let Some(array_type) = array_type {
-                        return Ok(JsonTree::JsonArray(name, array_type));
+                    if Self::is_tuple(&elements) {
+                        if let Some((line, col)) = null {
+                            return Err(NullNotSupportedError(line, col));
+                        }
+
+                        let positions = elements.into_iter().map(|(element_type, _, _)| element_type).collect();
+                        return Ok(JsonTree::JsonArray(name, JsonArrayType::Tuple(positions)));
                     }
 
-                    return Err(TokenizerError::EmptyArrayNotSupportedError(token.line, token.col));
+                    for (element_type, line, col) in elements {
+                        array_type = Some(self.parse_new_array_type(array_type, element_type, line, col)?);
+                    }
+
+                    let array_type = match (array_type, null) {
+                        (Some(array_type), Some(_)) => JsonArrayType::Optional(Box::new(array_type)),
+                        (Some(array_type), None) => array_type,
+                        (None, Some((line, col))) => return Err(NullNotSupportedError(line, col)),
+                        (None, None) => return Ok(JsonTree::JsonArray(name, JsonArrayType::Empty)),
+                    };
+
+                    if self.options.fixed_arrays {
+                        return Ok(JsonTree::JsonArray(name, JsonArrayType::Fixed(len, Box::new(array_type))));
+                    }
+
+                    return Ok(JsonTree::JsonArray(name, array_type));
                 }
                 JsonToken::ArrayStart => {
                     let deeper_array = self.parse_array_token(String::new())?;
                     if let JsonTree::JsonArray(_, deeper_array_type) = deeper_array {
                         let deeper_array_type = JsonArrayType::JsonArray(Box::new(deeper_array_type));
-                        array_type = Some(Self::parse_new_array_type(array_type, deeper_array_type, token.line, token.col)?);
+                        self.add_element(&mut array_type, &mut elements, deeper_array_type, token.line, token.col)?;
                     } else {
                         return Err(TokenizerError::UnknownSyntaxError);
                     }
                 }
                 JsonToken::ObjectStart => {
                     let object = self.parse_object_token()?;
-                    let new_type = JsonArrayType::JsonObject(object);
-                    array_type = Some(Self::parse_new_array_type(array_type, new_type, token.line, token.col)?);
+                    let new_type = self.object_array_type(object);
+                    self.add_element(&mut array_type, &mut elements, new_type, token.line, token.col)?;
+                }
+                JsonToken::Value(JsonType::Null) if self.options.null_elements => {
+                    null.get_or_insert((token.line, token.col));
                 }
                 JsonToken::Value(json_type) => {
-                    let value_type;
-                    match json_type {
-                        JsonType::Int => value_type = JsonArrayType::Int,
-                        JsonType::Float => value_type = JsonArrayType::Float,
-                        JsonType::Bool => value_type = JsonArrayType::Bool,
-                        JsonType::String => value_type = JsonArrayType::String,
+                    let value_type = match json_type {
+                        JsonType::Int => JsonArrayType::Int,
+                        JsonType::Float => JsonArrayType::Float,
+                        JsonType::Bool => JsonArrayType::Bool,
+                        JsonType::String(value) => JsonArrayType::String(Samples::new(value)),
                         JsonType::Null => return Err(NullNotSupportedError(token.line, token.col)),
-                    }
-                    array_type = Some(Self::parse_new_array_type(array_type, value_type, token.line, token.col)?);
+                    };
+                    self.add_element(&mut array_type, &mut elements, value_type, token.line, token.col)?;
                 }
                 JsonToken::Comma => (),
                 _ => {
@@ -117,6 +434,41 @@ impl Tokenizer {
         }
     }
 
+    /// Adds the type of an element to the type of its array. With [ParseOptions::tuples], elements are kept
+    /// in `elements` along with their position in the source, until the end of the array tells whether it's a tuple.
+    fn add_element(&self, array_type: &mut Option<JsonArrayType>, elements: &mut Vec<(JsonArrayType, usize, usize)>, new_type: JsonArrayType, line: usize, col: usize) -> Result<(), TokenizerError> {
+        if self.options.tuples {
+            elements.push((new_type, line, col));
+        } else {
+            *array_type = Some(self.parse_new_array_type(array_type.take(), new_type, line, col)?);
+        }
+
+        Ok(())
+    }
+
+    /// Checks whether the elements kept by [Tokenizer::add_element] are of different kinds, making their array a tuple.
+    fn is_tuple(elements: &[(JsonArrayType, usize, usize)]) -> bool {
+        elements.windows(2).any(|pair| mem::discriminant(&pair[0].0) != mem::discriminant(&pair[1].0))
+    }
+
+    /// Consumes the tokens of an array up to its matching end, see [ParseOptions::shallow_arrays].
+    /// # Arguments
+    /// * `name` name of the array's field
+    fn skip_array_token(&mut self, name: String) -> Result<JsonTree, TokenizerError> {
+        let mut depth = 0;
+
+        for (_, token) in self.token_iter.by_ref() {
+            match token.value {
+                JsonToken::ArrayStart => depth += 1,
+                JsonToken::ArrayEnd if depth == 0 => return Ok(JsonTree::JsonArray(name, JsonArrayType::Unknown)),
+                JsonToken::ArrayEnd => depth -= 1,
+                _ => (),
+            }
+        }
+
+        Err(TokenizerError::UnknownSyntaxError)
+    }
+
     /// Parses a list of [JsonToken]
     /// # Returns
     /// Object's fields
@@ -132,13 +484,17 @@ impl Tokenizer {
                     if actual_count != 0 {
                         if let Some(name) = name {
                             let deeper_object = self.parse_object_token()?;
-                            object.push(JsonTree::JsonObject(name, deeper_object));
+                            object.push(self.object_field(name, deeper_object, token.line, token.col)?);
                         } else {
-                            return Err(TokenizerError::SyntaxError(token.line, token.col));
+                            return Err(TokenizerError::ValueWithoutNameError(token.line, token.col));
                         }
                         name = None;
                     }
                 }
+                // A name without value, e.g. before a literal the lexer left out.
+                JsonToken::ObjectEnd if name.is_some() => {
+                    return Err(TokenizerError::SyntaxError(token.line, token.col));
+                }
                 JsonToken::ObjectEnd => {
                     return Ok(object);
                 }
@@ -147,7 +503,7 @@ impl Tokenizer {
                         let array = self.parse_array_token(name)?;
                         object.push(array)
                     } else {
-                        return Err(TokenizerError::SyntaxError(token.line, token.col));
+                        return Err(TokenizerError::ValueWithoutNameError(token.line, token.col));
                     }
 
                     name = None;
@@ -155,7 +511,7 @@ impl Tokenizer {
                 JsonToken::ArrayEnd => {}
                 JsonToken::Colon => {
                     if name.is_none() {
-                        return Err(TokenizerError::SyntaxError(token.line, token.col));
+                        return Err(TokenizerError::MissingNameError(token.line, token.col));
                     }
                 }
                 JsonToken::Comma => {}
@@ -172,15 +528,19 @@ impl Tokenizer {
                             JsonType::Int => object.push(JsonTree::Int(name)),
                             JsonType::Float => object.push(JsonTree::Float(name)),
                             JsonType::Bool => object.push(JsonTree::Bool(name)),
-                            JsonType::String => object.push(JsonTree::String(name)),
+                            JsonType::String(value) => object.push(JsonTree::String(name, Samples::new(value))),
+                            JsonType::Null if self.options.null_fields => object.push(JsonTree::Optional(Box::new(JsonTree::Null(name)))),
                             JsonType::Null => return Err(TokenizerError::NullNotSupportedError(token.line, token.col))
                         }
                     } else {
-                        return Err(TokenizerError::SyntaxError(token.line, token.col));
+                        return Err(TokenizerError::ValueWithoutNameError(token.line, token.col));
                     }
 
                     name = None;
                 }
+                JsonToken::Unexpected(_) => {
+                    return Err(TokenizerError::SyntaxError(token.line, token.col));
+                }
             }
 
             actual_count += 1;
@@ -190,9 +550,70 @@ impl Tokenizer {
 
     /// Starts the conversion from the list of tokens to a [JsonTree].
     /// # Returns
-    /// JSON representation in list of [JsonTree]
-    pub fn start_tokenizer(mut self) -> Result<Vec<JsonTree>, TokenizerError> {
-        Ok(self.parse_object_token()?)
+    /// JSON representation as a [JsonRoot]: the fields of the root object, the type of the root array
+    /// or the value held by the whole document.
+    pub fn start_tokenizer(mut self) -> Result<JsonRoot, TokenizerError> {
+        if self.token_iter.peek().is_none() {
+            return Err(TokenizerError::EmptyInputError);
+        }
+
+        if let Some((_, Token { value: JsonToken::ArrayStart, .. })) = self.token_iter.peek() {
+            self.token_iter.next();
+            let array_type = match self.parse_array_token(String::new())? {
+                JsonTree::JsonArray(_, array_type) => array_type,
+                _ => return Err(TokenizerError::UnknownSyntaxError),
+            };
+            self.end_of_input()?;
+            return Ok(JsonRoot::Array(array_type));
+        }
+
+        if let Some((_, Token { value: JsonToken::Value(_), .. })) = self.token_iter.peek() {
+            let root = self.parse_root_value()?;
+            self.end_of_input()?;
+            return Ok(JsonRoot::Value(root));
+        }
+
+        let mut root = self.parse_object_token()?;
+
+        if self.options.concatenated {
+            while let Some((_, token)) = self.token_iter.peek() {
+                if token.value != JsonToken::ObjectStart {
+                    return Err(SyntaxError(token.line, token.col));
+                }
+
+                let document = self.parse_object_token()?;
+                Self::merge_documents(&mut root, document);
+            }
+        }
+
+        self.end_of_input()?;
+        Ok(JsonRoot::Object(root))
+    }
+
+    /// Parses a document holding a single value, neither an object nor an array, as a field named `root`.
+    fn parse_root_value(&mut self) -> Result<JsonTree, TokenizerError> {
+        let token = match self.token_iter.next() {
+            Some((_, token)) => token,
+            None => return Err(TokenizerError::UnknownSyntaxError),
+        };
+
+        let name = String::from("root");
+        match token.value {
+            JsonToken::Value(JsonType::Int) => Ok(JsonTree::Int(name)),
+            JsonToken::Value(JsonType::Float) => Ok(JsonTree::Float(name)),
+            JsonToken::Value(JsonType::Bool) => Ok(JsonTree::Bool(name)),
+            JsonToken::Value(JsonType::String(value)) => Ok(JsonTree::String(name, Samples::new(value))),
+            JsonToken::Value(JsonType::Null) => Err(NullNotSupportedError(token.line, token.col)),
+            _ => Err(SyntaxError(token.line, token.col)),
+        }
+    }
+
+    /// Checks that nothing but whitespace follows the root value.
+    fn end_of_input(&mut self) -> Result<(), TokenizerError> {
+        match self.token_iter.peek() {
+            Some((_, token)) => Err(TokenizerError::TrailingContentError(token.line, token.col)),
+            None => Ok(()),
+        }
     }
 }
 
@@ -200,7 +621,8 @@ impl Tokenizer {
 mod tests {
     use crate::lib::parser::lexer::Lexer;
     use crate::lib::parser::tokenizer::Tokenizer;
-    use crate::lib::model::tree::{JsonArrayType, JsonTree};
+    use crate::lib::model::parse_options::ParseOptions;
+    use crate::lib::model::tree::{JsonArrayType, JsonRoot, JsonTree, Samples};
 
     #[test]
     #[should_panic]
@@ -208,15 +630,26 @@ mod tests {
         let json = "\"error\": \"oof\"";
 
         let lexer = Lexer::new(json);
-        let tokenizer = Tokenizer::new(lexer.start_lex());
+        let tokenizer = Tokenizer::new(lexer.start_lex(), ParseOptions::default());
         tokenizer.start_tokenizer().unwrap();
     }
 
+    #[test]
+    fn empty_input() {
+        for json in ["", " \n\t\n"] {
+            let lexer = Lexer::new(json);
+            let tokenizer = Tokenizer::new(lexer.start_lex(), ParseOptions::default());
+            let error = tokenizer.start_tokenizer().unwrap_err();
+
+            assert_eq!(error.to_string(), "empty input, expected a JSON value");
+        }
+    }
+
     #[test]
     fn simple_json() {
         let json = "{\"f1\": \"value\", \"f2\": true, \"f3\": 45.3, \"f4\": 12}";
         let expected_result = vec![
-            JsonTree::String("f1".to_owned()),
+            JsonTree::String("f1".to_owned(), Samples::new("value".to_owned())),
             JsonTree::Bool("f2".to_owned()),
             JsonTree::Float("f3".to_owned()),
             JsonTree::Int("f4".to_owned()),
@@ -224,10 +657,10 @@ mod tests {
 
         let lexer = Lexer::new(json);
         let lexer_result = lexer.start_lex();
-        let tokenizer = Tokenizer::new(lexer_result);
+        let tokenizer = Tokenizer::new(lexer_result, ParseOptions::default());
 
         let tree = tokenizer.start_tokenizer().unwrap();
-        assert_eq!(tree, expected_result);
+        assert_eq!(tree, JsonRoot::Object(expected_result));
     }
 
     #[test]
@@ -235,13 +668,13 @@ mod tests {
         let json = "{\"f1\": \"value\", \"f2\": true, \"f3\": { \"f4\": 45.3, \"f5\": {\"f6\": true, \"f7\":\"aº\"}}, \"a\": 32}";
 
         let expected_result = vec![
-            JsonTree::String("f1".to_owned()),
+            JsonTree::String("f1".to_owned(), Samples::new("value".to_owned())),
             JsonTree::Bool("f2".to_owned()),
             JsonTree::JsonObject("f3".to_owned(), vec![
                 JsonTree::Float("f4".to_owned()),
                 JsonTree::JsonObject("f5".to_owned(), vec![
                     JsonTree::Bool("f6".to_owned()),
-                    JsonTree::String("f7".to_owned()),
+                    JsonTree::String("f7".to_owned(), Samples::new("aº".to_owned())),
                 ]),
             ]),
             JsonTree::Int("a".to_owned()),
@@ -249,10 +682,10 @@ mod tests {
 
         let lexer = Lexer::new(json);
         let lexer_result = lexer.start_lex();
-        let tokenizer = Tokenizer::new(lexer_result);
+        let tokenizer = Tokenizer::new(lexer_result, ParseOptions::default());
         let tree = tokenizer.start_tokenizer().unwrap();
 
-        assert_eq!(tree, expected_result);
+        assert_eq!(tree, JsonRoot::Object(expected_result));
     }
 
     #[test]
@@ -265,10 +698,10 @@ mod tests {
 
         let lexer = Lexer::new(json);
         let lexer_result = lexer.start_lex();
-        let tokenizer = Tokenizer::new(lexer_result);
+        let tokenizer = Tokenizer::new(lexer_result, ParseOptions::default());
         let tree = tokenizer.start_tokenizer().unwrap();
 
-        assert_eq!(tree, expected_result);
+        assert_eq!(tree, JsonRoot::Object(expected_result));
     }
 
 
@@ -283,10 +716,10 @@ mod tests {
 
         let lexer = Lexer::new(json);
         let lexer_result = lexer.start_lex();
-        let tokenizer = Tokenizer::new(lexer_result);
+        let tokenizer = Tokenizer::new(lexer_result, ParseOptions::default());
         let tree = tokenizer.start_tokenizer().unwrap();
 
-        assert_eq!(tree, expected_result);
+        assert_eq!(tree, JsonRoot::Object(expected_result));
     }
 
     #[test]
@@ -296,7 +729,7 @@ mod tests {
 
         let lexer = Lexer::new(json);
         let lexer_result = lexer.start_lex();
-        let tokenizer = Tokenizer::new(lexer_result);
+        let tokenizer = Tokenizer::new(lexer_result, ParseOptions::default());
         tokenizer.start_tokenizer().unwrap();
     }
 
@@ -315,10 +748,10 @@ mod tests {
 
         let lexer = Lexer::new(json);
         let lexer_result = lexer.start_lex();
-        let tokenizer = Tokenizer::new(lexer_result);
+        let tokenizer = Tokenizer::new(lexer_result, ParseOptions::default());
         let tree = tokenizer.start_tokenizer().unwrap();
 
-        assert_eq!(tree, expected_result);
+        assert_eq!(tree, JsonRoot::Object(expected_result));
     }
 
     #[test]
@@ -337,12 +770,310 @@ mod tests {
 
         let lexer = Lexer::new(json);
         let lexer_result = lexer.start_lex();
-        let tokenizer = Tokenizer::new(lexer_result);
+        let tokenizer = Tokenizer::new(lexer_result, ParseOptions::default());
+        let tree = tokenizer.start_tokenizer().unwrap();
+
+        assert_eq!(tree, JsonRoot::Object(expected_result));
+    }
+
+    #[test]
+    fn array_object_merging_same_fields() {
+        let json = "{\"f1\": [{\"f2\": \"yes\", \"f3\": {\"f4\": 1}}, {\"f2\": \"no\", \"f3\": {\"f5\": true}}, {\"f2\": \"yes\"}]}";
+        let expected_result = vec![
+            JsonTree::JsonArray("f1".to_owned(), JsonArrayType::JsonObject(
+                vec![
+                    JsonTree::String("f2".to_owned(), Samples { values: vec!["yes".to_owned(), "no".to_owned()], count: 3, truncated: false }),
+                    JsonTree::JsonObject("f3".to_owned(), vec![
+                        JsonTree::Int("f4".to_owned()),
+                        JsonTree::Bool("f5".to_owned()),
+                    ]),
+                ]
+            ))
+        ];
+
+        let lexer = Lexer::new(json);
+        let lexer_result = lexer.start_lex();
+        let tokenizer = Tokenizer::new(lexer_result, ParseOptions::default());
+        let tree = tokenizer.start_tokenizer().unwrap();
+
+        assert_eq!(tree, JsonRoot::Object(expected_result));
+    }
+
+    #[test]
+    fn root_array() {
+        let json = "[{\"f1\": 1}, {\"f2\": true}]";
+        let expected_result = JsonRoot::Array(JsonArrayType::JsonObject(vec![
+            JsonTree::Int("f1".to_owned()),
+            JsonTree::Bool("f2".to_owned()),
+        ]));
+
+        let lexer = Lexer::new(json);
+        let lexer_result = lexer.start_lex();
+        let tokenizer = Tokenizer::new(lexer_result, ParseOptions::default());
         let tree = tokenizer.start_tokenizer().unwrap();
 
         assert_eq!(tree, expected_result);
     }
 
+    #[test]
+    fn mixed_array_fallback() {
+        let json = "{\"f1\": [{\"f2\": 1}, \"error\"]}";
+        let expected_result = vec![
+            JsonTree::JsonArray("f1".to_owned(), JsonArrayType::Unknown)
+        ];
+
+        let lexer = Lexer::new(json);
+        let lexer_result = lexer.start_lex();
+        let tokenizer = Tokenizer::new(lexer_result, ParseOptions { fallback: true, ..ParseOptions::default() });
+        let tree = tokenizer.start_tokenizer().unwrap();
+
+        assert_eq!(tree, JsonRoot::Object(expected_result));
+    }
+
+    #[test]
+    fn discriminated_array() {
+        let json = "[{\"type\": \"a\", \"x\": 1}, {\"type\": \"b\", \"y\": 2}, {\"type\": \"a\", \"z\": true}]";
+        let expected_result = JsonArrayType::Tagged("type".to_owned(), vec![
+            ("a".to_owned(), vec![JsonTree::Int("x".to_owned()), JsonTree::Bool("z".to_owned())]),
+            ("b".to_owned(), vec![JsonTree::Int("y".to_owned())]),
+        ]);
+
+        let lexer = Lexer::new(json);
+        let lexer_result = lexer.start_lex();
+        let options = ParseOptions { discriminator: Some("type".to_owned()), ..ParseOptions::default() };
+        let tree = Tokenizer::new(lexer_result, options).start_tokenizer().unwrap();
+
+        assert_eq!(tree, JsonRoot::Array(expected_result));
+    }
+
+    #[test]
+    fn shallow_arrays() {
+        let json = "{\"f1\": [[1, 2], [{\"f2\": 1}], []], \"f3\": [], \"f4\": 1}";
+        let expected_result = vec![
+            JsonTree::JsonArray("f1".to_owned(), JsonArrayType::Unknown),
+            JsonTree::JsonArray("f3".to_owned(), JsonArrayType::Unknown),
+            JsonTree::Int("f4".to_owned()),
+        ];
+
+        let lexer = Lexer::new(json);
+        let lexer_result = lexer.start_lex();
+        let tokenizer = Tokenizer::new(lexer_result, ParseOptions { shallow_arrays: true, ..ParseOptions::default() });
+        let tree = tokenizer.start_tokenizer().unwrap();
+
+        assert_eq!(tree, JsonRoot::Object(expected_result));
+    }
+
+    #[test]
+    fn concatenated_documents() {
+        let json = "{\"a\": 1}{\"a\": 1, \"b\": 2}";
+        let expected_result = vec![
+            JsonTree::Int("a".to_owned()),
+            JsonTree::Optional(Box::new(JsonTree::Int("b".to_owned()))),
+        ];
+
+        let lexer = Lexer::new(json);
+        let lexer_result = lexer.start_lex();
+        let tokenizer = Tokenizer::new(lexer_result, ParseOptions { concatenated: true, ..ParseOptions::default() });
+        let tree = tokenizer.start_tokenizer().unwrap();
+
+        assert_eq!(tree, JsonRoot::Object(expected_result));
+    }
+
+    #[test]
+    fn concatenated_nested_documents() {
+        let json = "{\"a\": {\"b\": 1, \"c\": [{\"d\": 1}]}}\n{\"a\": {\"e\": true, \"c\": [{\"f\": 1}]}}\n";
+        let expected_result = vec![
+            JsonTree::JsonObject("a".to_owned(), vec![
+                JsonTree::Optional(Box::new(JsonTree::Int("b".to_owned()))),
+                JsonTree::JsonArray("c".to_owned(), JsonArrayType::JsonObject(vec![
+                    JsonTree::Int("d".to_owned()),
+                    JsonTree::Int("f".to_owned()),
+                ])),
+                JsonTree::Optional(Box::new(JsonTree::Bool("e".to_owned()))),
+            ]),
+        ];
+
+        let lexer = Lexer::new(json);
+        let tokenizer = Tokenizer::new(lexer.start_lex(), ParseOptions { concatenated: true, ..ParseOptions::default() });
+        let tree = tokenizer.start_tokenizer().unwrap();
+
+        assert_eq!(tree, JsonRoot::Object(expected_result));
+    }
+
+    #[test]
+    #[should_panic]
+    fn mixed_array_error() {
+        let json = "{\"f1\": [{\"f2\": 1}, \"error\"]}";
+
+        let lexer = Lexer::new(json);
+        let lexer_result = lexer.start_lex();
+        let tokenizer = Tokenizer::new(lexer_result, ParseOptions::default());
+        tokenizer.start_tokenizer().unwrap();
+    }
+
+    #[test]
+    fn null_elements() {
+        let json = "{\"f1\": [1, null, 2], \"f2\": [[1], null], \"f3\": [{\"f4\": [null, true]}, {\"f4\": [false]}]}";
+        let expected_result = vec![
+            JsonTree::JsonArray("f1".to_owned(), JsonArrayType::Optional(Box::new(JsonArrayType::Int))),
+            JsonTree::JsonArray("f2".to_owned(), JsonArrayType::Optional(Box::new(JsonArrayType::JsonArray(Box::new(JsonArrayType::Int))))),
+            JsonTree::JsonArray("f3".to_owned(), JsonArrayType::JsonObject(vec![
+                JsonTree::JsonArray("f4".to_owned(), JsonArrayType::Optional(Box::new(JsonArrayType::Bool))),
+            ])),
+        ];
+
+        let lexer = Lexer::new(json);
+        let options = ParseOptions { null_elements: true, ..ParseOptions::default() };
+        let tree = Tokenizer::new(lexer.start_lex(), options).start_tokenizer().unwrap();
+
+        assert_eq!(tree, JsonRoot::Object(expected_result));
+    }
+
+    #[test]
+    fn null_fields() {
+        let json = r#"{"f1": [
+            {"f2": null, "f3": 1, "f4": null, "f5": {"f6": true}},
+            {"f2": 5, "f3": null, "f4": null, "f5": null}
+        ], "f7": null}"#;
+        let expected_result = vec![
+            JsonTree::JsonArray("f1".to_owned(), JsonArrayType::JsonObject(vec![
+                JsonTree::Optional(Box::new(JsonTree::Int("f2".to_owned()))),
+                JsonTree::Optional(Box::new(JsonTree::Int("f3".to_owned()))),
+                JsonTree::Optional(Box::new(JsonTree::Null("f4".to_owned()))),
+                JsonTree::Optional(Box::new(JsonTree::JsonObject("f5".to_owned(), vec![JsonTree::Bool("f6".to_owned())]))),
+            ])),
+            JsonTree::Optional(Box::new(JsonTree::Null("f7".to_owned()))),
+        ];
+
+        let lexer = Lexer::new(json);
+        let options = ParseOptions { null_fields: true, ..ParseOptions::default() };
+        let tree = Tokenizer::new(lexer.start_lex(), options).start_tokenizer().unwrap();
+
+        assert_eq!(tree, JsonRoot::Object(expected_result));
+    }
+
+    #[test]
+    #[should_panic(expected = "null values are not supported")]
+    fn fail_on_null_array() {
+        let json = "{ \"f2\": [null, null] }";
+
+        let lexer = Lexer::new(json);
+        let options = ParseOptions { null_elements: true, ..ParseOptions::default() };
+        Tokenizer::new(lexer.start_lex(), options).start_tokenizer().unwrap_or_else(|e| panic!("{}", e));
+    }
+
+    #[test]
+    fn fixed_arrays() {
+        let json = "{\"f1\": [{\"f2\": [1.5, 2.5, 3.5], \"f3\": [1]}, {\"f2\": [4.5, 5.5, 6.5], \"f3\": [2, 3]}]}";
+        let expected_result = vec![
+            JsonTree::JsonArray("f1".to_owned(), JsonArrayType::Fixed(2, Box::new(JsonArrayType::JsonObject(vec![
+                JsonTree::JsonArray("f2".to_owned(), JsonArrayType::Fixed(3, Box::new(JsonArrayType::Float))),
+                JsonTree::JsonArray("f3".to_owned(), JsonArrayType::Int),
+            ])))),
+        ];
+
+        let lexer = Lexer::new(json);
+        let options = ParseOptions { fixed_arrays: true, ..ParseOptions::default() };
+        let tree = Tokenizer::new(lexer.start_lex(), options).start_tokenizer().unwrap();
+
+        assert_eq!(tree, JsonRoot::Object(expected_result));
+    }
+
+    #[test]
+    fn tuples() {
+        let json = "{\"f1\": [[1, \"a\", true], [2, \"b\", false]], \"f2\": [1, 2], \"f3\": [1.5, [1]]}";
+        let expected_result = vec![
+            JsonTree::JsonArray("f1".to_owned(), JsonArrayType::JsonArray(Box::new(JsonArrayType::Tuple(vec![
+                JsonArrayType::Int,
+                JsonArrayType::String(Samples { values: vec!["a".to_owned(), "b".to_owned()], count: 2, truncated: false }),
+                JsonArrayType::Bool,
+            ])))),
+            JsonTree::JsonArray("f2".to_owned(), JsonArrayType::Int),
+            JsonTree::JsonArray("f3".to_owned(), JsonArrayType::Tuple(vec![
+                JsonArrayType::Float,
+                JsonArrayType::JsonArray(Box::new(JsonArrayType::Int)),
+            ])),
+        ];
+
+        let lexer = Lexer::new(json);
+        let options = ParseOptions { tuples: true, ..ParseOptions::default() };
+        let tree = Tokenizer::new(lexer.start_lex(), options).start_tokenizer().unwrap();
+
+        assert_eq!(tree, JsonRoot::Object(expected_result));
+
+        let json = "{\"f1\": [[1, \"a\"], [\"b\", 2]]}";
+        let lexer = Lexer::new(json);
+        let options = ParseOptions { tuples: true, ..ParseOptions::default() };
+
+        assert!(Tokenizer::new(lexer.start_lex(), options).start_tokenizer().is_err());
+    }
+
+    #[test]
+    fn integer_maps() {
+        let json = "{\"f1\": {\"100\": {\"f2\": 1}, \"250\": {\"f3\": true}}, \"f4\": {\"0\": \"a\", \"1\": \"b\"}}";
+        let expected_result = vec![
+            JsonTree::Map("f1".to_owned(), JsonArrayType::JsonObject(vec![
+                JsonTree::Int("f2".to_owned()),
+                JsonTree::Bool("f3".to_owned()),
+            ])),
+            JsonTree::JsonObject("f4".to_owned(), vec![
+                JsonTree::String("0".to_owned(), Samples::new("a".to_owned())),
+                JsonTree::String("1".to_owned(), Samples::new("b".to_owned())),
+            ]),
+        ];
+
+        let lexer = Lexer::new(json);
+        let options = ParseOptions { integer_maps: true, ..ParseOptions::default() };
+        let tree = Tokenizer::new(lexer.start_lex(), options).start_tokenizer().unwrap();
+
+        assert_eq!(tree, JsonRoot::Object(expected_result));
+    }
+
+    #[test]
+    #[should_panic(expected = "syntax error")]
+    fn fail_on_bad_literal() {
+        let json = "{ \"f1\": fals }";
+        let lexer = Lexer::new(json);
+        Tokenizer::new(lexer.start_lex(), ParseOptions::default()).start_tokenizer().unwrap_or_else(|e| panic!("{}", e));
+    }
+
+    #[test]
+    fn bad_literals_in_arrays() {
+        let error = |json: &str| Tokenizer::new(Lexer::new(json).start_lex(), ParseOptions::default()).start_tokenizer().unwrap_err().to_string();
+
+        assert_eq!(error("{\"a\":[true, nul]}"), "syntax error detected near line 1 column 12");
+        assert_eq!(error("{\"a\":[xyz]}"), "syntax error detected near line 1 column 6");
+        assert_eq!(error("{\"a\":[nul]}"), "syntax error detected near line 1 column 6");
+    }
+
+    #[test]
+    fn missing_names() {
+        let error = |json: &str| Tokenizer::new(Lexer::new(json).start_lex(), ParseOptions::default()).start_tokenizer().unwrap_err().to_string();
+
+        assert_eq!(error("{:1}"), "missing field name before ':' near line 1 column 1");
+        assert_eq!(error("{1}"), "value without field name near line 1 column 1");
+        assert_eq!(error("{\"f1\": 1,\n [2]}"), "value without field name near line 2 column 1");
+    }
+
+    #[test]
+    fn trailing_content() {
+        let error = |json: &str| Tokenizer::new(Lexer::new(json).start_lex(), ParseOptions::default()).start_tokenizer().unwrap_err().to_string();
+
+        assert_eq!(error("{\"a\":1} x"), "unexpected trailing content after the root value near line 1 column 8");
+        assert_eq!(error("{\"a\":1}\n{\"b\":2}"), "unexpected trailing content after the root value near line 2 column 0");
+        assert_eq!(error("[1] 2"), "unexpected trailing content after the root value near line 1 column 4");
+
+        let tree = Tokenizer::new(Lexer::new("{\"a\":1}\n\n").start_lex(), ParseOptions::default()).start_tokenizer().unwrap();
+        assert_eq!(tree, JsonRoot::Object(vec![JsonTree::Int("a".to_owned())]));
+    }
+
+    #[test]
+    fn number_at_line_end() {
+        let json = "{\n\"f1\": 1\n}";
+        let tree = Tokenizer::new(Lexer::new(json).start_lex(), ParseOptions::default()).start_tokenizer().unwrap();
+
+        assert_eq!(tree, JsonRoot::Object(vec![JsonTree::Int("f1".to_owned())]));
+    }
 
     #[test]
     #[should_panic(expected = "null values are not supported")]
@@ -350,17 +1081,49 @@ mod tests {
         let json = "{ \"f2\": null }";
         let lexer = Lexer::new(json);
         let lexer_result = lexer.start_lex();
-        let tokenizer = Tokenizer::new(lexer_result);
-        tokenizer.start_tokenizer().unwrap();
+        let tokenizer = Tokenizer::new(lexer_result, ParseOptions::default());
+        tokenizer.start_tokenizer().unwrap_or_else(|e| panic!("{}", e));
     }
 
     #[test]
-    #[should_panic(expected = "empty arrays are not supported")]
-    fn fail_on_empty_array() {
-        let json = "{ \"f2\": [] }";
+    fn empty_arrays() {
+        let json = "{\"f1\": [], \"f2\": [{\"a\": []}, {\"a\": [1]}, {\"a\": []}]}";
+        let expected_result = vec![
+            JsonTree::JsonArray("f1".to_owned(), JsonArrayType::Empty),
+            JsonTree::JsonArray("f2".to_owned(), JsonArrayType::JsonObject(vec![
+                JsonTree::JsonArray("a".to_owned(), JsonArrayType::Int),
+            ])),
+        ];
+
         let lexer = Lexer::new(json);
-        let lexer_result = lexer.start_lex();
-        let tokenizer = Tokenizer::new(lexer_result);
-        tokenizer.start_tokenizer().unwrap();
+        let tokenizer = Tokenizer::new(lexer.start_lex(), ParseOptions::default());
+
+        assert_eq!(tokenizer.start_tokenizer().unwrap(), JsonRoot::Object(expected_result));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn jagged_arrays() {
+        let json = "{\"f1\": [[1], [2, 3], [4, 5, 6]], \"f2\": [[[1]], [[2, 3], [4]]]}";
+        let expected_result = vec![
+            JsonTree::JsonArray("f1".to_owned(), JsonArrayType::JsonArray(Box::new(JsonArrayType::Int))),
+            JsonTree::JsonArray("f2".to_owned(), JsonArrayType::JsonArray(Box::new(
+                JsonArrayType::JsonArray(Box::new(JsonArrayType::Int)),
+            ))),
+        ];
+
+        let lexer = Lexer::new(json);
+        let tokenizer = Tokenizer::new(lexer.start_lex(), ParseOptions::default());
+
+        assert_eq!(tokenizer.start_tokenizer().unwrap(), JsonRoot::Object(expected_result));
+
+        let lexer = Lexer::new(json);
+        let options = ParseOptions { fixed_arrays: true, ..ParseOptions::default() };
+        let tree = Tokenizer::new(lexer.start_lex(), options).start_tokenizer().unwrap();
+        let expected_result = JsonTree::JsonArray("f1".to_owned(), JsonArrayType::Fixed(3, Box::new(JsonArrayType::JsonArray(Box::new(JsonArrayType::Int)))));
+
+        match tree {
+            JsonRoot::Object(fields) => assert_eq!(fields[0], expected_result),
+            root => panic!("expected an object, got {:?}", root),
+        }
+    }
+}
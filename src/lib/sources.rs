@@ -0,0 +1,83 @@
+use std::collections::BTreeMap;
+use crate::lib::model::tree::{JsonArrayType, JsonRoot, JsonTree};
+
+/// Names of the files each field appeared in, by field path as written in the `path_overrides` option.
+pub type FieldSources = BTreeMap<String, Vec<String>>;
+
+/// Records the fields of a document, before it is merged with the documents of other files.
+/// # Arguments
+/// * `sources` files of the fields recorded so far.
+/// * `root` tree of the document.
+/// * `source` name of the file holding the document.
+pub fn add_sources(sources: &mut FieldSources, root: &JsonRoot, source: &str) {
+    match root {
+        JsonRoot::Object(fields) => add_fields(sources, fields, "", source),
+        JsonRoot::Array(array_type) => add_array(sources, array_type, "[]", source),
+        JsonRoot::Value(_) => (),
+    }
+}
+
+fn add_fields(sources: &mut FieldSources, fields: &[JsonTree], path: &str, source: &str) {
+    for field in fields {
+        let name = field.name();
+        let field_path = if path.is_empty() { name.to_owned() } else { format!("{}.{}", path, name) };
+
+        match field.required() {
+            JsonTree::JsonObject(_, tree) => add_fields(sources, tree, &field_path, source),
+            JsonTree::JsonArray(_, array_type) => add_array(sources, array_type, &format!("{}[]", field_path), source),
+            JsonTree::Map(_, value_type) => add_array(sources, value_type, &format!("{}{{}}", field_path), source),
+            _ => (),
+        }
+
+        let files = sources.entry(field_path).or_default();
+        if !files.iter().any(|file| file == source) {
+            files.push(source.to_owned());
+        }
+    }
+}
+
+fn add_array(sources: &mut FieldSources, array_type: &JsonArrayType, path: &str, source: &str) {
+    match array_type {
+        JsonArrayType::JsonObject(tree) => add_fields(sources, tree, path, source),
+        JsonArrayType::JsonArray(array_type) => add_array(sources, array_type, &format!("{}[]", path), source),
+        JsonArrayType::Optional(array_type) | JsonArrayType::Fixed(_, array_type) => add_array(sources, array_type, path, source),
+        JsonArrayType::Tagged(_, variants) => {
+            for (_, tree) in variants {
+                add_fields(sources, tree, path, source);
+            }
+        }
+        JsonArrayType::Tuple(positions) => {
+            for array_type in positions {
+                add_array(sources, array_type, path, source);
+            }
+        }
+        _ => (),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::lib::model::tree::{JsonArrayType, JsonRoot, JsonTree};
+    use crate::lib::sources::{FieldSources, add_sources};
+
+    #[test]
+    fn sources_by_path() {
+        let a = JsonRoot::Object(vec![
+            JsonTree::Int("id".to_owned()),
+            JsonTree::JsonArray("items".to_owned(), JsonArrayType::JsonObject(vec![JsonTree::Bool("done".to_owned())])),
+        ]);
+        let b = JsonRoot::Object(vec![
+            JsonTree::Int("id".to_owned()),
+            JsonTree::Float("score".to_owned()),
+        ]);
+
+        let mut sources = FieldSources::new();
+        add_sources(&mut sources, &a, "a.json");
+        add_sources(&mut sources, &b, "b.json");
+
+        assert_eq!(sources["id"], vec!["a.json", "b.json"]);
+        assert_eq!(sources["items"], vec!["a.json"]);
+        assert_eq!(sources["items[].done"], vec!["a.json"]);
+        assert_eq!(sources["score"], vec!["b.json"]);
+    }
+}
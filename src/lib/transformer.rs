@@ -1,21 +1,46 @@
+use std::io::{self, Write};
 use std::mem;
-use crate::lib::model::transform_config::TransformConfig;
-use crate::lib::model::tree::{JsonArrayType, JsonTree};
+use crate::lib::model::transform_config::{BuilderConfig, CaseType, ConstructorConfig, EmptyObject, ModuleConfig, NestedConfig, TransformConfig};
+use crate::lib::model::tree::{JsonArrayType, JsonRoot, JsonTree, Samples};
 use thiserror::Error;
-use crate::lib::case::{convert_case};
+use crate::lib::case::{convert_name, singularize, transliterate};
+use crate::lib::inference::{InferredType, PrefixInference, TypeInference, is_base64, is_bool_string, is_constant_string, has_currency_symbol, is_decimal_string, is_empty_string};
+use crate::lib::parser::schema::FieldDocs;
+use crate::lib::sources::FieldSources;
 
 #[derive(Error, Debug)]
+#[allow(clippy::enum_variant_names)]
 pub enum TransformerError {
     #[error("Bad type definition in config: {{name}} needed.\n{0}")]
     BadTypeDefinition(String),
+    #[error("Bad unit type definition in config: {{object_name}} needed.\n{0}")]
+    BadUnitDefinition(String),
     #[error("Bad field definition in config: {{field_name}} needed.\n{0}")]
     BadFieldDefinitionName(String),
     #[error("Bad field definition in config: {{field_type}} needed. \n{0}")]
     BadFieldDefinitionType(String),
+    #[error("Bad optional field definition in config: {{field_name}} and {{field_type}} needed. \n{0}")]
+    BadOptionalFieldDefinition(String),
     #[error("Bad field rename definition in config: {{name}} needed. \n{0}")]
     BadFieldRenameDefinition(String),
+    #[error("Bad source comment definition in config: {{sources}} needed. \n{0}")]
+    BadSourceCommentDefinition(String),
+    #[error("Bad doc comment definition in config: {{doc}} needed. \n{0}")]
+    BadDocCommentDefinition(String),
     #[error("Bad array type definition in config: {{field_type}} needed. \n {0}")]
     BadArrayTypeDefinition(String),
+    #[error("Bad fixed array type definition in config: {{field_type}} and {{len}} needed. \n {0}")]
+    BadFixedArrayTypeDefinition(String),
+    #[error("Bad tuple type definition in config: {{field_types}} needed. \n {0}")]
+    BadTupleTypeDefinition(String),
+    #[error("Bad map type definition in config: {{field_type}} needed. \n {0}")]
+    BadMapTypeDefinition(String),
+    #[error("Bad alias definition in config: {{object_name}} needed.\n {0}")]
+    BadAliasDefinitionName(String),
+    #[error("Bad alias definition in config: {{field_type}} needed.\n {0}")]
+    BadAliasDefinitionType(String),
+    #[error("Bad optional definition in config: {{field_type}} needed.\n {0}")]
+    BadOptionalDefinition(String),
     #[error("Bad constructor definition: {{object_name}} needed.\n {0}")]
     BadConstructorDefinitionName(String),
     #[error("Bad constructor definition: {{arguments}} needed.\n {0}")]
@@ -24,20 +49,246 @@ pub enum TransformerError {
     BadArgumentDefinitionName(String),
     #[error("Bad constructor field definition: {{name}} needed.\n {0}")]
     BadConstructorFieldDefinition(String),
+    #[error("Bad enum definition: {{object_name}} needed.\n {0}")]
+    BadEnumDefinitionName(String),
+    #[error("Bad enum variant definition: {{variant}} needed.\n {0}")]
+    BadEnumVariantDefinition(String),
+    #[error("Bad conversion definition: {{object_name}} needed.\n {0}")]
+    BadConversionDefinitionName(String),
+    #[error("Bad conversion field definition: {{field_name}} needed.\n {0}")]
+    BadConversionFieldDefinition(String),
+    #[error("Bad parse helper definition: {{object_name}} needed.\n {0}")]
+    BadParseHelperDefinition(String),
+    #[error("Bad builder field definition: {{field_name}} and {{field_type}} needed.\n {0}")]
+    BadBuilderFieldDefinition(String),
+    #[error("Bad module definition: {{name}} needed.\n {0}")]
+    BadModuleDefinitionName(String),
+    #[error("Bad module reference: {{name}} and {{object_name}} needed.\n {0}")]
+    BadModuleReference(String),
+    #[error("Bad nested type definition: {{object_name}} needed.\n {0}")]
+    BadNestedDefinition(String),
+    #[error("The definition doesn't support nested types, it has no nested_definition.")]
+    NestedTypesUnsupported,
+    #[error("Bad sealed definition: {{object_name}} needed.\n {0}")]
+    BadSealedDefinitionName(String),
+    #[error("Bad sealed variant definition: {{variant}} needed.\n {0}")]
+    BadSealedVariantDefinition(String),
 }
 
+/// Replaced by the name of the generated type.
+pub const OBJECT_NAME: &str = "{object_name}";
+/// Replaced by the configured visibility of the generated type.
+pub const VISIBILITY: &str = "{visibility}";
+/// Replaced by the configured readonly modifier of a field.
+pub const MODIFIER: &str = "{modifier}";
+/// Replaced by the name of a field, converted to the configured case.
+pub const FIELD_NAME: &str = "{field_name}";
+/// Replaced by the type of a field.
+pub const FIELD_TYPE: &str = "{field_type}";
+/// Replaced by the number of elements of a fixed-size array.
+pub const LEN: &str = "{len}";
+/// Replaced by the types of the elements of a tuple, separated by commas.
+pub const FIELD_TYPES: &str = "{field_types}";
+/// Replaced by the type of the keys of a map.
+pub const KEY_TYPE: &str = "{key_type}";
+/// Replaced by the original name of a field in annotations, or the field name in constructors.
+pub const NAME: &str = "{name}";
+/// Replaced by the files a field appeared in.
+pub const SOURCES: &str = "{sources}";
+/// Replaced by a line of the description of a field.
+pub const DOC: &str = "{doc}";
+/// Replaced by the list of constructor arguments.
+pub const ARGUMENTS: &str = "{arguments}";
+/// Replaced by the type of a constructor argument.
+pub const TYPE: &str = "{type}";
+/// Replaced by the name of an enum variant, converted to the case of types.
+pub const VARIANT: &str = "{variant}";
+
+/// Number of columns a tab counts for when measuring lines, see [TransformOptions::max_line_width](crate::lib::model::transform_config::TransformOptions::max_line_width).
+pub const TAB_WIDTH: usize = 4;
+
+/// Derives floats can't implement, left out of the derive attributes of the types holding one.
+const HASH_DERIVES: [&str; 3] = ["Eq", "Ord", "Hash"];
+
+/// Width of the widest line of a text, tabs counting as [TAB_WIDTH] columns.
+fn line_width(text: &str) -> usize {
+    text.lines()
+        .map(|line| line.chars().map(|c| if c == '\t' { TAB_WIDTH } else { 1 }).sum())
+        .max()
+        .unwrap_or(0)
+}
+
+/// Places every constructor argument on its own line, indented one level deeper than the constructor,
+/// the end of the argument list going back to the constructor's indentation.
+/// # Arguments
+/// * `constructor` config of the constructor.
+/// * `arguments` rendered arguments.
+fn wrap_arguments(constructor: &ConstructorConfig, arguments: &[String]) -> String {
+    let indent: String = constructor.definition.chars().take_while(|c| *c == '\t').collect();
+    let separator = constructor.separator.trim_end();
+
+    let mut arguments_str = format!("\n{}\t", indent);
+    arguments_str.push_str(&arguments.join(&format!("{}\n{}\t", separator, indent)));
+    if constructor.separator_at_end {
+        arguments_str.push_str(separator);
+    }
+    arguments_str.push('\n');
+    arguments_str.push_str(&indent);
+    arguments_str
+}
+
+/// Checks whether lines of a type reference another type, holding its name as a whole word.
+fn references(lines: &[String], name: &str) -> bool {
+    let is_word = |char: char| char.is_alphanumeric() || char == '_';
+
+    lines.iter().any(|line| line.match_indices(name).any(|(start, _)| {
+        let before = line[..start].chars().next_back();
+        let after = line[start + name.len()..].chars().next();
+        !before.is_some_and(is_word) && !after.is_some_and(is_word)
+    }))
+}
+
+/// Replaces the placeholders of a template in a single pass, values being inserted as they are:
+/// a key holding placeholder-looking text, such as `{name}`, is not replaced again.
+/// # Arguments
+/// * `template` text holding the placeholders.
+/// * `values` placeholders along with their value.
+fn render(template: &str, values: &[(&str, &str)]) -> String {
+    let mut output = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find('{') {
+        output.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        match values.iter().find(|(placeholder, _)| rest.starts_with(placeholder)) {
+            Some((placeholder, value)) => {
+                output.push_str(value);
+                rest = &rest[placeholder.len()..];
+            }
+            None => {
+                output.push('{');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    output.push_str(rest);
+    output
+}
+
+/// Placeholder recognized in a field of [TransformConfig].
+pub struct Placeholder {
+    /// Path of the config field, as written in a definition file.
+    pub field: &'static str,
+    /// Placeholder text.
+    pub placeholder: &'static str,
+    /// Whether a definition is rejected when the field lacks the placeholder.
+    pub required: bool,
+}
+
+/// Every placeholder replaced by the [Transformer], by config field.
+pub const PLACEHOLDERS: [Placeholder; 53] = [
+    Placeholder { field: "type_definition", placeholder: OBJECT_NAME, required: true },
+    Placeholder { field: "type_definition", placeholder: VISIBILITY, required: false },
+    Placeholder { field: "unit_definition", placeholder: OBJECT_NAME, required: true },
+    Placeholder { field: "unit_definition", placeholder: VISIBILITY, required: false },
+    Placeholder { field: "field_definition", placeholder: FIELD_NAME, required: true },
+    Placeholder { field: "field_definition", placeholder: FIELD_TYPE, required: true },
+    Placeholder { field: "field_definition", placeholder: MODIFIER, required: false },
+    Placeholder { field: "optional_field_definition", placeholder: FIELD_NAME, required: true },
+    Placeholder { field: "optional_field_definition", placeholder: FIELD_TYPE, required: true },
+    Placeholder { field: "optional_field_definition", placeholder: MODIFIER, required: false },
+    Placeholder { field: "readonly_optional_field_definition", placeholder: FIELD_NAME, required: true },
+    Placeholder { field: "readonly_optional_field_definition", placeholder: FIELD_TYPE, required: true },
+    Placeholder { field: "readonly_optional_field_definition", placeholder: MODIFIER, required: false },
+    Placeholder { field: "name_change_annotation", placeholder: NAME, required: true },
+    Placeholder { field: "source_comment", placeholder: SOURCES, required: true },
+    Placeholder { field: "doc_comment", placeholder: DOC, required: true },
+    Placeholder { field: "array_definition", placeholder: FIELD_TYPE, required: true },
+    Placeholder { field: "fixed_array_definition", placeholder: FIELD_TYPE, required: true },
+    Placeholder { field: "fixed_array_definition", placeholder: LEN, required: true },
+    Placeholder { field: "map_definition", placeholder: FIELD_TYPE, required: true },
+    Placeholder { field: "map_definition", placeholder: KEY_TYPE, required: false },
+    Placeholder { field: "tuple_definition", placeholder: FIELD_TYPES, required: true },
+    Placeholder { field: "alias_definition", placeholder: OBJECT_NAME, required: true },
+    Placeholder { field: "alias_definition", placeholder: FIELD_TYPE, required: true },
+    Placeholder { field: "alias_definition", placeholder: VISIBILITY, required: false },
+    Placeholder { field: "optional_definition", placeholder: FIELD_TYPE, required: true },
+    Placeholder { field: "constructor.definition", placeholder: OBJECT_NAME, required: true },
+    Placeholder { field: "constructor.definition", placeholder: ARGUMENTS, required: true },
+    Placeholder { field: "constructor.argument_definition", placeholder: NAME, required: true },
+    Placeholder { field: "constructor.argument_definition", placeholder: TYPE, required: false },
+    Placeholder { field: "constructor.field_definition.field_definition", placeholder: NAME, required: true },
+    Placeholder { field: "enum_definition.definition", placeholder: OBJECT_NAME, required: true },
+    Placeholder { field: "enum_definition.definition", placeholder: VISIBILITY, required: false },
+    Placeholder { field: "enum_definition.variant_definition", placeholder: VARIANT, required: true },
+    Placeholder { field: "enum_definition.variant_definition", placeholder: NAME, required: false },
+    Placeholder { field: "sealed_definition.definition", placeholder: OBJECT_NAME, required: true },
+    Placeholder { field: "sealed_definition.definition", placeholder: VISIBILITY, required: false },
+    Placeholder { field: "sealed_definition.definition", placeholder: NAME, required: false },
+    Placeholder { field: "sealed_definition.variant_definition", placeholder: VARIANT, required: true },
+    Placeholder { field: "sealed_definition.variant_definition", placeholder: NAME, required: false },
+    Placeholder { field: "sealed_definition.variant_end", placeholder: OBJECT_NAME, required: false },
+    Placeholder { field: "builder.field_definition", placeholder: FIELD_NAME, required: true },
+    Placeholder { field: "builder.field_definition", placeholder: FIELD_TYPE, required: true },
+    Placeholder { field: "module_definition.definition", placeholder: NAME, required: true },
+    Placeholder { field: "module_definition.reference", placeholder: NAME, required: true },
+    Placeholder { field: "module_definition.reference", placeholder: OBJECT_NAME, required: true },
+    Placeholder { field: "nested_definition.type_definition", placeholder: OBJECT_NAME, required: true },
+    Placeholder { field: "nested_definition.type_definition", placeholder: VISIBILITY, required: false },
+    Placeholder { field: "conversion.definition", placeholder: OBJECT_NAME, required: true },
+    Placeholder { field: "conversion.field_definition", placeholder: FIELD_NAME, required: true },
+    Placeholder { field: "conversion.field_definition", placeholder: NAME, required: false },
+    Placeholder { field: "conversion.field_definition", placeholder: FIELD_TYPE, required: false },
+    Placeholder { field: "parse_helper", placeholder: OBJECT_NAME, required: true },
+];
 
 /// Holds the data needed to turn a [JsonTree] into a representation provided by [TransformConfig].
-pub struct Transformer {
+pub struct Transformer<'w> {
     /// Name of the root object.
     name: Option<String>,
     /// Wanted representation of the [JsonTree]
     config: TransformConfig,
     /// Source tree
-    tree: Vec<JsonTree>,
+    tree: JsonRoot,
     /// Output of the transformer.
-    /// Each vec represents an object, each String inside that vec represents a line.
-    output: Vec<Vec<String>>,
+    /// Each vec represents an object along with its name, each String inside that vec represents a line.
+    output: Vec<(String, Vec<String>)>,
+    /// Names given to the generated types, in the order they were given.
+    type_names: Vec<String>,
+    /// Nested objects generated so far: the name they are named after, their type name and their lines,
+    /// see [Transformer::nested_object_type].
+    type_shapes: Vec<(String, String, Vec<String>)>,
+    /// Path of the object being transformed, see [Transformer::field_path].
+    path: String,
+    /// Classification of string values, see [TypeInference].
+    inference: Box<dyn TypeInference>,
+    /// A field was given the `bool_string_annotation`, its `bool_string_helper` is emitted after the types.
+    bool_strings_used: bool,
+    /// A field was given the `decimal_string_annotation`, its `decimal_string_helper` is emitted after the types.
+    decimal_strings_used: bool,
+    /// Types holding a float, directly or through another type, that can't derive [HASH_DERIVES].
+    unhashable_types: Vec<String>,
+    /// Number of types given the unknown type because of [TransformOptions::max_types](crate::lib::model::transform_config::TransformOptions::max_types).
+    skipped_types: usize,
+    /// Number of modules the type being transformed is declared in, see [Transformer::transform_nested_object].
+    module_depth: usize,
+    /// Number of types whose fields are being built, the types transformed meanwhile being declared inside them
+    /// with the `nested_types` option.
+    nesting_depth: usize,
+    /// Files each field appeared in, see [Transformer::with_sources].
+    sources: FieldSources,
+    /// Descriptions of the fields, see [Transformer::with_docs].
+    docs: FieldDocs,
+    /// Destination of the types when streaming, see [Transformer::start_transform_to].
+    writer: Option<&'w mut dyn Write>,
+    /// First error met while streaming, no more types are written after it.
+    write_error: Option<io::Error>,
+    /// A type was written when streaming, the next ones being preceded by a blank line.
+    wrote_type: bool,
+    /// Problems met while transforming that didn't stop it, see [Transformer::warnings].
+    warnings: Vec<String>,
 }
 
 /// Holds the type and name (maybe converted) of a field from [JsonTree] ready for writing into the output.
@@ -48,9 +299,11 @@ struct FieldInfo<'a> {
     type_str: String,
     ///Name string, could be converted.
     name: String,
+    ///Lines placed above the field definition.
+    annotations: Vec<String>,
 }
 
-impl Transformer {
+impl<'w> Transformer<'w> {
 
     /// Creates a new [Transformer].
     /// # Arguments
@@ -59,229 +312,3389 @@ impl Transformer {
     /// * `name` name of the root object
     /// # Errors
     /// If [TransformConfig] contains invalid data, a [TransformerError] will be returned.
-    pub fn new<'a>(config: TransformConfig, tree: Vec<JsonTree>, name: Option<String>) -> Result<Self, TransformerError> {
+    pub fn new(config: TransformConfig, tree: JsonRoot, name: Option<String>) -> Result<Self, TransformerError> {
+        let inference = PrefixInference::new(config.options.string_prefixes.clone());
+        Self::with_inference(config, tree, name, Box::new(inference))
+    }
+
+    /// Creates a new [Transformer] classifying string values with `inference`, see [Transformer::new].
+    pub fn with_inference(mut config: TransformConfig, tree: JsonRoot, name: Option<String>, inference: Box<dyn TypeInference>) -> Result<Self, TransformerError> {
         let field_str = config.field_definition.to_string();
         let field_rename_str = config.name_change_annotation.to_string();
-        let array_type_str = config.array_definition.to_string();
         let type_str = config.type_definition.to_string();
 
-        if !type_str.contains("{object_name}") {
+        if !type_str.contains(OBJECT_NAME) {
             return Err(TransformerError::BadTypeDefinition(type_str));
         }
 
-        if !field_str.contains("{field_name}") {
+        if let Some(ref unit) = config.unit_definition {
+            if !unit.contains(OBJECT_NAME) {
+                return Err(TransformerError::BadUnitDefinition(unit.to_string()));
+            }
+        }
+
+        if !field_str.contains(FIELD_NAME) {
             return Err(TransformerError::BadFieldDefinitionName(field_str));
         }
 
-        if !field_rename_str.contains("{name}") {
-            return Err(TransformerError::BadFieldRenameDefinition(type_str));
+        if !field_rename_str.contains(NAME) {
+            return Err(TransformerError::BadFieldRenameDefinition(field_rename_str));
+        }
+
+        if let Some(ref comment) = config.source_comment {
+            if !comment.contains(SOURCES) {
+                return Err(TransformerError::BadSourceCommentDefinition(comment.to_string()));
+            }
+        }
+
+        if let Some(ref comment) = config.doc_comment {
+            if !comment.contains(DOC) {
+                return Err(TransformerError::BadDocCommentDefinition(comment.to_string()));
+            }
         }
 
-        if !field_str.contains("{field_type}") {
+        if !field_str.contains(FIELD_TYPE) {
             return Err(TransformerError::BadFieldDefinitionType(field_str));
         }
 
-        if !array_type_str.contains("{field_type}") {
-            return Err(TransformerError::BadArrayTypeDefinition(array_type_str));
+        if !config.untyped_arrays && !config.array_definition.contains(FIELD_TYPE) {
+            return Err(TransformerError::BadArrayTypeDefinition(config.array_definition.to_string()));
+        }
+
+        if let Some(ref fixed_array) = config.fixed_array_definition {
+            if !fixed_array.contains(FIELD_TYPE) || !fixed_array.contains(LEN) {
+                return Err(TransformerError::BadFixedArrayTypeDefinition(fixed_array.to_string()));
+            }
+        }
+
+        if let Some(ref map) = config.map_definition {
+            if !map.contains(FIELD_TYPE) {
+                return Err(TransformerError::BadMapTypeDefinition(map.to_string()));
+            }
+        }
+
+        if let Some(ref tuple) = config.tuple_definition {
+            if !tuple.contains(FIELD_TYPES) {
+                return Err(TransformerError::BadTupleTypeDefinition(tuple.to_string()));
+            }
+        }
+
+        if let Some(ref alias) = config.alias_definition {
+            if !alias.contains(OBJECT_NAME) {
+                return Err(TransformerError::BadAliasDefinitionName(alias.to_string()));
+            }
+
+            if !alias.contains(FIELD_TYPE) {
+                return Err(TransformerError::BadAliasDefinitionType(alias.to_string()));
+            }
+        }
+
+        for optional_field in config.optional_field_definition.iter().chain(config.readonly_optional_field_definition.iter()) {
+            if !optional_field.contains(FIELD_NAME) || !optional_field.contains(FIELD_TYPE) {
+                return Err(TransformerError::BadOptionalFieldDefinition(optional_field.to_string()));
+            }
+        }
+
+        if let Some(ref optional) = config.optional_definition {
+            if !optional.contains(FIELD_TYPE) {
+                return Err(TransformerError::BadOptionalDefinition(optional.to_string()));
+            }
         }
 
         if let Some(ref constructor) = config.constructor {
             let constructor_str = constructor.definition.to_string();
             let argument_str = constructor.argument_definition.to_string();
 
-            if !constructor_str.contains("{object_name}") {
+            if !constructor_str.contains(OBJECT_NAME) {
                 return Err(TransformerError::BadConstructorDefinitionName(constructor_str));
             }
 
-            if !constructor_str.contains("{arguments}") {
+            if !constructor_str.contains(ARGUMENTS) {
                 return Err(TransformerError::BadConstructorDefinitionArgument(constructor_str));
             }
 
-            if !argument_str.contains("{name}") {
+            if !argument_str.contains(NAME) {
                 return Err(TransformerError::BadArgumentDefinitionName(argument_str));
             }
 
             if let Some(ref field) = constructor.field_definition {
-                if !field.field_definition.contains("{name}") {
+                if !field.field_definition.contains(NAME) {
                     return Err(TransformerError::BadConstructorFieldDefinition(field.field_definition.to_string()));
                 }
             }
         }
 
+        if let Some(ref enum_config) = config.enum_definition {
+            if !enum_config.definition.contains(OBJECT_NAME) {
+                return Err(TransformerError::BadEnumDefinitionName(enum_config.definition.to_string()));
+            }
+
+            if !enum_config.variant_definition.contains(VARIANT) {
+                return Err(TransformerError::BadEnumVariantDefinition(enum_config.variant_definition.to_string()));
+            }
+        }
+
+        if let Some(ref sealed) = config.sealed_definition {
+            if !sealed.definition.contains(OBJECT_NAME) {
+                return Err(TransformerError::BadSealedDefinitionName(sealed.definition.to_string()));
+            }
+
+            if let Some(ref variant) = sealed.variant_definition {
+                if !variant.contains(VARIANT) {
+                    return Err(TransformerError::BadSealedVariantDefinition(variant.to_string()));
+                }
+            }
+        }
+
+        if let Some(ref builder) = config.builder {
+            if !builder.field_definition.contains(FIELD_NAME) || !builder.field_definition.contains(FIELD_TYPE) {
+                return Err(TransformerError::BadBuilderFieldDefinition(builder.field_definition.to_string()));
+            }
+        }
+
+        if let Some(ref module) = config.module_definition {
+            if !module.definition.contains(NAME) {
+                return Err(TransformerError::BadModuleDefinitionName(module.definition.to_string()));
+            }
+
+            if !module.reference.contains(NAME) || !module.reference.contains(OBJECT_NAME) {
+                return Err(TransformerError::BadModuleReference(module.reference.to_string()));
+            }
+        }
+
+        match config.nested_definition {
+            Some(ref nested) => {
+                if let Some(ref definition) = nested.type_definition {
+                    if !definition.contains(OBJECT_NAME) {
+                        return Err(TransformerError::BadNestedDefinition(definition.to_string()));
+                    }
+                }
+            }
+            None if config.options.nested_types => return Err(TransformerError::NestedTypesUnsupported),
+            None => {}
+        }
+
+        if let Some(ref conversion) = config.conversion {
+            if !conversion.definition.contains(OBJECT_NAME) {
+                return Err(TransformerError::BadConversionDefinitionName(conversion.definition.to_string()));
+            }
+
+            if !conversion.field_definition.contains(FIELD_NAME) {
+                return Err(TransformerError::BadConversionFieldDefinition(conversion.field_definition.to_string()));
+            }
+        }
+
+        if let Some(ref parse_helper) = config.parse_helper {
+            if !parse_helper.contains(OBJECT_NAME) {
+                return Err(TransformerError::BadParseHelperDefinition(parse_helper.to_string()));
+            }
+        }
+
+        if config.options.allow_dead_code {
+            if let Some(ref attribute) = config.dead_code_attribute {
+                config.type_attributes.insert(0, attribute.to_string());
+            }
+        }
+
         Ok(Self {
             name,
             config,
             tree,
             output: vec![],
+            type_names: vec![],
+            type_shapes: vec![],
+            unhashable_types: vec![],
+            bool_strings_used: false,
+            decimal_strings_used: false,
+            path: String::new(),
+            inference,
+            skipped_types: 0,
+            module_depth: 0,
+            nesting_depth: 0,
+            sources: FieldSources::new(),
+            docs: FieldDocs::new(),
+            writer: None,
+            write_error: None,
+            wrote_type: false,
+            warnings: vec![],
         })
     }
 
-    /// Transforms an object of the tree.
+    /// Gives the files each field appeared in, noted above the fields when the `field_sources` option is enabled.
+    pub fn with_sources(mut self, sources: FieldSources) -> Self {
+        self.sources = sources;
+        self
+    }
+
+    /// Gives the descriptions of the fields found in a JSON Schema, placed above the fields with the config's `doc_comment`.
+    pub fn with_docs(mut self, docs: FieldDocs) -> Self {
+        self.docs = docs;
+        self
+    }
+
+    /// Transforms a root array, the objects it holds are named after the `item_name` option.
+    /// If the config has an `alias_definition`, an alias named `name` is emitted for the array type.
     /// # Arguments
-    /// * `tree` object source
-    /// * `name` of the object
-    fn transform_object(&mut self, tree: &Vec<JsonTree>, name: String) {
-        let mut object: Vec<String> = Vec::new();
+    /// * `array_type` type of the elements of the array.
+    /// * `name` name of the root.
+    fn transform_root_array(&mut self, array_type: &JsonArrayType, name: String) {
+        let item_name = self.config.options.item_name.clone().unwrap_or_else(|| format!("{}Item", name));
+        let array_str = self.array_field_type(&item_name, array_type);
 
-        object.push(self.config.type_definition.replace("{object_name}", &name));
+        if let Some(ref alias) = self.config.alias_definition {
+            let with_name = alias.replace(OBJECT_NAME, &name).replace(VISIBILITY, &self.visibility());
+            self.push_type(name, vec![with_name.replace(FIELD_TYPE, &array_str)]);
+        }
+    }
 
-        let fields: Vec<FieldInfo> = tree.iter().map(|tree| match tree {
-            JsonTree::Int(name) => FieldInfo {
-                type_str: self.config.int_type.to_string(),
-                original_str: name,
-                name: convert_case(name, &self.config.case_type)
-            },
-            JsonTree::Float(name) => FieldInfo {
-                type_str: self.config.float_type.to_string(),
-                original_str: name,
-                name: convert_case(name, &self.config.case_type)
-            },
-            JsonTree::String(name) => FieldInfo {
-                type_str: self.config.string_type.to_string(),
-                original_str: name,
-                name: convert_case(name, &self.config.case_type)
-            },
-            JsonTree::Bool(name) => FieldInfo {
-                type_str: self.config.bool_type.to_string(),
-                original_str: name,
-                name: convert_case(name, &self.config.case_type)
-            },
-            JsonTree::JsonObject(name, tree) => {
-                let case_str = convert_case(name, &self.config.case_type);
-                let type_str = convert_case(name, &self.config.object_case_type);
-                self.transform_object(tree, type_str.clone());
-                FieldInfo {
-                    type_str,
-                    original_str: name,
-                    name: case_str
-                }
-            },
-            JsonTree::JsonArray(name, array_type) => {
-                let case_str = convert_case(name, &self.config.case_type);
-                let mut array_str = self.config.array_definition.replace("{field_type}", &case_str);
+    /// Transforms a root holding a single value. If the config has an `alias_definition`,
+    /// an alias named `name` is emitted for the type of the value.
+    /// # Arguments
+    /// * `tree` value of the root.
+    /// * `name` name of the root.
+    fn transform_root_value(&mut self, tree: JsonTree, name: String) {
+        let value_str = match tree.into_array_type() {
+            Some(value_type) => self.element_type(&name, &value_type),
+            None => self.unknown_type(),
+        };
 
-                if let JsonArrayType::JsonObject(tree) = array_type {
-                    let type_str = convert_case(name, &self.config.object_case_type);
-                    self.transform_object(tree, type_str.clone());
-                    array_str = self.config.array_definition.replace("{field_type}", &type_str);
-                }
+        if let Some(ref alias) = self.config.alias_definition {
+            let with_name = alias.replace(OBJECT_NAME, &name).replace(VISIBILITY, &self.visibility());
+            self.push_type(name, vec![with_name.replace(FIELD_TYPE, &value_str)]);
+        }
+    }
 
-                FieldInfo {
-                    type_str: array_str,
-                    original_str: name,
-                    name: case_str
+    /// Chooses the type of a string field, which can be refined by the values seen for it.
+    /// # Arguments
+    /// * `samples` values of the field. The string type is kept when some were left out.
+    /// # Returns
+    /// Type of the field and, if needed, an annotation for it.
+    fn string_field_type(&self, samples: &Samples) -> (String, Option<String>) {
+        let options = &self.config.options;
+        if let Some(InferredType::Named(type_str)) = self.inferred_type(samples) {
+            return (type_str, None);
+        }
+
+        // Values were left out, the checks below would only judge those kept.
+        if !samples.complete() {
+            return (self.config.string_type.to_string(), None);
+        }
+        let samples = samples.values.as_slice();
+
+        if options.bool_strings && is_bool_string(samples) {
+            if let Some(ref annotation) = self.config.bool_string_annotation {
+                return (self.config.bool_type.to_string(), Some(annotation.to_string()));
+            }
+        }
+
+        if options.base64_bytes && is_base64(samples) {
+            if let Some(ref bytes_type) = self.config.bytes_type {
+                let annotation = self.config.bytes_annotation.as_ref().map(|annotation| annotation.to_string());
+                return (bytes_type.to_string(), annotation);
+            }
+        }
+
+        if options.decimal_strings && is_decimal_string(samples) {
+            if let Some(ref decimal_type) = self.config.decimal_type {
+                // The decimal type can't read the currency symbol, the annotation strips it.
+                if !has_currency_symbol(samples) {
+                    return (decimal_type.to_string(), None);
+                }
+                if let Some(ref annotation) = self.config.decimal_string_annotation {
+                    return (decimal_type.to_string(), Some(annotation.to_string()));
                 }
             }
-        }).collect();
+        }
+
+        (self.config.string_type.to_string(), None)
+    }
 
+    /// Type given to all the values of a string field by the [TypeInference], if they agree.
+    /// Samples missing some values have no type, a value left out could have been given another one.
+    fn inferred_type(&self, samples: &Samples) -> Option<InferredType> {
+        if !samples.complete() {
+            return None;
+        }
+
+        let mut types = samples.values.iter().map(|sample| self.inference.classify_string(sample));
+        let first = types.next()?;
+
+        types.all(|inferred_type| inferred_type == first).then_some(first)
+    }
+
+    /// Emits a single-variant enum for a string field always holding the same value,
+    /// when the `constant_enums` option is enabled and the config has an `enum_definition`.
+    /// # Arguments
+    /// * `name` name of the field, the enum is named after it.
+    /// * `samples` values seen for the field.
+    /// # Returns
+    /// Name of the enum, if one was emitted.
+    fn enum_field_type(&mut self, name: &str, samples: &Samples) -> Option<String> {
+        if !self.config.options.constant_enums || self.config.enum_definition.is_none() || !is_constant_string(samples) {
+            return None;
+        }
 
-        for field_info in fields.iter() {
+        if self.types_exhausted() {
+            return None;
+        }
+
+        let type_str = self.type_name(name);
+        self.transform_enum(&samples.values, &type_str);
+        Some(type_str)
+    }
+
+    /// Builds the type of an array field, transforming the objects it holds.
+    /// # Arguments
+    /// * `name` name of the objects inside the array.
+    /// * `array_type` type of the elements of the array.
+    fn array_field_type(&mut self, name: &str, array_type: &JsonArrayType) -> String {
+        let path_len = self.path.len();
+        self.path.push_str("[]");
 
-            if field_info.name != field_info.original_str {
-                let with_name = self.config.name_change_annotation.replace("{name}", field_info.original_str);
-                object.push(with_name);
+        let (len, array_type) = match array_type {
+            JsonArrayType::Fixed(len, array_type) => (Some(*len), array_type.as_ref()),
+            JsonArrayType::Tuple(positions) => {
+                let tuple_type = self.tuple_type(name, positions);
+                self.path.truncate(path_len);
+                return tuple_type;
             }
+            array_type => (None, array_type),
+        };
+        let element_type = self.element_type(name, array_type);
 
-            let with_name = self.config.field_definition.replace("{field_name}", &field_info.name);
-            object.push(with_name.replace("{field_type}", &field_info.type_str));
+        self.path.truncate(path_len);
+        match (len, &self.config.fixed_array_definition) {
+            (Some(len), Some(fixed_array)) => fixed_array.replace(FIELD_TYPE, &element_type).replace(LEN, &len.to_string()),
+            _ => self.config.array_definition.replace(FIELD_TYPE, &element_type),
         }
+    }
 
-        if let Some(ref constructor) = self.config.constructor {
-            let mut arguments_str = String::new();
-            for (i, field_info) in fields.iter().enumerate() {
-                let with_type = constructor.argument_definition.replace("{type}", &field_info.type_str);
-                let with_name = with_type.replace("{name}", &field_info.name);
-                if i < fields.len() - 1 || (i == fields.len() - 1 && constructor.separator_at_end) {
-                    arguments_str.push_str(&*(with_name + &constructor.separator));
-                } else {
-                    arguments_str.push_str(&with_name);
+    /// Builds the type of an array holding elements of different types, with the config's `tuple_definition`.
+    /// Tuples become arrays of the unknown type when the config has none.
+    /// # Arguments
+    /// * `name` name of the objects inside the tuple.
+    /// * `positions` type of each element of the tuple.
+    fn tuple_type(&mut self, name: &str, positions: &[JsonArrayType]) -> String {
+        let tuple = match self.config.tuple_definition {
+            Some(ref tuple) => tuple.to_string(),
+            None => {
+                let unknown_type = self.unknown_type();
+                return self.config.array_definition.replace(FIELD_TYPE, &unknown_type);
+            }
+        };
+
+        let types: Vec<String> = positions.iter().map(|position| self.element_type(name, position)).collect();
+        tuple.replace(FIELD_TYPES, &types.join(", "))
+    }
+
+    /// Builds the type of a map field, transforming the objects it holds.
+    /// # Arguments
+    /// * `name` name of the objects inside the map.
+    /// * `value_type` type of the values of the map.
+    fn map_field_type(&mut self, name: &str, value_type: &JsonArrayType) -> String {
+        let path_len = self.path.len();
+        self.path.push_str("{}");
+        let element_type = self.element_type(name, value_type);
+
+        self.path.truncate(path_len);
+        match self.config.map_definition {
+            Some(ref map) => map.replace(KEY_TYPE, &self.config.int_type).replace(FIELD_TYPE, &element_type),
+            None => self.unknown_type(),
+        }
+    }
+
+    /// Builds the type of the elements of an array, see [Transformer::array_field_type].
+    fn element_type(&mut self, name: &str, array_type: &JsonArrayType) -> String {
+        match array_type {
+            JsonArrayType::Int => self.config.int_type.to_string(),
+            JsonArrayType::Float => self.config.float_type.to_string(),
+            // Elements can't be annotated, those needing an annotation to be read keep the string type.
+            JsonArrayType::String(samples) => match self.string_field_type(samples) {
+                (type_str, None) => type_str,
+                (_, Some(_)) => self.config.string_type.to_string(),
+            },
+            JsonArrayType::Bool => self.config.bool_type.to_string(),
+            JsonArrayType::JsonObject(tree) if self.is_object_map(tree) => self.object_map_type(),
+            JsonArrayType::JsonObject(_) if self.types_exhausted() => self.unknown_type(),
+            JsonArrayType::JsonObject(tree) => self.nested_object_type(tree, name),
+            JsonArrayType::JsonArray(array_type) => self.array_field_type(name, array_type),
+            JsonArrayType::Tagged(key, variants) => self.transform_sealed(key, variants, name),
+            JsonArrayType::Optional(array_type) => {
+                let element_type = self.element_type(name, array_type);
+                match self.config.optional_definition {
+                    Some(ref optional) => optional.replace(FIELD_TYPE, &element_type),
+                    None => element_type,
                 }
             }
+            JsonArrayType::Fixed(_, array_type) => self.element_type(name, array_type),
+            JsonArrayType::Tuple(positions) => self.tuple_type(name, positions),
+            JsonArrayType::Unknown => self.unknown_type(),
+            JsonArrayType::Empty => self.config.empty_array_type.as_ref().map_or_else(|| self.unknown_type(), |type_str| type_str.to_string()),
+        }
+    }
 
-            let with_name = constructor.definition.replace("{object_name}", &name);
-            object.push(with_name.replace("{arguments}", &arguments_str));
+    /// Gives a name to a generated type. If the name was already given to another type,
+    /// a number is appended to it, starting from 2. Names only depend on the order of the tree.
+    /// # Arguments
+    /// * `name` field name the type is named after.
+    fn type_name(&mut self, name: &str) -> String {
+        let type_str = self.convert_case(name, &self.config.object_case_type);
+        let mut unique_str = type_str.clone();
+        let mut suffix = 2;
 
-            if let Some(ref field) = constructor.field_definition {
-                for field_info in fields {
-                    object.push(field.field_definition.replace("{name}", &field_info.name));
+        while self.type_names.contains(&unique_str) {
+            unique_str = format!("{}{}", type_str, suffix);
+            suffix += 1;
+        }
+
+        self.type_names.push(unique_str.clone());
+        unique_str
+    }
+
+    /// Inlines the fields of nested objects into their parent, prefixed by the name of the object.
+    /// Arrays are kept, but the objects they hold are flattened too.
+    /// # Arguments
+    /// * `tree` fields of an object.
+    /// # Returns
+    /// Fields of the flattened object.
+    fn flatten(tree: Vec<JsonTree>) -> Vec<JsonTree> {
+        let mut fields = Vec::new();
+
+        for field in tree {
+            match field {
+                JsonTree::JsonObject(name, tree) => {
+                    for mut child in Self::flatten(tree) {
+                        let child_name = format!("{}_{}", name, child.name());
+                        *child.name_mut() = child_name;
+                        fields.push(child);
+                    }
                 }
-                object.push(field.end.to_string());
+                JsonTree::JsonArray(name, array_type) => fields.push(JsonTree::JsonArray(name, Self::flatten_array(array_type))),
+                JsonTree::Map(name, value_type) => fields.push(JsonTree::Map(name, Self::flatten_array(value_type))),
+                JsonTree::Optional(tree) => {
+                    for mut child in Self::flatten(vec![*tree]) {
+                        child.set_optional();
+                        fields.push(child);
+                    }
+                }
+                field => fields.push(field),
             }
         }
 
-        object.push(self.config.block_end.to_string());
+        fields
+    }
 
-        self.output.push(object);
+    /// Flattens the objects held by an array. See [Transformer::flatten].
+    fn flatten_array(array_type: JsonArrayType) -> JsonArrayType {
+        match array_type {
+            JsonArrayType::JsonObject(tree) => JsonArrayType::JsonObject(Self::flatten(tree)),
+            JsonArrayType::JsonArray(array_type) => JsonArrayType::JsonArray(Box::new(Self::flatten_array(*array_type))),
+            JsonArrayType::Optional(array_type) => JsonArrayType::Optional(Box::new(Self::flatten_array(*array_type))),
+            JsonArrayType::Fixed(len, array_type) => JsonArrayType::Fixed(len, Box::new(Self::flatten_array(*array_type))),
+            JsonArrayType::Tuple(positions) => JsonArrayType::Tuple(positions.into_iter().map(Self::flatten_array).collect()),
+            JsonArrayType::Tagged(key, variants) => JsonArrayType::Tagged(
+                key,
+                variants.into_iter().map(|(value, tree)| (value, Self::flatten(tree))).collect(),
+            ),
+            array_type => array_type,
+        }
     }
 
-    /// consumes the struct and start the transformation process.
+    /// Makes the string fields only seen empty optional, in nested objects and arrays too.
+    /// # Arguments
+    /// * `tree` fields of an object.
     /// # Returns
-    /// Struct's field `output`. Each vector represents an object, each object is made of a vector of lines.
-    pub fn start_transform(mut self) -> Vec<Vec<String>> {
-        let tree = mem::replace(&mut self.tree, Vec::new());
-        let name = self.name.clone().unwrap_or_else(|| String::from("Root"));
-        self.transform_object(&tree, name);
-        self.output
+    /// Fields of the object, those only seen empty being optional.
+    fn empty_strings_as_null(tree: Vec<JsonTree>) -> Vec<JsonTree> {
+        tree.into_iter().map(|field| match field {
+            JsonTree::String(name, samples) if is_empty_string(&samples) => {
+                JsonTree::Optional(Box::new(JsonTree::String(name, samples)))
+            }
+            JsonTree::JsonObject(name, tree) => JsonTree::JsonObject(name, Self::empty_strings_as_null(tree)),
+            JsonTree::JsonArray(name, array_type) => JsonTree::JsonArray(name, Self::empty_strings_in_array(array_type)),
+            JsonTree::Map(name, value_type) => JsonTree::Map(name, Self::empty_strings_in_array(value_type)),
+            JsonTree::Optional(tree) => {
+                let mut field = Self::empty_strings_as_null(vec![*tree]).remove(0);
+                field.set_optional();
+                field
+            }
+            field => field,
+        }).collect()
     }
-}
 
+    /// Makes the string fields only seen empty optional in the objects held by an array.
+    /// See [Transformer::empty_strings_as_null].
+    fn empty_strings_in_array(array_type: JsonArrayType) -> JsonArrayType {
+        match array_type {
+            JsonArrayType::JsonObject(tree) => JsonArrayType::JsonObject(Self::empty_strings_as_null(tree)),
+            JsonArrayType::JsonArray(array_type) => JsonArrayType::JsonArray(Box::new(Self::empty_strings_in_array(*array_type))),
+            JsonArrayType::Optional(array_type) => JsonArrayType::Optional(Box::new(Self::empty_strings_in_array(*array_type))),
+            JsonArrayType::Fixed(len, array_type) => JsonArrayType::Fixed(len, Box::new(Self::empty_strings_in_array(*array_type))),
+            JsonArrayType::Tuple(positions) => JsonArrayType::Tuple(positions.into_iter().map(Self::empty_strings_in_array).collect()),
+            JsonArrayType::Tagged(key, variants) => JsonArrayType::Tagged(
+                key,
+                variants.into_iter().map(|(value, tree)| (value, Self::empty_strings_as_null(tree))).collect(),
+            ),
+            array_type => array_type,
+        }
+    }
 
-#[cfg(test)]
-mod tests {
-    use std::borrow::Cow;
-    use crate::lib::model::transform_config::CaseType;
-    use crate::lib::model::transform_config::{RUST_DEFINITION, TransformConfig};
-    use crate::lib::parser::lexer::Lexer;
-    use crate::lib::parser::tokenizer::Tokenizer;
-    use crate::lib::transformer::Transformer;
+    /// Checks whether [TransformOptions::max_types](crate::lib::model::transform_config::TransformOptions::max_types)
+    /// types were already generated, counting the type that can't be generated if so.
+    fn types_exhausted(&mut self) -> bool {
+        let exhausted = self.config.options.max_types.is_some_and(|max_types| self.type_names.len() >= max_types);
+        if exhausted {
+            self.skipped_types += 1;
+        }
+        exhausted
+    }
 
-    #[test]
-    fn simple_json() {
-        let json = "{\"f1\": \"value\", \"f2\": true, \"f3\": 45.3, \"f4\": 12}";
-        let expected_result = vec![
-            vec![
-                "#[derive(Serialize, Deserialize, Debug)]\nstruct Root {",
-                "\tf1: String,",
-                "\tf2: bool,",
-                "\tf3: f32,",
-                "\tf4: i32,",
-                "}",
-            ]
-        ];
+    /// Converts a name to `case_type`, keeping the configured acronyms together and handling the underscores
+    /// around it according to the `underscores` option. With the `ascii_only` option, the name is transliterated first.
+    fn convert_case(&self, name: &str, case_type: &CaseType) -> String {
+        if self.config.options.ascii_only {
+            return convert_name(&transliterate(name), case_type, &self.config.acronyms, &self.config.options.underscores);
+        }
+        convert_name(name, case_type, &self.config.acronyms, &self.config.options.underscores)
+    }
 
-        let lexer = Lexer::new(json);
-        let tokenizer = Tokenizer::new(lexer.start_lex());
-        let transformer = Transformer::new(RUST_DEFINITION, tokenizer.start_tokenizer().unwrap(), None).unwrap();
-        let result = transformer.start_transform();
+    /// Comment noting the files a field of the object being transformed appeared in,
+    /// if the `field_sources` option is enabled.
+    fn source_comment(&self, name: &str) -> Option<String> {
+        let comment = self.config.source_comment.as_ref().filter(|_| self.config.options.field_sources)?;
+        let files = self.sources.get(&self.field_path(name))?;
 
-        assert_eq!(result, expected_result);
+        Some(comment.replace(SOURCES, &files.join(", ")))
     }
 
-    #[test]
-    fn nested_json() {
-        let json = "{\"f1\": \"value\", \"f2\": true, \"f3\": 45.3, \"f4\": {\"f5\": true}}";
-        let expected_result = vec![
-            vec![
-                "#[derive(Serialize, Deserialize, Debug)]\nstruct F4 {",
-                "\tf5: bool,",
-                "}",
-            ],
-            vec![
-                "#[derive(Serialize, Deserialize, Debug)]\nstruct Root {",
-                "\tf1: String,",
-                "\tf2: bool,",
-                "\tf3: f32,",
-                "\tf4: F4,",
-                "}",
-            ],
-        ];
+    /// Comment holding the description of a field of the object being transformed, a line per line of the description.
+    fn doc_comment(&self, name: &str) -> Option<String> {
+        let comment = self.config.doc_comment.as_ref()?;
+        let doc = self.docs.get(&self.field_path(name))?;
 
-        let lexer = Lexer::new(json);
-        let tokenizer = Tokenizer::new(lexer.start_lex());
-        let transformer = Transformer::new(RUST_DEFINITION, tokenizer.start_tokenizer().unwrap(), None).unwrap();
-        let result = transformer.start_transform();
+        Some(doc.lines().map(|line| comment.replace(DOC, line.trim())).collect::<Vec<String>>().join("\n"))
+    }
 
-        assert_eq!(result, expected_result);
+    /// Config's `module_definition`, if the `modules` option is enabled.
+    fn module(&self) -> Option<&ModuleConfig> {
+        self.config.module_definition.as_ref().filter(|_| self.config.options.modules)
+    }
+
+    /// Config's `nested_definition`, if the `nested_types` option is enabled.
+    fn nested(&self) -> Option<&NestedConfig> {
+        self.config.nested_definition.as_ref().filter(|_| self.config.options.nested_types)
+    }
+
+    /// Config's `builder`, if the `builder` option is enabled.
+    fn builder(&self) -> Option<&BuilderConfig> {
+        self.config.builder.as_ref().filter(|_| self.config.options.builder)
+    }
+
+    /// Type used for values whose type can't be inferred.
+    fn unknown_type(&self) -> String {
+        self.config.unknown_type.as_ref().unwrap_or(&self.config.string_type).to_string()
+    }
+
+    /// Checks whether an object gets the config's `object_map_type` instead of its own type,
+    /// see [TransformOptions::empty_object_as](crate::lib::model::transform_config::TransformOptions::empty_object_as).
+    fn is_object_map(&self, tree: &[JsonTree]) -> bool {
+        tree.is_empty() && self.config.options.empty_object_as == EmptyObject::Map
+    }
+
+    /// Type of objects holding values of any type by name, the unknown type when the config has none.
+    fn object_map_type(&self) -> String {
+        match self.config.object_map_type {
+            Some(ref object_map) => object_map.to_string(),
+            None => self.unknown_type(),
+        }
+    }
+
+    /// Text replacing [VISIBILITY], empty when no visibility is configured.
+    fn visibility(&self) -> String {
+        if let Some(module) = self.module().filter(|_| self.module_depth > 0) {
+            return format!("{} ", module.visibility);
+        }
+
+        match self.config.visibility {
+            Some(ref visibility) => format!("{} ", visibility),
+            None => String::new(),
+        }
+    }
+
+    /// Text replacing [MODIFIER], empty unless the `readonly` option is enabled.
+    fn modifier(&self) -> String {
+        match self.config.readonly_modifier {
+            Some(ref modifier) if self.config.options.readonly => format!("{} ", modifier),
+            _ => String::new(),
+        }
+    }
+
+    /// Emits an enum with a variant for each value.
+    /// # Arguments
+    /// * `values` values of the enum, variants are named after them.
+    /// * `name` name of the enum.
+    fn transform_enum(&mut self, values: &[String], name: &str) {
+        let enum_config = match self.config.enum_definition {
+            Some(ref enum_config) => enum_config,
+            None => return,
+        };
+        let mut object: Vec<String> = Vec::new();
+
+        object.extend(self.config.type_attributes.iter().cloned());
+        let with_name = enum_config.definition.replace(OBJECT_NAME, name);
+        object.push(with_name.replace(VISIBILITY, &self.visibility()));
+
+        for value in values {
+            let variant = self.convert_case(value, &self.config.object_case_type);
+            object.push(render(&enum_config.variant_definition, &[(VARIANT, &variant), (NAME, value)]));
+        }
+
+        object.push(enum_config.end.to_string());
+
+        self.push_type(name.to_owned(), object);
+    }
+
+    /// Path of a field of the object being transformed, as written in the `path_overrides` option:
+    /// field names separated by `.`, array elements being marked by `[]` after the array name and map values by `{}`.
+    fn field_path(&self, name: &str) -> String {
+        if self.path.is_empty() {
+            name.to_owned()
+        } else {
+            format!("{}.{}", self.path, name)
+        }
+    }
+
+    /// Builds the [FieldInfo] of a field, transforming the types it needs.
+    /// # Arguments
+    /// * `tree` field source.
+    fn field_info<'a>(&mut self, tree: &'a JsonTree) -> FieldInfo<'a> {
+        let path = self.field_path(tree.name());
+
+        // Optional fields are overridden by the field they wrap, keeping them optional.
+        let override_type = match tree {
+            JsonTree::Optional(_) => None,
+            _ => self.config.options.path_overrides.get(&path).cloned(),
+        };
+
+        if let Some(type_str) = override_type {
+            return FieldInfo {
+                type_str,
+                original_str: tree.name(),
+                name: self.convert_case(tree.name(), &self.config.case_type),
+                annotations: vec![],
+            };
+        }
+
+        match tree {
+            JsonTree::Int(name) => FieldInfo {
+                type_str: self.config.int_type.to_string(),
+                original_str: name,
+                name: self.convert_case(name, &self.config.case_type),
+                annotations: vec![],
+            },
+            JsonTree::Float(name) => FieldInfo {
+                type_str: self.config.float_type.to_string(),
+                original_str: name,
+                name: self.convert_case(name, &self.config.case_type),
+                annotations: vec![],
+            },
+            JsonTree::String(name, samples) => {
+                let (type_str, annotation) = match self.enum_field_type(name, samples) {
+                    Some(type_str) => (type_str, None),
+                    None => self.string_field_type(samples),
+                };
+                if annotation.is_some() && annotation.as_deref() == self.config.bool_string_annotation.as_deref() {
+                    self.bool_strings_used = true;
+                }
+                if annotation.is_some() && annotation.as_deref() == self.config.decimal_string_annotation.as_deref() {
+                    self.decimal_strings_used = true;
+                }
+                FieldInfo {
+                    type_str,
+                    original_str: name,
+                    name: self.convert_case(name, &self.config.case_type),
+                    annotations: annotation.into_iter().collect(),
+                }
+            },
+            JsonTree::Bool(name) => FieldInfo {
+                type_str: self.config.bool_type.to_string(),
+                original_str: name,
+                name: self.convert_case(name, &self.config.case_type),
+                annotations: vec![],
+            },
+            JsonTree::JsonObject(name, tree) if self.is_object_map(tree) => FieldInfo {
+                type_str: self.object_map_type(),
+                original_str: name,
+                name: self.convert_case(name, &self.config.case_type),
+                annotations: vec![],
+            },
+            JsonTree::JsonObject(name, _) if self.types_exhausted() => FieldInfo {
+                type_str: self.unknown_type(),
+                original_str: name,
+                name: self.convert_case(name, &self.config.case_type),
+                annotations: vec![],
+            },
+            JsonTree::JsonObject(name, tree) => {
+                let case_str = self.convert_case(name, &self.config.case_type);
+                let parent_path = mem::replace(&mut self.path, path);
+                let type_str = self.nested_object_type(tree, name);
+                self.path = parent_path;
+                FieldInfo {
+                    type_str,
+                    original_str: name,
+                    name: case_str,
+                    annotations: vec![],
+                }
+            },
+            JsonTree::JsonArray(name, array_type) => {
+                let case_str = self.convert_case(name, &self.config.case_type);
+                let parent_path = mem::replace(&mut self.path, path);
+                let array_str = self.array_field_type(&singularize(name), array_type);
+                self.path = parent_path;
+
+                FieldInfo {
+                    type_str: array_str,
+                    original_str: name,
+                    name: case_str,
+                    annotations: vec![],
+                }
+            }
+            JsonTree::Map(name, value_type) => {
+                let case_str = self.convert_case(name, &self.config.case_type);
+                let parent_path = mem::replace(&mut self.path, path);
+                let map_str = self.map_field_type(&singularize(name), value_type);
+                self.path = parent_path;
+
+                FieldInfo {
+                    type_str: map_str,
+                    original_str: name,
+                    name: case_str,
+                    annotations: vec![],
+                }
+            }
+            JsonTree::Null(name) => FieldInfo {
+                type_str: self.unknown_type(),
+                original_str: name,
+                name: self.convert_case(name, &self.config.case_type),
+                annotations: vec![],
+            },
+            JsonTree::Optional(tree) => {
+                let mut field_info = self.field_info(tree);
+                if self.config.nullable_unknown_type && field_info.type_str == self.unknown_type() {
+                    return field_info;
+                }
+
+                if let Some(ref optional) = self.config.optional_definition {
+                    field_info.type_str = optional.replace(FIELD_TYPE, &field_info.type_str);
+                }
+                field_info
+            }
+        }
+    }
+
+    /// Transforms the kinds of objects of a discriminated array, each into its own type named after
+    /// the discriminator value, wrapped by the config's `sealed_definition`.
+    /// Without `sealed_definition`, the kinds are still transformed but the elements get the unknown type.
+    /// # Arguments
+    /// * `key` discriminator field.
+    /// * `variants` fields of each kind, by discriminator value.
+    /// * `name` name of the wrapper.
+    /// # Returns
+    /// Type of the elements of the array.
+    fn transform_sealed(&mut self, key: &str, variants: &[(String, Vec<JsonTree>)], name: &str) -> String {
+        if self.types_exhausted() {
+            return self.unknown_type();
+        }
+
+        if self.config.sealed_definition.is_none() {
+            for (value, tree) in variants {
+                let variant_str = self.type_name(value);
+                self.transform_object(tree, variant_str);
+            }
+            return self.unknown_type();
+        }
+
+        let type_str = self.type_name(name);
+        let block_end = match self.config.sealed_definition.as_ref().and_then(|sealed| sealed.variant_end.as_ref()) {
+            Some(variant_end) => variant_end.replace(OBJECT_NAME, &type_str),
+            None => self.config.block_end.to_string(),
+        };
+
+        let mut variant_names = Vec::new();
+        for (value, tree) in variants {
+            let variant_str = self.type_name(value);
+            self.transform_object_ending(tree, variant_str.clone(), &block_end);
+            variant_names.push((value, variant_str));
+        }
+
+        let unhashable = variant_names.iter().any(|(_, variant_str)| self.unhashable_types.contains(variant_str));
+        let mut object: Vec<String> = Vec::new();
+        if let Some(ref sealed) = self.config.sealed_definition {
+            object.extend(self.config.type_attributes.iter().cloned());
+            let with_name = sealed.definition.replace(OBJECT_NAME, &type_str).replace(NAME, key);
+            object.push(with_name.replace(VISIBILITY, &self.visibility()));
+
+            if let Some(ref variant_definition) = sealed.variant_definition {
+                for (value, variant_str) in variant_names {
+                    object.push(render(variant_definition, &[(VARIANT, &variant_str), (NAME, value)]));
+                }
+            }
+
+            object.extend(sealed.end.iter().map(|end| end.to_string()));
+        }
+
+        if unhashable {
+            object = self.without_hash_derives(&type_str, object);
+        }
+
+        self.push_type(type_str.clone(), object);
+        type_str
+    }
+
+    /// Tells whether a field type holds a float, directly or through a type in [Transformer::unhashable_types].
+    fn is_unhashable(&self, type_str: &str) -> bool {
+        type_str.split(|c: char| !c.is_alphanumeric() && c != '_')
+            .any(|word| word == self.config.float_type || self.unhashable_types.iter().any(|type_name| type_name == word))
+    }
+
+    /// Removes [HASH_DERIVES] from the derive attributes of a type holding a float, which can't implement them,
+    /// warning about those removed. Derive attributes left empty are removed.
+    /// # Arguments
+    /// * `name` name of the type.
+    /// * `object` lines of the type.
+    /// # Returns
+    /// Lines of the type without the derives.
+    fn without_hash_derives(&mut self, name: &str, object: Vec<String>) -> Vec<String> {
+        self.unhashable_types.push(name.to_owned());
+
+        let mut removed = Vec::new();
+        let object = object.into_iter().filter_map(|line| {
+            let derives = match line.trim().strip_prefix("#[derive(").and_then(|rest| rest.strip_suffix(")]")) {
+                Some(derives) => derives,
+                None => return Some(line),
+            };
+            let (dropped, kept): (Vec<&str>, Vec<&str>) = derives.split(',').map(str::trim).filter(|derive| !derive.is_empty())
+                .partition(|derive| HASH_DERIVES.contains(derive));
+            if dropped.is_empty() {
+                return Some(line);
+            }
+
+            removed.extend(dropped.iter().map(|derive| derive.to_string()));
+            let indent = &line[..line.len() - line.trim_start().len()];
+            (!kept.is_empty()).then(|| format!("{}#[derive({})]", indent, kept.join(", ")))
+        }).collect();
+
+        if !removed.is_empty() {
+            self.warnings.push(format!("{} holds a float and can't derive {}, they are left out", name, removed.join(", ")));
+        }
+        object
+    }
+
+    /// Names and transforms an object held by a field or an array. An object named after the same field
+    /// as a previous one reuses its type when both would be generated the same, instead of being given
+    /// a numbered name. Types declared inside their parent or a module are never reused.
+    /// # Arguments
+    /// * `tree` object source
+    /// * `name` field name the object is named after.
+    /// # Returns
+    /// Type of the fields holding the object.
+    fn nested_object_type(&mut self, tree: &[JsonTree], name: &str) -> String {
+        let type_str = self.type_name(name);
+        let type_str = self.transform_nested_object(tree, type_str);
+        if self.config.options.nested_types || self.module().is_some() {
+            return type_str;
+        }
+
+        let base_str = self.convert_case(name, &self.config.object_case_type);
+        let lines = match self.output.last() {
+            Some((_, lines)) => lines,
+            None => return type_str,
+        };
+        let same_type = self.type_shapes.iter()
+            .find(|(other_base, other_str, other_lines)| {
+                *other_base == base_str && other_lines.len() == lines.len()
+                    && other_lines.iter().zip(lines).all(|(other, line)| other.replace(other_str.as_str(), &type_str) == *line)
+            })
+            .map(|(_, other_str, _)| other_str.clone());
+
+        match same_type {
+            Some(other_str) => {
+                self.output.pop();
+                self.type_names.retain(|type_name| *type_name != type_str);
+                self.unhashable_types.retain(|type_name| *type_name != type_str);
+                other_str
+            }
+            None => {
+                let lines = lines.clone();
+                self.type_shapes.push((base_str, type_str.clone(), lines));
+                type_str
+            }
+        }
+    }
+
+    /// Transforms an object held by a field or an array. When the `modules` option is enabled, the object
+    /// is declared along with the types it needs inside a module named after it.
+    /// # Arguments
+    /// * `tree` object source
+    /// * `name` of the object
+    /// # Returns
+    /// Type of the fields holding the object.
+    fn transform_nested_object(&mut self, tree: &[JsonTree], name: String) -> String {
+        let (definition, end, reference) = match self.module() {
+            Some(module) => (module.definition.to_string(), module.end.to_string(), module.reference.to_string()),
+            None => {
+                self.transform_object(tree, name.clone());
+                return name;
+            }
+        };
+
+        let first_type = self.output.len();
+        self.module_depth += 1;
+        self.transform_object(tree, name.clone());
+        self.module_depth -= 1;
+
+        let module_name = self.convert_case(&name, &CaseType::SnakeCase);
+        let mut module = vec![definition.replace(NAME, &module_name)];
+        for line in self.output.drain(first_type..).flat_map(|(_, lines)| lines) {
+            module.push(format!("\t{}", line.replace('\n', "\n\t")));
+        }
+        module.push(end);
+
+        self.push_type(module_name.clone(), module);
+        reference.replace(NAME, &module_name).replace(OBJECT_NAME, &name)
+    }
+
+    /// Transforms an object of the tree.
+    /// # Arguments
+    /// * `tree` object source
+    /// * `name` of the object
+    fn transform_object(&mut self, tree: &[JsonTree], name: String) {
+        let block_end = self.config.block_end.to_string();
+        self.transform_object_ending(tree, name, &block_end);
+    }
+
+    /// Transforms an object of the tree, closing it with `block_end`.
+    /// # Arguments
+    /// * `tree` object source
+    /// * `name` of the object
+    /// * `block_end` last line of the object.
+    fn transform_object_ending(&mut self, tree: &[JsonTree], name: String, block_end: &str) {
+        let mut object: Vec<String> = Vec::new();
+
+        object.extend(self.config.type_attributes.iter().cloned());
+        object.extend(self.builder().map(|builder| builder.attribute.to_string()));
+
+        if let Some(unit) = self.config.unit_definition.as_ref().filter(|_| tree.is_empty() && self.config.options.empty_object_as == EmptyObject::Unit) {
+            object.push(unit.replace(OBJECT_NAME, &name).replace(VISIBILITY, &self.visibility()));
+            self.push_type(name, object);
+            return;
+        }
+
+        let type_definition = match self.nested().and_then(|nested| nested.type_definition.as_ref()) {
+            Some(nested_definition) if self.nesting_depth > 0 => nested_definition,
+            _ => &self.config.type_definition,
+        };
+        // Every line of the definition is its own line of the output, the last one being the declaration.
+        let with_name = type_definition.replace(OBJECT_NAME, &name).replace(VISIBILITY, &self.visibility());
+        let mut lines: Vec<String> = with_name.split('\n').map(str::to_owned).collect();
+        let declaration = lines.pop().unwrap_or_default();
+        object.append(&mut lines);
+        // Placed after the derives of the definition, attributes of a derive can't come before it.
+        object.extend(self.config.deny_unknown_fields_attribute.iter().filter(|_| self.config.options.deny_unknown_fields).map(|attribute| attribute.to_string()));
+        object.extend(self.config.declaration_attributes.iter().cloned());
+        object.push(declaration);
+
+        let first_child = self.output.len();
+        self.nesting_depth += 1;
+        let fields: Vec<FieldInfo> = tree.iter().map(|tree| self.field_info(tree)).collect();
+        self.nesting_depth -= 1;
+        if fields.iter().any(|field_info| self.is_unhashable(&field_info.type_str)) {
+            object = self.without_hash_derives(&name, object);
+        }
+
+        let modifier = self.modifier();
+        for (field, field_info) in tree.iter().zip(fields.iter()) {
+            object.extend(self.doc_comment(field.name()));
+            object.extend(self.source_comment(field.name()));
+
+            if field_info.name != field_info.original_str && !self.config.options.skip_annotations {
+                object.push(render(&self.config.name_change_annotation, &[(NAME, field_info.original_str)]));
+            }
+
+            object.extend(field_info.annotations.iter().cloned());
+
+            let optional_field_definition = match self.config.readonly_optional_field_definition {
+                Some(ref readonly) if !modifier.is_empty() => Some(readonly),
+                _ => self.config.optional_field_definition.as_ref(),
+            };
+            let definition = match (field, optional_field_definition, self.builder()) {
+                (JsonTree::Optional(_), Some(optional_field), _) => optional_field,
+                (_, _, Some(builder)) => &builder.field_definition,
+                _ => &self.config.field_definition,
+            };
+            object.push(render(definition, &[(FIELD_NAME, &field_info.name), (MODIFIER, &modifier), (FIELD_TYPE, &field_info.type_str)]));
+        }
+
+        if let Some(ref constructor) = self.config.constructor {
+            let arguments: Vec<String> = fields.iter().map(|field_info| {
+                render(&constructor.argument_definition, &[(TYPE, &field_info.type_str), (NAME, &field_info.name)])
+            }).collect();
+
+            let mut arguments_str = arguments.join(&constructor.separator);
+            // Without arguments, there is nothing to separate.
+            if constructor.separator_at_end && !arguments.is_empty() {
+                arguments_str.push_str(&constructor.separator);
+            }
+
+            let with_name = constructor.definition.replace(OBJECT_NAME, &name);
+            let mut definition = with_name.replace(ARGUMENTS, &arguments_str);
+            // Arguments already placed on their own lines by the config are left as they are.
+            let wrap = self.config.options.max_line_width.is_some_and(|width| line_width(&definition) > width);
+            if wrap && !arguments.iter().any(|argument| argument.contains('\n')) {
+                definition = with_name.replace(ARGUMENTS, &wrap_arguments(constructor, &arguments));
+            }
+            object.push(definition);
+
+            if let Some(ref field) = constructor.field_definition {
+                for field_info in fields.iter() {
+                    object.push(render(&field.field_definition, &[(NAME, &field_info.name)]));
+                }
+                object.push(field.end.to_string());
+            }
+        }
+
+        let mut body_end = None;
+        if let Some(nested) = self.nested() {
+            let body_start = nested.body_start.as_deref().filter(|_| self.output.len() > first_child);
+            match body_start {
+                // The kinds of a discriminated array end with their own block end, their types are left beside them.
+                Some(_) if block_end != self.config.block_end => {}
+                Some(body_start) => {
+                    object.push(body_start.to_owned());
+                    body_end = Some(nested.end.as_deref().unwrap_or_default().to_owned());
+                }
+                None => body_end = Some(block_end.to_owned()),
+            }
+        }
+
+        match body_end {
+            Some(body_end) => {
+                // Types needed by the fields were pushed while building them, they are moved inside this one.
+                for line in self.output.drain(first_child..).flat_map(|(_, lines)| lines) {
+                    object.push(format!("\t{}", line.replace('\n', "\n\t")));
+                }
+                if !body_end.is_empty() {
+                    object.push(body_end);
+                }
+            }
+            // Indentation based languages have no block end, pushing it would leave an empty line.
+            None if !block_end.is_empty() => object.push(block_end.to_owned()),
+            None => {}
+        }
+
+        if self.config.options.value_conversions {
+            if let Some(ref conversion) = self.config.conversion {
+                object.push(conversion.definition.replace(OBJECT_NAME, &name));
+                for field_info in fields.iter() {
+                    let values = [(FIELD_NAME, field_info.name.as_str()), (NAME, field_info.original_str), (FIELD_TYPE, &field_info.type_str)];
+                    object.push(render(&conversion.field_definition, &values));
+                }
+                object.push(conversion.end.to_string());
+            }
+        }
+
+        self.push_type(name, object);
+    }
+
+    /// Adds a generated type to the output. When streaming, the types added before it are written first,
+    /// unless the `nested_types` option or a module keeps them for their parent: they are complete, while the last one
+    /// may still be the root, missing its `root_attributes`.
+    fn push_type(&mut self, name: String, lines: Vec<String>) {
+        let sorted = self.config.options.sort_types || self.config.options.sort_types_by_name;
+        if !self.config.options.nested_types && !sorted && self.module_depth == 0 {
+            self.write_types();
+        }
+        self.output.push((name, lines));
+    }
+
+    /// Writes and drops the types of the output when streaming, see [Transformer::start_transform_to].
+    /// Types are separated by a blank line, as in [Transformer::transform_to_string].
+    fn write_types(&mut self) {
+        let writer = match self.writer {
+            Some(ref mut writer) if self.write_error.is_none() => writer,
+            _ => return,
+        };
+
+        for (_, lines) in self.output.drain(..) {
+            let separator = if self.wrote_type { Some(String::new()) } else { None };
+            self.wrote_type = true;
+            for line in separator.into_iter().chain(lines) {
+                if let Err(error) = writeln!(writer, "{}", line) {
+                    self.write_error = Some(error);
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Orders the output so that every type comes after the types it references, types keeping their order otherwise.
+    /// Types referencing each other keep the first one generated first.
+    fn sort_types(&mut self) {
+        let types = mem::take(&mut self.output);
+        let mut visited = vec![false; types.len()];
+        let mut order = Vec::with_capacity(types.len());
+
+        for i in 0..types.len() {
+            Self::visit_type(&types, i, &mut visited, &mut order);
+        }
+
+        let mut types: Vec<Option<(String, Vec<String>)>> = types.into_iter().map(Some).collect();
+        self.output = order.into_iter().filter_map(|i| types[i].take()).collect();
+    }
+
+    /// Adds a type to `order` after the types it references, see [Transformer::sort_types].
+    fn visit_type(types: &[(String, Vec<String>)], i: usize, visited: &mut [bool], order: &mut Vec<usize>) {
+        if visited[i] {
+            return;
+        }
+        // Marked before its references, a cycle stops here.
+        visited[i] = true;
+
+        for (j, (name, _)) in types.iter().enumerate() {
+            if j != i && references(&types[i].1, name) {
+                Self::visit_type(types, j, visited, order);
+            }
+        }
+
+        order.push(i);
+    }
+
+    /// Transforms an object in isolation, along with the objects it holds, without walking the whole tree.
+    /// Names are given as if nothing else had been transformed since the last call, so repeated calls give the same types.
+    /// It takes `&mut self` rather than `&self` as the types are built with the transformer's own state,
+    /// the names given meanwhile being put back before returning.
+    /// # Arguments
+    /// * `tree` fields of the object.
+    /// * `name` name of the object.
+    /// # Returns
+    /// Generated types, the object being the last one.
+    pub fn transform_subtree(&mut self, tree: &[JsonTree], name: &str) -> Vec<Vec<String>> {
+        let type_names = self.type_names.clone();
+        let type_shapes = self.type_shapes.clone();
+        let unhashable_types = self.unhashable_types.clone();
+        let skipped_types = self.skipped_types;
+        let helpers_used = (self.bool_strings_used, self.decimal_strings_used);
+
+        let types = self.transform_root_object(tree, name);
+
+        self.type_names = type_names;
+        self.type_shapes = type_shapes;
+        self.unhashable_types = unhashable_types;
+        self.skipped_types = skipped_types;
+        (self.bool_strings_used, self.decimal_strings_used) = helpers_used;
+        types.into_iter().map(|(_, lines)| lines).collect()
+    }
+
+    /// Transforms an object along with the objects it holds, see [Transformer::transform_subtree].
+    /// Names already given by previous transformations are not reused.
+    /// # Returns
+    /// Generated types along with their names, the object being the last one.
+    fn transform_root_object(&mut self, tree: &[JsonTree], name: &str) -> Vec<(String, Vec<String>)> {
+        let output = mem::take(&mut self.output);
+        if !self.type_names.iter().any(|type_name| type_name == name) {
+            self.type_names.push(name.to_owned());
+        }
+
+        self.transform_object(tree, name.to_owned());
+        mem::replace(&mut self.output, output)
+    }
+
+    /// consumes the struct and start the transformation process.
+    /// # Returns
+    /// Struct's field `output`. Each vector represents an object, each object is made of a vector of lines.
+    pub fn start_transform(mut self) -> Vec<Vec<String>> {
+        self.start_transform_named().into_iter().map(|(_, lines)| lines).collect()
+    }
+
+    /// Same as [Transformer::start_transform], joining the types into ready to write source text: every line ends
+    /// with a newline and types are separated by a blank line, the root type last.
+    pub fn transform_to_string(self) -> String {
+        let types: Vec<String> = self.start_transform().into_iter()
+            .map(|lines| lines.into_iter().map(|line| line + "\n").collect())
+            .collect();
+        types.join("\n")
+    }
+
+    /// Same as [Transformer::start_transform], each object being given along with the name of its type.
+    /// The transformer is kept for its [Transformer::warnings].
+    pub fn start_transform_named(&mut self) -> Vec<(String, Vec<String>)> {
+        self.transform();
+        mem::take(&mut self.output)
+    }
+
+    /// Problems met while transforming that didn't stop it, such as types left out because of
+    /// [TransformOptions::max_types](crate::lib::model::transform_config::TransformOptions::max_types).
+    pub fn warnings(&self) -> &[String] {
+        &self.warnings
+    }
+
+    /// Same as [Transformer::start_transform], writing every type to `writer` as soon as it is generated
+    /// instead of keeping them all. Types are written in the order they are generated, the root type last,
+    /// giving the same text as [Transformer::transform_to_string].
+    /// # Errors
+    /// If writing fails, the first error is returned and the remaining types are not written.
+    pub fn start_transform_to(&mut self, writer: &'w mut dyn Write) -> io::Result<()> {
+        self.writer = Some(writer);
+        self.transform();
+        self.write_types();
+        self.writer = None;
+
+        match self.write_error.take() {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
+    }
+
+    /// Transforms the whole tree into the output, the root type being the last one.
+    fn transform(&mut self) {
+        let tree = mem::replace(&mut self.tree, JsonRoot::Object(vec![]));
+        let name = self.name.clone().unwrap_or_else(|| String::from("Root"));
+        self.type_names.push(name.clone());
+
+        let tree = if self.config.options.flatten {
+            match tree {
+                JsonRoot::Object(tree) => JsonRoot::Object(Self::flatten(tree)),
+                JsonRoot::Array(array_type) => JsonRoot::Array(Self::flatten_array(array_type)),
+                root => root,
+            }
+        } else {
+            tree
+        };
+
+        let tree = if self.config.options.empty_strings_as_null {
+            match tree {
+                JsonRoot::Object(tree) => JsonRoot::Object(Self::empty_strings_as_null(tree)),
+                JsonRoot::Array(array_type) => JsonRoot::Array(Self::empty_strings_in_array(array_type)),
+                JsonRoot::Value(tree) => JsonRoot::Value(Self::empty_strings_as_null(vec![tree]).remove(0)),
+            }
+        } else {
+            tree
+        };
+
+        match tree {
+            JsonRoot::Object(tree) => {
+                self.output = self.transform_root_object(&tree, &name);
+                // The root object is pushed last, after the objects it holds.
+                if let Some((_, root)) = self.output.last_mut() {
+                    root.splice(0..0, self.config.root_attributes.iter().cloned());
+                    if self.config.options.parse_helper {
+                        if let Some(ref parse_helper) = self.config.parse_helper {
+                            root.push(parse_helper.replace(OBJECT_NAME, &name));
+                        }
+                    }
+                }
+            }
+            JsonRoot::Array(array_type) => self.transform_root_array(&array_type, name),
+            JsonRoot::Value(tree) => self.transform_root_value(tree, name),
+        }
+
+        if self.bool_strings_used {
+            if let Some(ref helper) = self.config.bool_string_helper {
+                let lines = helper.split('\n').map(str::to_owned).collect();
+                self.push_type(String::from("bool_from_string"), lines);
+            }
+        }
+
+        if self.decimal_strings_used {
+            if let Some(ref helper) = self.config.decimal_string_helper {
+                let lines = helper.split('\n').map(str::to_owned).collect();
+                self.push_type(String::from("decimal_from_string"), lines);
+            }
+        }
+
+        if self.config.options.sort_types_by_name {
+            // A stable sort, sort_types then only moves the types referenced before their declaration.
+            self.output.sort_by(|(name, _), (other_name, _)| name.cmp(other_name));
+        }
+
+        if self.config.options.sort_types {
+            self.sort_types();
+        }
+
+        if self.skipped_types > 0 {
+            self.warnings.push(format!("the maximum number of types was reached, {} more were given the unknown type", self.skipped_types));
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+    use crate::lib::model::parse_options::ParseOptions;
+    use crate::lib::model::transform_config::{CaseType, EmptyObject, TransformOptions, Underscores};
+    use crate::lib::model::transform_config::{DART_DEFINITION, JAVA_DEFINITION, KOTLIN_DEFINITION, KOTLIN_SERIALIZABLE_DEFINITION, PHP_DEFINITION, RUST_DEFINITION, TYPESCRIPT_DEFINITION, TransformConfig};
+    use crate::lib::parser::lexer::Lexer;
+    use crate::lib::parser::schema::SchemaReader;
+    use crate::lib::parser::tokenizer::Tokenizer;
+    use crate::lib::model::tree::{JsonRoot, JsonTree};
+    use crate::lib::transformer::{Transformer, TransformerError, references};
+    use crate::lib::inference::{InferredType, TypeInference};
+    use crate::lib::sources::{FieldSources, add_sources};
+
+    #[test]
+    fn simple_json() {
+        let json = "{\"f1\": \"value\", \"f2\": true, \"f3\": 45.3, \"f4\": 12}";
+        let expected_result = vec![
+            vec![
+                "#[derive(Serialize, Deserialize, Debug)]",
+                "struct Root {",
+                "\tf1: String,",
+                "\tf2: bool,",
+                "\tf3: f32,",
+                "\tf4: i32,",
+                "}",
+            ]
+        ];
+
+        let lexer = Lexer::new(json);
+        let tokenizer = Tokenizer::new(lexer.start_lex(), ParseOptions::default());
+        let transformer = Transformer::new(RUST_DEFINITION, tokenizer.start_tokenizer().unwrap(), None).unwrap();
+        let result = transformer.start_transform();
+
+        assert_eq!(result, expected_result);
+    }
+
+    #[test]
+    fn nested_json() {
+        let json = "{\"f1\": \"value\", \"f2\": true, \"f3\": 45.3, \"f4\": {\"f5\": true}}";
+        let expected_result = vec![
+            vec![
+                "#[derive(Serialize, Deserialize, Debug)]",
+                "struct F4 {",
+                "\tf5: bool,",
+                "}",
+            ],
+            vec![
+                "#[derive(Serialize, Deserialize, Debug)]",
+                "struct Root {",
+                "\tf1: String,",
+                "\tf2: bool,",
+                "\tf3: f32,",
+                "\tf4: F4,",
+                "}",
+            ],
+        ];
+
+        let lexer = Lexer::new(json);
+        let tokenizer = Tokenizer::new(lexer.start_lex(), ParseOptions::default());
+        let transformer = Transformer::new(RUST_DEFINITION, tokenizer.start_tokenizer().unwrap(), None).unwrap();
+        let result = transformer.start_transform();
+
+        assert_eq!(result, expected_result);
+    }
+
+    #[test]
+    fn nested_json_subtree() {
+        let json = "{\"f1\": \"value\", \"f2\": true, \"f3\": 45.3, \"f4\": {\"f5\": true, \"f6\": {\"f7\": 1}}}";
+        let expected_result = vec![
+            vec![
+                "#[derive(Serialize, Deserialize, Debug)]".to_owned(),
+                "struct F6 {".to_owned(),
+                "\tf7: i32,".to_owned(),
+                "}".to_owned(),
+            ],
+            vec![
+                "#[derive(Serialize, Deserialize, Debug)]".to_owned(),
+                "struct F4 {".to_owned(),
+                "\tf5: bool,".to_owned(),
+                "\tf6: F6,".to_owned(),
+                "}".to_owned(),
+            ],
+        ];
+
+        let lexer = Lexer::new(json);
+        let tokenizer = Tokenizer::new(lexer.start_lex(), ParseOptions::default());
+        let tree = match tokenizer.start_tokenizer().unwrap() {
+            JsonRoot::Object(tree) => tree,
+            root => panic!("unexpected root {:?}", root),
+        };
+        let subtree = tree.iter().find_map(|field| match field {
+            JsonTree::JsonObject(name, tree) if name == "f4" => Some(tree),
+            _ => None,
+        }).unwrap();
+
+        let mut transformer = Transformer::new(RUST_DEFINITION, JsonRoot::Object(vec![]), None).unwrap();
+
+        assert_eq!(transformer.transform_subtree(subtree, "F4"), expected_result);
+        // The names given by the first call are forgotten.
+        assert_eq!(transformer.transform_subtree(subtree, "F4"), expected_result);
+    }
+
+    #[test]
+    fn empty_block_end() {
+        let json = "{\"f1\": 1, \"f2\": {\"f3\": true}}";
+        let expected_result = vec![
+            vec![
+                "class F2:",
+                "\tf3: bool",
+            ],
+            vec![
+                "class Root:",
+                "\tf1: int",
+                "\tf2: F2",
+            ],
+        ];
+
+        let mut config = RUST_DEFINITION;
+        config.type_definition = Cow::Borrowed("class {object_name}:");
+        config.field_definition = Cow::Borrowed("\t{field_name}: {field_type}");
+        config.int_type = Cow::Borrowed("int");
+        config.block_end = Cow::Borrowed("");
+
+        let lexer = Lexer::new(json);
+        let tokenizer = Tokenizer::new(lexer.start_lex(), ParseOptions::default());
+        let transformer = Transformer::new(config, tokenizer.start_tokenizer().unwrap(), None).unwrap();
+        let result = transformer.start_transform();
+
+        assert_eq!(result, expected_result);
+    }
+
+    #[test]
+    fn nested_types() {
+        let json = "{\"f1\": 1, \"f2\": {\"f3\": {\"f4\": true}}}";
+        let expected_result = vec![
+            vec![
+                "class Root {",
+                "\tprivate final int f1;",
+                "\tprivate final F2 f2;",
+                "\tpublic Root(int f1, F2 f2) {",
+                "\t\tthis.f1 = f1;",
+                "\t\tthis.f2 = f2;",
+                "\t}",
+                "\tstatic class F2 {",
+                "\t\tprivate final F3 f3;",
+                "\t\tpublic F2(F3 f3) {",
+                "\t\t\tthis.f3 = f3;",
+                "\t\t}",
+                "\t\tstatic class F3 {",
+                "\t\t\tprivate final boolean f4;",
+                "\t\t\tpublic F3(boolean f4) {",
+                "\t\t\t\tthis.f4 = f4;",
+                "\t\t\t}",
+                "\t\t}",
+                "\t}",
+                "}",
+            ],
+        ];
+
+        let mut config = JAVA_DEFINITION;
+        config.options.nested_types = true;
+
+        let lexer = Lexer::new(json);
+        let tokenizer = Tokenizer::new(lexer.start_lex(), ParseOptions::default());
+        let transformer = Transformer::new(config, tokenizer.start_tokenizer().unwrap(), None).unwrap();
+        let result = transformer.start_transform();
+
+        assert_eq!(result, expected_result);
+    }
+
+    #[test]
+    fn kotlin_nested_types() {
+        let json = "{\"f1\": 1, \"f2\": {\"f3\": {\"f4\": true}, \"f5\": [{\"f6\": \"a\"}]}}";
+        let expected_result = vec![
+            vec![
+                "@Serializable",
+                "data class Root (",
+                "\tval f1: Long,",
+                "\tval f2: F2,",
+                ") {",
+                "\t@Serializable",
+                "\tdata class F2 (",
+                "\t\tval f3: F3,",
+                "\t\tval f5: List<F5>,",
+                "\t) {",
+                "\t\t@Serializable",
+                "\t\tdata class F3 (",
+                "\t\t\tval f4: Boolean,",
+                "\t\t)",
+                "\t\t@Serializable",
+                "\t\tdata class F5 (",
+                "\t\t\tval f6: String,",
+                "\t\t)",
+                "\t}",
+                "}",
+            ],
+        ];
+
+        let mut config = KOTLIN_SERIALIZABLE_DEFINITION;
+        config.options.nested_types = true;
+
+        let lexer = Lexer::new(json);
+        let tokenizer = Tokenizer::new(lexer.start_lex(), ParseOptions::default());
+        let transformer = Transformer::new(config, tokenizer.start_tokenizer().unwrap(), None).unwrap();
+        let result = transformer.start_transform();
+
+        assert_eq!(result, expected_result);
+    }
+
+    #[test]
+    fn nested_types_unsupported() {
+        let mut config = RUST_DEFINITION;
+        config.options.nested_types = true;
+
+        let lexer = Lexer::new("{\"f1\": {\"f2\": 1}}");
+        let tokenizer = Tokenizer::new(lexer.start_lex(), ParseOptions::default());
+        let result = Transformer::new(config, tokenizer.start_tokenizer().unwrap(), None);
+
+        assert!(matches!(result, Err(TransformerError::NestedTypesUnsupported)));
+    }
+
+    #[test]
+    fn untyped_array_definition() {
+        let mut config = RUST_DEFINITION;
+        config.array_definition = Cow::Borrowed("Vec");
+        let result = Transformer::new(config, JsonRoot::Object(vec![]), None);
+
+        assert!(matches!(result, Err(TransformerError::BadArrayTypeDefinition(_))));
+
+        let mut config = RUST_DEFINITION;
+        config.array_definition = Cow::Borrowed("Vec");
+        config.untyped_arrays = true;
+
+        assert!(Transformer::new(config, JsonRoot::Object(vec![]), None).is_ok());
+    }
+
+    #[test]
+    fn path_overrides() {
+        let json = "{\"user\": {\"address\": {\"zip\": \"123\"}, \"orders\": [{\"zip\": \"4\"}]}, \"zip\": \"456\"}";
+        let expected_result = vec![
+            vec![
+                "#[derive(Serialize, Deserialize, Debug)]",
+                "struct Address {",
+                "\tzip: ZipCode,",
+                "}",
+            ],
+            vec![
+                "#[derive(Serialize, Deserialize, Debug)]",
+                "struct Order {",
+                "\tzip: u32,",
+                "}",
+            ],
+            vec![
+                "#[derive(Serialize, Deserialize, Debug)]",
+                "struct User {",
+                "\taddress: Address,",
+                "\torders: Vec<Order>,",
+                "}",
+            ],
+            vec![
+                "#[derive(Serialize, Deserialize, Debug)]",
+                "struct Root {",
+                "\tuser: User,",
+                "\tzip: String,",
+                "}",
+            ],
+        ];
+
+        let mut config = RUST_DEFINITION;
+        config.options.path_overrides.insert("user.address.zip".to_owned(), "ZipCode".to_owned());
+        config.options.path_overrides.insert("user.orders[].zip".to_owned(), "u32".to_owned());
+
+        let lexer = Lexer::new(json);
+        let tokenizer = Tokenizer::new(lexer.start_lex(), ParseOptions::default());
+        let transformer = Transformer::new(config, tokenizer.start_tokenizer().unwrap(), None).unwrap();
+        let result = transformer.start_transform();
+
+        assert_eq!(result, expected_result);
+    }
+
+    #[test]
+    fn array_json() {
+        let json = "{\"f1\": [1, 2], \"f2\": [[\"a\"], [\"b\"]], \"f3\": [{\"f4\": true}]}";
+        let expected_result = vec![
+            vec![
+                "#[derive(Serialize, Deserialize, Debug)]",
+                "struct F3 {",
+                "\tf4: bool,",
+                "}",
+            ],
+            vec![
+                "#[derive(Serialize, Deserialize, Debug)]",
+                "struct Root {",
+                "\tf1: Vec<i32>,",
+                "\tf2: Vec<Vec<String>>,",
+                "\tf3: Vec<F3>,",
+                "}",
+            ],
+        ];
+
+        let lexer = Lexer::new(json);
+        let tokenizer = Tokenizer::new(lexer.start_lex(), ParseOptions::default());
+        let transformer = Transformer::new(RUST_DEFINITION, tokenizer.start_tokenizer().unwrap(), None).unwrap();
+        let result = transformer.start_transform();
+
+        assert_eq!(result, expected_result);
+    }
+
+    #[test]
+    fn root_array_names() {
+        let json = "[{\"id\": 1}]";
+        let expected_result = vec![
+            vec![
+                "#[derive(Serialize, Deserialize, Debug)]",
+                "struct User {",
+                "\tid: i32,",
+                "}",
+            ],
+            vec![
+                "type Users = Vec<User>;",
+            ],
+        ];
+
+        let mut config = RUST_DEFINITION;
+        config.options.item_name = Some("User".to_owned());
+
+        let lexer = Lexer::new(json);
+        let tokenizer = Tokenizer::new(lexer.start_lex(), ParseOptions::default());
+        let transformer = Transformer::new(config, tokenizer.start_tokenizer().unwrap(), Some("Users".to_owned())).unwrap();
+        let result = transformer.start_transform();
+
+        assert_eq!(result, expected_result);
+    }
+
+    #[test]
+    fn root_array_default_names() {
+        let json = "[{\"id\": 1}]";
+        let expected_result = vec![
+            vec![
+                "#[derive(Serialize, Deserialize, Debug)]",
+                "struct RootItem {",
+                "\tid: i32,",
+                "}",
+            ],
+            vec![
+                "type Root = Vec<RootItem>;",
+            ],
+        ];
+
+        let transform = |item_name: Option<&str>, name: Option<&str>| {
+            let mut config = RUST_DEFINITION;
+            config.options.item_name = item_name.map(str::to_owned);
+
+            let lexer = Lexer::new(json);
+            let tokenizer = Tokenizer::new(lexer.start_lex(), ParseOptions::default());
+            let transformer = Transformer::new(config, tokenizer.start_tokenizer().unwrap(), name.map(str::to_owned)).unwrap();
+            transformer.start_transform()
+        };
+
+        assert_eq!(transform(None, None), expected_result);
+        assert_eq!(transform(Some("User"), None)[1], vec!["type Root = Vec<User>;"]);
+        assert_eq!(transform(None, Some("Users"))[1], vec!["type Users = Vec<UsersItem>;"]);
+    }
+
+    #[test]
+    fn truncated_samples_keep_string_type() {
+        let mut prices: Vec<String> = (1..=40).map(|i| format!("{{\"price\": \"{}.99\"}}", i)).collect();
+        prices.push("{\"price\": \"N/A\"}".to_owned());
+        let json = format!("[{}]", prices.join(", "));
+
+        let mut config = RUST_DEFINITION;
+        config.options.decimal_strings = true;
+
+        let lexer = Lexer::new(&json);
+        let tokenizer = Tokenizer::new(lexer.start_lex(), ParseOptions::default());
+        let transformer = Transformer::new(config, tokenizer.start_tokenizer().unwrap(), None).unwrap();
+
+        assert_eq!(transformer.start_transform()[0][2], "\tprice: String,");
+    }
+
+    #[test]
+    fn truncated_samples_without_prefix_type() {
+        let mut ids: Vec<String> = (1..=40).map(|i| format!("{{\"id\": \"usr_{}\"}}", i)).collect();
+        ids.push("{\"id\": \"grp_1\"}".to_owned());
+        let json = format!("[{}]", ids.join(", "));
+
+        let mut config = RUST_DEFINITION;
+        config.options.string_prefixes.insert("usr_".to_owned(), "UserId".to_owned());
+
+        let lexer = Lexer::new(&json);
+        let tokenizer = Tokenizer::new(lexer.start_lex(), ParseOptions::default());
+        let transformer = Transformer::new(config, tokenizer.start_tokenizer().unwrap(), None).unwrap();
+
+        assert_eq!(transformer.start_transform()[0][2], "\tid: String,");
+    }
+
+    #[test]
+    fn truncated_samples_not_bytes() {
+        let mut values: Vec<String> = (10..50).map(|i| format!("{{\"data\": \"SGVsbG8sIGJhc2U2NCBlbmNvZGVkIHdvcmxkIQ{}\"}}", i)).collect();
+        values.push("{\"data\": \"not base64 at all, it holds spaces and commas\"}".to_owned());
+        let json = format!("[{}]", values.join(", "));
+
+        let mut config = RUST_DEFINITION;
+        config.options.base64_bytes = true;
+
+        let lexer = Lexer::new(&json);
+        let tokenizer = Tokenizer::new(lexer.start_lex(), ParseOptions::default());
+        let transformer = Transformer::new(config, tokenizer.start_tokenizer().unwrap(), None).unwrap();
+
+        assert_eq!(transformer.start_transform()[0], vec![
+            "#[derive(Serialize, Deserialize, Debug)]",
+            "struct RootItem {",
+            "\tdata: String,",
+            "}",
+        ]);
+    }
+
+    #[test]
+    fn transform_to_string() {
+        let json = "{\"f1\": 1, \"f2\": {\"f3\": true}}";
+        let expected_result = concat!(
+            "#[derive(Serialize, Deserialize, Debug)]\nstruct F2 {\n\tf3: bool,\n}\n",
+            "\n",
+            "#[derive(Serialize, Deserialize, Debug)]\nstruct Root {\n\tf1: i32,\n\tf2: F2,\n}\n",
+        );
+
+        let lexer = Lexer::new(json);
+        let tokenizer = Tokenizer::new(lexer.start_lex(), ParseOptions::default());
+        let transformer = Transformer::new(RUST_DEFINITION, tokenizer.start_tokenizer().unwrap(), None).unwrap();
+
+        assert_eq!(transformer.transform_to_string(), expected_result);
+    }
+
+    #[test]
+    fn root_value() {
+        let transform = |json: &str| {
+            let lexer = Lexer::new(json);
+            let tokenizer = Tokenizer::new(lexer.start_lex(), ParseOptions::default());
+            let transformer = Transformer::new(RUST_DEFINITION, tokenizer.start_tokenizer().unwrap(), None).unwrap();
+            transformer.start_transform()
+        };
+
+        assert_eq!(transform("42"), vec![vec!["type Root = i32;"]]);
+        assert_eq!(transform("\"hi\""), vec![vec!["type Root = String;"]]);
+        assert_eq!(transform("true"), vec![vec!["type Root = bool;"]]);
+    }
+
+    #[test]
+    fn type_attributes_and_visibility() {
+        let json = "{\"f1\": {\"f2\": true}}";
+        let expected_result = vec![
+            vec![
+                "#[non_exhaustive]",
+                "#[derive(Serialize, Deserialize, Debug)]",
+                "pub struct F1 {",
+                "\tf2: bool,",
+                "}",
+            ],
+            vec![
+                "#[allow(dead_code)]",
+                "#[non_exhaustive]",
+                "#[derive(Serialize, Deserialize, Debug)]",
+                "pub struct Root {",
+                "\tf1: F1,",
+                "}",
+            ],
+        ];
+
+        let mut config = RUST_DEFINITION;
+        config.type_attributes = vec!["#[non_exhaustive]".to_owned()];
+        config.root_attributes = vec!["#[allow(dead_code)]".to_owned()];
+        config.visibility = Some(Cow::Borrowed("pub"));
+
+        let lexer = Lexer::new(json);
+        let tokenizer = Tokenizer::new(lexer.start_lex(), ParseOptions::default());
+        let transformer = Transformer::new(config, tokenizer.start_tokenizer().unwrap(), None).unwrap();
+        let result = transformer.start_transform();
+
+        assert_eq!(result, expected_result);
+    }
+
+    #[test]
+    fn declaration_attributes() {
+        let json = "{\"f1\": true}";
+        let expected_result = vec![
+            vec![
+                "#[non_exhaustive]",
+                "#[derive(Serialize, Deserialize, Debug)]",
+                "#[serde(deny_unknown_fields)]",
+                "#[serde(rename_all = \"camelCase\")]",
+                "",
+                "struct Root {",
+                "\tf1: bool,",
+                "}",
+            ],
+        ];
+
+        let mut config = RUST_DEFINITION;
+        config.type_attributes = vec!["#[non_exhaustive]".to_owned()];
+        config.declaration_attributes = vec!["#[serde(rename_all = \"camelCase\")]".to_owned(), String::new()];
+        config.options.deny_unknown_fields = true;
+
+        let lexer = Lexer::new(json);
+        let tokenizer = Tokenizer::new(lexer.start_lex(), ParseOptions::default());
+        let transformer = Transformer::new(config, tokenizer.start_tokenizer().unwrap(), None).unwrap();
+
+        assert_eq!(transformer.start_transform(), expected_result);
+    }
+
+    #[test]
+    fn hash_derives_without_floats() {
+        let json = "{\"tag\": {\"id\": 1}, \"points\": [{\"x\": 1.5}]}";
+        let expected_result = vec![
+            vec![
+                "#[derive(Serialize, Deserialize, Debug)]",
+                "#[derive(PartialEq, Eq, Hash)]",
+                "struct Tag {",
+                "\tid: i32,",
+                "}",
+            ],
+            vec![
+                "#[derive(Serialize, Deserialize, Debug)]",
+                "#[derive(PartialEq)]",
+                "struct Point {",
+                "\tx: f32,",
+                "}",
+            ],
+            vec![
+                "#[derive(Serialize, Deserialize, Debug)]",
+                "#[derive(PartialEq)]",
+                "struct Root {",
+                "\ttag: Tag,",
+                "\tpoints: Vec<Point>,",
+                "}",
+            ],
+        ];
+
+        let mut config = RUST_DEFINITION;
+        config.declaration_attributes = vec!["#[derive(PartialEq, Eq, Hash)]".to_owned()];
+
+        let lexer = Lexer::new(json);
+        let tokenizer = Tokenizer::new(lexer.start_lex(), ParseOptions::default());
+        let mut transformer = Transformer::new(config, tokenizer.start_tokenizer().unwrap(), None).unwrap();
+        let result: Vec<Vec<String>> = transformer.start_transform_named().into_iter().map(|(_, lines)| lines).collect();
+
+        assert_eq!(result, expected_result);
+        assert_eq!(transformer.warnings(), [
+            "Point holds a float and can't derive Eq, Hash, they are left out",
+            "Root holds a float and can't derive Eq, Hash, they are left out",
+        ]);
+    }
+
+    #[test]
+    fn alternating_objects_and_arrays() {
+        let json = "{\"a\": [{\"b\": [{\"c\": 1}]}]}";
+        let expected_result = vec![
+            vec![
+                "#[derive(Serialize, Deserialize, Debug)]",
+                "struct B {",
+                "\tc: i32,",
+                "}",
+            ],
+            vec![
+                "#[derive(Serialize, Deserialize, Debug)]",
+                "struct A {",
+                "\tb: Vec<B>,",
+                "}",
+            ],
+            vec![
+                "#[derive(Serialize, Deserialize, Debug)]",
+                "struct Root {",
+                "\ta: Vec<A>,",
+                "}",
+            ],
+        ];
+
+        let lexer = Lexer::new(json);
+        let tokenizer = Tokenizer::new(lexer.start_lex(), ParseOptions::default());
+        let transformer = Transformer::new(RUST_DEFINITION, tokenizer.start_tokenizer().unwrap(), None).unwrap();
+        let result = transformer.start_transform();
+
+        assert_eq!(result, expected_result);
+    }
+
+    #[test]
+    fn nested_object_arrays() {
+        let json = "{\"groups\": [{\"users\": [{\"id\": 1}]}], \"matrix\": [[{\"cell\": true}]]}";
+        let expected_result = vec![
+            vec![
+                "#[derive(Serialize, Deserialize, Debug)]",
+                "struct User {",
+                "\tid: i32,",
+                "}",
+            ],
+            vec![
+                "#[derive(Serialize, Deserialize, Debug)]",
+                "struct Group {",
+                "\tusers: Vec<User>,",
+                "}",
+            ],
+            vec![
+                "#[derive(Serialize, Deserialize, Debug)]",
+                "struct Matrix {",
+                "\tcell: bool,",
+                "}",
+            ],
+            vec![
+                "#[derive(Serialize, Deserialize, Debug)]",
+                "struct Root {",
+                "\tgroups: Vec<Group>,",
+                "\tmatrix: Vec<Vec<Matrix>>,",
+                "}",
+            ],
+        ];
+
+        let lexer = Lexer::new(json);
+        let tokenizer = Tokenizer::new(lexer.start_lex(), ParseOptions::default());
+        let transformer = Transformer::new(RUST_DEFINITION, tokenizer.start_tokenizer().unwrap(), None).unwrap();
+        let result = transformer.start_transform();
+
+        assert_eq!(result, expected_result);
+    }
+
+    #[test]
+    fn deterministic_type_names() {
+        let json = "{\"a\": {\"data\": {\"x\": 1}}, \"b\": {\"data\": {\"y\": true}}, \"c\": [{\"data\": {\"z\": 1.5}}]}";
+        let transform = || {
+            let lexer = Lexer::new(json);
+            let tokenizer = Tokenizer::new(lexer.start_lex(), ParseOptions::default());
+            let transformer = Transformer::new(RUST_DEFINITION, tokenizer.start_tokenizer().unwrap(), None).unwrap();
+            transformer.start_transform()
+        };
+
+        let result = transform();
+        let type_lines: Vec<&str> = result.iter().map(|object| object[1].as_str()).collect();
+        assert_eq!(type_lines, vec![
+            "struct Data {",
+            "struct A {",
+            "struct Data2 {",
+            "struct B {",
+            "struct Data3 {",
+            "struct C {",
+            "struct Root {",
+        ]);
+
+        for _ in 0..100 {
+            assert_eq!(transform(), result);
+        }
+    }
+
+    #[test]
+    fn same_name_objects() {
+        let json = "{\"item\": {\"id\": 1}, \"orders\": [{\"item\": {\"name\": \"a\"}}], \"other\": {\"item\": {\"id\": 2}}}";
+        let expected_result = vec![
+            vec![
+                "#[derive(Serialize, Deserialize, Debug)]",
+                "struct Item {",
+                "\tid: i32,",
+                "}",
+            ],
+            vec![
+                "#[derive(Serialize, Deserialize, Debug)]",
+                "struct Item2 {",
+                "\tname: String,",
+                "}",
+            ],
+            vec![
+                "#[derive(Serialize, Deserialize, Debug)]",
+                "struct Order {",
+                "\titem: Item2,",
+                "}",
+            ],
+            vec![
+                "#[derive(Serialize, Deserialize, Debug)]",
+                "struct Other {",
+                "\titem: Item,",
+                "}",
+            ],
+            vec![
+                "#[derive(Serialize, Deserialize, Debug)]",
+                "struct Root {",
+                "\titem: Item,",
+                "\torders: Vec<Order>,",
+                "\tother: Other,",
+                "}",
+            ],
+        ];
+
+        let lexer = Lexer::new(json);
+        let tokenizer = Tokenizer::new(lexer.start_lex(), ParseOptions::default());
+        let transformer = Transformer::new(RUST_DEFINITION, tokenizer.start_tokenizer().unwrap(), None).unwrap();
+
+        assert_eq!(transformer.start_transform(), expected_result);
+    }
+
+    #[test]
+    fn declaration_order() {
+        let json = "{\"user\": {\"address\": {\"city\": \"x\"}, \"name\": \"y\"}, \"tags\": [{\"id\": 1}]}";
+
+        let lexer = Lexer::new(json);
+        let tokenizer = Tokenizer::new(lexer.start_lex(), ParseOptions::default());
+        let mut transformer = Transformer::new(RUST_DEFINITION, tokenizer.start_tokenizer().unwrap(), None).unwrap();
+        let names: Vec<String> = transformer.start_transform_named().into_iter().map(|(name, _)| name).collect();
+
+        assert_eq!(names, vec!["Address", "User", "Tag", "Root"]);
+    }
+
+    #[test]
+    fn flatten() {
+        let json = "{\"addr\": {\"city\": \"x\", \"geo\": {\"lat\": 1.5}}, \"tags\": [{\"meta\": {\"id\": 1}}]}";
+        let expected_result = vec![
+            vec![
+                "#[derive(Serialize, Deserialize, Debug)]",
+                "struct Tag {",
+                "\tmeta_id: i32,",
+                "}",
+            ],
+            vec![
+                "#[derive(Serialize, Deserialize, Debug)]",
+                "struct Root {",
+                "\taddr_city: String,",
+                "\taddr_geo_lat: f32,",
+                "\ttags: Vec<Tag>,",
+                "}",
+            ],
+        ];
+
+        let mut config = RUST_DEFINITION;
+        config.options.flatten = true;
+
+        let lexer = Lexer::new(json);
+        let tokenizer = Tokenizer::new(lexer.start_lex(), ParseOptions::default());
+        let transformer = Transformer::new(config, tokenizer.start_tokenizer().unwrap(), None).unwrap();
+        let result = transformer.start_transform();
+
+        assert_eq!(result, expected_result);
+    }
+
+    #[test]
+    fn mixed_array_fallback() {
+        let json = "{\"f1\": [{\"a\": 1}, \"error\"]}";
+        let expected_result = vec![
+            vec![
+                "#[derive(Serialize, Deserialize, Debug)]",
+                "struct Root {",
+                "\tf1: Vec<serde_json::Value>,",
+                "}",
+            ],
+        ];
+
+        let lexer = Lexer::new(json);
+        let tokenizer = Tokenizer::new(lexer.start_lex(), ParseOptions { fallback: true, ..ParseOptions::default() });
+        let transformer = Transformer::new(RUST_DEFINITION, tokenizer.start_tokenizer().unwrap(), None).unwrap();
+        let result = transformer.start_transform();
+
+        assert_eq!(result, expected_result);
+    }
+
+    #[test]
+    fn scalar_and_array_fallback() {
+        let json = "{\"f1\": [1, [2, 3]]}";
+        let expected_result = vec![
+            vec![
+                "#[derive(Serialize, Deserialize, Debug)]",
+                "struct Root {",
+                "\tf1: Vec<serde_json::Value>,",
+                "}",
+            ],
+        ];
+
+        let lexer = Lexer::new(json);
+        let tokenizer = Tokenizer::new(lexer.start_lex(), ParseOptions { fallback: true, ..ParseOptions::default() });
+        let transformer = Transformer::new(RUST_DEFINITION, tokenizer.start_tokenizer().unwrap(), None).unwrap();
+        let result = transformer.start_transform();
+
+        assert_eq!(result, expected_result);
+
+        let lexer = Lexer::new(json);
+        let tokenizer = Tokenizer::new(lexer.start_lex(), ParseOptions::default());
+        assert!(tokenizer.start_tokenizer().is_err());
+    }
+
+    #[test]
+    fn parse_helper() {
+        let json = "{\"f1\": {\"f2\": 1}}";
+        let expected_result = vec![
+            vec![
+                "#[derive(Serialize, Deserialize, Debug)]",
+                "struct F1 {",
+                "\tf2: i32,",
+                "}",
+            ],
+            vec![
+                "#[derive(Serialize, Deserialize, Debug)]",
+                "struct Root {",
+                "\tf1: F1,",
+                "}",
+                "impl Root {\n\tpub fn from_json(s: &str) -> serde_json::Result<Self> {\n\t\tserde_json::from_str(s)\n\t}\n}",
+            ],
+        ];
+
+        let mut config = RUST_DEFINITION;
+        config.options.parse_helper = true;
+
+        let lexer = Lexer::new(json);
+        let tokenizer = Tokenizer::new(lexer.start_lex(), ParseOptions::default());
+        let transformer = Transformer::new(config, tokenizer.start_tokenizer().unwrap(), None).unwrap();
+        let result = transformer.start_transform();
+
+        assert_eq!(result, expected_result);
+    }
+
+    #[test]
+    fn bool_strings() {
+        let json = "{\"f1\": [{\"active\": \"yes\", \"f2\": \"maybe\"}, {\"active\": \"no\", \"f2\": \"no\"}]}";
+        let expected_result = vec![
+            vec![
+                "#[derive(Serialize, Deserialize, Debug)]",
+                "struct F1 {",
+                "\t#[serde(default, deserialize_with = \"bool_from_string\")]",
+                "\tactive: bool,",
+                "\tf2: String,",
+                "}",
+            ],
+            vec![
+                "#[derive(Serialize, Deserialize, Debug)]",
+                "struct Root {",
+                "\tf1: Vec<F1>,",
+                "}",
+            ],
+        ];
+
+        let mut config = RUST_DEFINITION;
+        config.options.bool_strings = true;
+
+        let lexer = Lexer::new(json);
+        let tokenizer = Tokenizer::new(lexer.start_lex(), ParseOptions::default());
+        let transformer = Transformer::new(config, tokenizer.start_tokenizer().unwrap(), None).unwrap();
+        let result = transformer.start_transform();
+
+        assert_eq!(result[..2], expected_result);
+        // The helper the annotation calls comes once, after the types.
+        assert_eq!(result.len(), 3);
+        assert!(result[2][0].starts_with("fn bool_from_string<"));
+    }
+
+    #[test]
+    fn bool_strings_without_annotation() {
+        let json = "{\"active\": \"yes\", \"flags\": [\"on\", \"off\"]}";
+
+        let transform = |mut config: TransformConfig| {
+            config.options.bool_strings = true;
+            let lexer = Lexer::new(json);
+            let tokenizer = Tokenizer::new(lexer.start_lex(), ParseOptions::default());
+            Transformer::new(config, tokenizer.start_tokenizer().unwrap(), None).unwrap().start_transform()
+        };
+
+        assert_eq!(transform(TYPESCRIPT_DEFINITION)[0][1], "\tactive: string;");
+        assert_eq!(transform(RUST_DEFINITION)[0][4], "\tflags: Vec<String>,");
+    }
+
+    #[test]
+    fn empty_strings_as_null() {
+        let json = "[{\"name\": \"a\", \"note\": \"\"}, {\"name\": \"\", \"note\": \"\"}]";
+        let expected_result = vec![
+            vec![
+                "#[derive(Serialize, Deserialize, Debug)]",
+                "struct RootItem {",
+                "\tname: String,",
+                "\tnote: Option<String>,",
+                "}",
+            ],
+            vec!["type Root = Vec<RootItem>;"],
+        ];
+
+        let mut config = RUST_DEFINITION;
+        config.options.empty_strings_as_null = true;
+
+        let lexer = Lexer::new(json);
+        let tokenizer = Tokenizer::new(lexer.start_lex(), ParseOptions::default());
+        let transformer = Transformer::new(config, tokenizer.start_tokenizer().unwrap(), None).unwrap();
+        let result = transformer.start_transform();
+
+        assert_eq!(result, expected_result);
+    }
+
+    #[test]
+    fn decimal_strings() {
+        let json = "{\"items\": [{\"price\": \"19.99\", \"discount\": \"N/A\", \"tax\": \"1.5\"}, {\"price\": \"$5\", \"discount\": \"0.5\", \"tax\": \"0.25\"}]}";
+        let expected_result = vec![
+            vec![
+                "#[derive(Serialize, Deserialize, Debug)]",
+                "struct Item {",
+                "\t#[serde(default, deserialize_with = \"decimal_from_string\")]",
+                "\tprice: rust_decimal::Decimal,",
+                "\tdiscount: String,",
+                "\ttax: rust_decimal::Decimal,",
+                "}",
+            ],
+            vec![
+                "#[derive(Serialize, Deserialize, Debug)]",
+                "struct Root {",
+                "\titems: Vec<Item>,",
+                "}",
+            ],
+        ];
+
+        let mut config = RUST_DEFINITION;
+        config.options.decimal_strings = true;
+
+        let lexer = Lexer::new(json);
+        let tokenizer = Tokenizer::new(lexer.start_lex(), ParseOptions::default());
+        let transformer = Transformer::new(config, tokenizer.start_tokenizer().unwrap(), None).unwrap();
+        let result = transformer.start_transform();
+
+        assert_eq!(result[..2], expected_result);
+        // The helper stripping the currency symbol comes once, after the types.
+        assert_eq!(result.len(), 3);
+        assert!(result[2][0].starts_with("fn decimal_from_string<"));
+    }
+
+    #[test]
+    fn decimal_strings_without_annotation() {
+        let json = "{\"price\": \"$19.99\", \"tax\": \"1.5\"}";
+        let expected_result = vec![
+            vec![
+                "#[derive(Serialize, Deserialize, Debug)]",
+                "struct Root {",
+                "\tprice: String,",
+                "\ttax: rust_decimal::Decimal,",
+                "}",
+            ],
+        ];
+
+        let mut config = RUST_DEFINITION;
+        config.options.decimal_strings = true;
+        config.decimal_string_annotation = None;
+
+        let lexer = Lexer::new(json);
+        let tokenizer = Tokenizer::new(lexer.start_lex(), ParseOptions::default());
+        let transformer = Transformer::new(config, tokenizer.start_tokenizer().unwrap(), None).unwrap();
+        let result = transformer.start_transform();
+
+        assert_eq!(result, expected_result);
+    }
+
+    #[test]
+    fn base64_bytes() {
+        let json = "{\"data_b64\": \"SGVsbG8sIGJhc2U2NCBlbmNvZGVkIHdvcmxkIQ==\", \"word\": \"Hello\"}";
+        let expected_result = vec![
+            vec![
+                "#[derive(Serialize, Deserialize, Debug)]",
+                "struct Root {",
+                "\t#[serde(with = \"base64\")]",
+                "\tdata_b64: Vec<u8>,",
+                "\tword: String,",
+                "}",
+            ],
+        ];
+
+        let mut config = RUST_DEFINITION;
+        config.options.base64_bytes = true;
+
+        let lexer = Lexer::new(json);
+        let tokenizer = Tokenizer::new(lexer.start_lex(), ParseOptions::default());
+        let transformer = Transformer::new(config, tokenizer.start_tokenizer().unwrap(), None).unwrap();
+        let result = transformer.start_transform();
+
+        assert_eq!(result, expected_result);
+    }
+
+    #[test]
+    fn constant_enums() {
+        let json = "{\"items\": [{\"type\": \"user\", \"id\": 1}, {\"type\": \"user\", \"id\": 2}], \"name\": \"x\"}";
+        let expected_result = vec![
+            vec![
+                "#[derive(Serialize, Deserialize, Debug)]\nenum Type {",
+                "\t#[serde(rename = \"user\")]\n\tUser,",
+                "}",
+            ],
+            vec![
+                "#[derive(Serialize, Deserialize, Debug)]",
+                "struct Item {",
+                "\ttype: Type,",
+                "\tid: i32,",
+                "}",
+            ],
+            vec![
+                "#[derive(Serialize, Deserialize, Debug)]",
+                "struct Root {",
+                "\titems: Vec<Item>,",
+                "\tname: String,",
+                "}",
+            ],
+        ];
+
+        let mut config = RUST_DEFINITION;
+        config.options.constant_enums = true;
+
+        let lexer = Lexer::new(json);
+        let tokenizer = Tokenizer::new(lexer.start_lex(), ParseOptions::default());
+        let transformer = Transformer::new(config, tokenizer.start_tokenizer().unwrap(), None).unwrap();
+        let result = transformer.start_transform();
+
+        assert_eq!(result, expected_result);
+    }
+
+    #[test]
+    fn value_conversions() {
+        let json = "{\"userId\": 1, \"name\": \"x\"}";
+        let expected_result = vec![
+            vec![
+                "#[derive(Serialize, Deserialize, Debug)]",
+                "struct Root {",
+                "\t#[serde(rename = \"userId\")]",
+                "\tuser_id: i32,",
+                "\tname: String,",
+                "}",
+                "impl TryFrom<serde_json::Value> for Root {\n\ttype Error = serde_json::Error;\n\n\tfn try_from(value: serde_json::Value) -> Result<Self, Self::Error> {\n\t\tOk(Self {",
+                "\t\t\tuser_id: serde_json::from_value(value[\"userId\"].clone())?,",
+                "\t\t\tname: serde_json::from_value(value[\"name\"].clone())?,",
+                "\t\t})\n\t}\n}",
+            ],
+        ];
+
+        let mut config = RUST_DEFINITION;
+        config.options.value_conversions = true;
+
+        let lexer = Lexer::new(json);
+        let tokenizer = Tokenizer::new(lexer.start_lex(), ParseOptions::default());
+        let transformer = Transformer::new(config, tokenizer.start_tokenizer().unwrap(), None).unwrap();
+        let result = transformer.start_transform();
+
+        assert_eq!(result, expected_result);
+    }
+
+    #[test]
+    fn typescript_readonly_optional() {
+        let json = "{\"user_id\": 1, \"x\": \"a\"}{\"user_id\": 2}";
+        let expected_result = vec![
+            vec![
+                "export interface Root {",
+                "\treadonly user_id: number;",
+                "\treadonly x?: string;",
+                "}",
+            ],
+        ];
+
+        let mut config = TYPESCRIPT_DEFINITION;
+        config.options.readonly = true;
+
+        let lexer = Lexer::new(json);
+        let tokenizer = Tokenizer::new(lexer.start_lex(), ParseOptions { concatenated: true, ..ParseOptions::default() });
+        let transformer = Transformer::new(config, tokenizer.start_tokenizer().unwrap(), None).unwrap();
+        let result = transformer.start_transform();
+
+        assert_eq!(result, expected_result);
+    }
+
+    #[test]
+    fn php_class() {
+        let json = "{\"user_id\": 1, \"score\": 1.5, \"active\": true, \"tags\": [\"a\"], \"name\": \"x\"}{\"user_id\": 2, \"score\": 2.5, \"active\": false, \"tags\": [\"b\"]}";
+        let expected_result = vec![
+            vec![
+                "class Root {",
+                "\t#[SerializedName('user_id')]",
+                "\tpublic int $userId;",
+                "\tpublic float $score;",
+                "\tpublic bool $active;",
+                "\tpublic array $tags;",
+                "\tpublic ?string $name = null;",
+                "}",
+            ],
+        ];
+
+        let lexer = Lexer::new(json);
+        let tokenizer = Tokenizer::new(lexer.start_lex(), ParseOptions { concatenated: true, ..ParseOptions::default() });
+        let transformer = Transformer::new(PHP_DEFINITION, tokenizer.start_tokenizer().unwrap(), None).unwrap();
+        let result = transformer.start_transform();
+
+        assert_eq!(result, expected_result);
+    }
+
+    #[test]
+    fn php_readonly_nullable() {
+        let json = "{\"a\": 1, \"b\": null, \"c\": 2}{\"a\": 2, \"b\": null}";
+        let expected_result = vec![
+            vec![
+                "class Root {",
+                "\tpublic readonly int $a;",
+                "\tpublic readonly mixed $b;",
+                "\tpublic readonly ?int $c;",
+                "}",
+            ],
+        ];
+
+        let mut config = PHP_DEFINITION;
+        config.options.readonly = true;
+
+        let lexer = Lexer::new(json);
+        let options = ParseOptions { concatenated: true, null_fields: true, ..ParseOptions::default() };
+        let tokenizer = Tokenizer::new(lexer.start_lex(), options);
+        let transformer = Transformer::new(config, tokenizer.start_tokenizer().unwrap(), None).unwrap();
+        let result = transformer.start_transform();
+
+        assert_eq!(result, expected_result);
+
+        let expected_result = vec![
+            vec![
+                "class Root {",
+                "\tpublic int $a;",
+                "\tpublic mixed $b = null;",
+                "\tpublic ?int $c = null;",
+                "}",
+            ],
+        ];
+
+        let lexer = Lexer::new(json);
+        let options = ParseOptions { concatenated: true, null_fields: true, ..ParseOptions::default() };
+        let tokenizer = Tokenizer::new(lexer.start_lex(), options);
+        let transformer = Transformer::new(PHP_DEFINITION, tokenizer.start_tokenizer().unwrap(), None).unwrap();
+        let result = transformer.start_transform();
+
+        assert_eq!(result, expected_result);
+    }
+
+    #[test]
+    fn kotlin_serializable() {
+        let json = "{\"user_id\": 1, \"score\": 1.5, \"tags\": [\"a\"], \"name\": \"x\"}{\"user_id\": 2, \"score\": 2.5, \"tags\": [\"b\"]}";
+        let expected_result = vec![
+            vec![
+                "@Serializable",
+                "data class Root (",
+                "\t@SerialName(\"user_id\")",
+                "\tval userId: Long,",
+                "\tval score: Double,",
+                "\tval tags: List<String>,",
+                "\tval name: String? = null,",
+                ")",
+            ],
+        ];
+
+        let lexer = Lexer::new(json);
+        let tokenizer = Tokenizer::new(lexer.start_lex(), ParseOptions { concatenated: true, ..ParseOptions::default() });
+        let transformer = Transformer::new(KOTLIN_SERIALIZABLE_DEFINITION, tokenizer.start_tokenizer().unwrap(), None).unwrap();
+        let result = transformer.start_transform();
+
+        assert_eq!(result, expected_result);
+    }
+
+    #[test]
+    fn discriminated_array() {
+        let json = "[{\"type\": \"a\", \"x\": 1}, {\"type\": \"b\", \"y\": 2}]";
+        let expected_result = vec![
+            vec![
+                "data class A (",
+                "\tval x: int,",
+                ") : RootItem",
+            ],
+            vec![
+                "data class B (",
+                "\tval y: int,",
+                ") : RootItem",
+            ],
+            vec![
+                "sealed interface RootItem",
+            ],
+            vec![
+                "typealias Root = RootItem[]",
+            ],
+        ];
+
+        let lexer = Lexer::new(json);
+        let options = ParseOptions { discriminator: Some("type".to_owned()), ..ParseOptions::default() };
+        let tokenizer = Tokenizer::new(lexer.start_lex(), options);
+        let transformer = Transformer::new(KOTLIN_DEFINITION, tokenizer.start_tokenizer().unwrap(), None).unwrap();
+        let result = transformer.start_transform();
+
+        assert_eq!(result, expected_result);
+    }
+
+    #[test]
+    fn root_discriminated_array_alias() {
+        let json = "[{\"type\": \"a\", \"x\": 1}, {\"type\": \"b\"}]";
+        let expected_result = vec![
+            vec![
+                "#[derive(Serialize, Deserialize, Debug)]",
+                "struct A {",
+                "\tx: i32,",
+                "}",
+            ],
+            vec![
+                "#[derive(Serialize, Deserialize, Debug)]",
+                "struct B {",
+                "}",
+            ],
+            vec![
+                "#[derive(Serialize, Deserialize, Debug)]\n#[serde(tag = \"type\")]\nenum Thing {",
+                "\t#[serde(rename = \"a\")]\n\tA(A),",
+                "\t#[serde(rename = \"b\")]\n\tB(B),",
+                "}",
+            ],
+            vec![
+                "type Root = Vec<Thing>;",
+            ],
+        ];
+
+        let mut config = RUST_DEFINITION;
+        config.options.item_name = Some("Thing".to_owned());
+
+        let lexer = Lexer::new(json);
+        let options = ParseOptions { discriminator: Some("type".to_owned()), ..ParseOptions::default() };
+        let tokenizer = Tokenizer::new(lexer.start_lex(), options);
+        let transformer = Transformer::new(config, tokenizer.start_tokenizer().unwrap(), None).unwrap();
+        let result = transformer.start_transform();
+
+        assert_eq!(result, expected_result);
+    }
+
+    #[test]
+    fn discriminated_array_tagged_enum() {
+        let json = "{\"events\": [{\"type\": \"click\", \"x\": 1}, {\"type\": \"key-press\", \"key\": \"a\"}]}";
+        let expected_result = vec![
+            vec![
+                "#[derive(Serialize, Deserialize, Debug)]",
+                "struct Click {",
+                "\tx: i32,",
+                "}",
+            ],
+            vec![
+                "#[derive(Serialize, Deserialize, Debug)]",
+                "struct KeyPress {",
+                "\tkey: String,",
+                "}",
+            ],
+            vec![
+                "#[derive(Serialize, Deserialize, Debug)]\n#[serde(tag = \"type\")]\nenum Event {",
+                "\t#[serde(rename = \"click\")]\n\tClick(Click),",
+                "\t#[serde(rename = \"key-press\")]\n\tKeyPress(KeyPress),",
+                "}",
+            ],
+            vec![
+                "#[derive(Serialize, Deserialize, Debug)]",
+                "struct Root {",
+                "\tevents: Vec<Event>,",
+                "}",
+            ],
+        ];
+
+        let lexer = Lexer::new(json);
+        let options = ParseOptions { discriminator: Some("type".to_owned()), ..ParseOptions::default() };
+        let tokenizer = Tokenizer::new(lexer.start_lex(), options);
+        let transformer = Transformer::new(RUST_DEFINITION, tokenizer.start_tokenizer().unwrap(), None).unwrap();
+        let result = transformer.start_transform();
+
+        assert_eq!(result, expected_result);
+    }
+
+    #[test]
+    fn concatenated_documents() {
+        let json = "{\"a\": 1}{\"a\": 1, \"b\": 2}";
+        let expected_result = vec![
+            vec![
+                "#[derive(Serialize, Deserialize, Debug)]",
+                "struct Root {",
+                "\ta: i32,",
+                "\tb: Option<i32>,",
+                "}",
+            ],
+        ];
+
+        let lexer = Lexer::new(json);
+        let tokenizer = Tokenizer::new(lexer.start_lex(), ParseOptions { concatenated: true, ..ParseOptions::default() });
+        let transformer = Transformer::new(RUST_DEFINITION, tokenizer.start_tokenizer().unwrap(), None).unwrap();
+        let result = transformer.start_transform();
+
+        assert_eq!(result, expected_result);
+    }
+
+    #[test]
+    fn null_elements() {
+        let json = "{\"f1\": [1, null, 2], \"f2\": [[null, \"a\"]]}";
+        let expected_result = vec![
+            vec![
+                "#[derive(Serialize, Deserialize, Debug)]",
+                "struct Root {",
+                "\tf1: Vec<Option<i32>>,",
+                "\tf2: Vec<Vec<Option<String>>>,",
+                "}",
+            ],
+        ];
+
+        let lexer = Lexer::new(json);
+        let options = ParseOptions { null_elements: true, ..ParseOptions::default() };
+        let tokenizer = Tokenizer::new(lexer.start_lex(), options);
+        let transformer = Transformer::new(RUST_DEFINITION, tokenizer.start_tokenizer().unwrap(), None).unwrap();
+        let result = transformer.start_transform();
+
+        assert_eq!(result, expected_result);
+    }
+
+    #[test]
+    fn null_fields() {
+        let json = "{\"f1\": [{\"a\": null, \"b\": null}, {\"a\": 5, \"b\": null}]}";
+        let expected_result = vec![
+            vec![
+                "#[derive(Serialize, Deserialize, Debug)]",
+                "struct F1 {",
+                "\ta: Option<i32>,",
+                "\tb: Option<serde_json::Value>,",
+                "}",
+            ],
+            vec![
+                "#[derive(Serialize, Deserialize, Debug)]",
+                "struct Root {",
+                "\tf1: Vec<F1>,",
+                "}",
+            ],
+        ];
+
+        let lexer = Lexer::new(json);
+        let options = ParseOptions { null_fields: true, ..ParseOptions::default() };
+        let tokenizer = Tokenizer::new(lexer.start_lex(), options);
+        let transformer = Transformer::new(RUST_DEFINITION, tokenizer.start_tokenizer().unwrap(), None).unwrap();
+        let result = transformer.start_transform();
+
+        assert_eq!(result, expected_result);
+    }
+
+    #[test]
+    fn null_field_beside_value() {
+        let json = "{\"name\": \"x\", \"nick\": null}";
+        let expected_result = vec![
+            vec![
+                "#[derive(Serialize, Deserialize, Debug)]",
+                "struct Root {",
+                "\tname: String,",
+                "\tnick: Option<serde_json::Value>,",
+                "}",
+            ],
+        ];
+
+        let lexer = Lexer::new(json);
+        let options = ParseOptions { null_fields: true, ..ParseOptions::default() };
+        let tokenizer = Tokenizer::new(lexer.start_lex(), options);
+        let transformer = Transformer::new(RUST_DEFINITION, tokenizer.start_tokenizer().unwrap(), None).unwrap();
+        let result = transformer.start_transform();
+
+        assert_eq!(result, expected_result);
+    }
+
+    #[test]
+    fn fixed_arrays() {
+        let json = "{\"f1\": [{\"position\": [1.5, 2.5, 3.5]}, {\"position\": [4.5, 5.5, 6.5]}, {\"position\": [7.5, 8.5, 9.5]}, {\"position\": [0.5, 0.5, 0.5, 0.5]}]}";
+        let expected_result = vec![
+            vec![
+                "#[derive(Serialize, Deserialize, Debug)]",
+                "struct F1 {",
+                "\tposition: Vec<f32>,",
+                "}",
+            ],
+            vec![
+                "#[derive(Serialize, Deserialize, Debug)]",
+                "struct Root {",
+                "\tf1: [F1; 4],",
+                "}",
+            ],
+        ];
+
+        let lexer = Lexer::new(json);
+        let options = ParseOptions { fixed_arrays: true, ..ParseOptions::default() };
+        let tokenizer = Tokenizer::new(lexer.start_lex(), options);
+        let transformer = Transformer::new(RUST_DEFINITION, tokenizer.start_tokenizer().unwrap(), None).unwrap();
+        let result = transformer.start_transform();
+
+        assert_eq!(result, expected_result);
+
+        let json = "{\"position\": [1.5, 2.5, 3.5]}";
+        let lexer = Lexer::new(json);
+        let options = ParseOptions { fixed_arrays: true, ..ParseOptions::default() };
+        let tokenizer = Tokenizer::new(lexer.start_lex(), options);
+        let transformer = Transformer::new(RUST_DEFINITION, tokenizer.start_tokenizer().unwrap(), None).unwrap();
+        let result = transformer.start_transform();
+
+        assert_eq!(result[0][2], "\tposition: [f32; 3],");
+    }
+
+    #[test]
+    fn max_types() {
+        let fields: Vec<String> = (0..20).map(|i| format!("\"f{}\": {{\"g{}\": 1}}", i, i)).collect();
+        let json = format!("{{{}}}", fields.join(", "));
+
+        let lexer = Lexer::new(&json);
+        let tokenizer = Tokenizer::new(lexer.start_lex(), ParseOptions::default());
+        let mut config = RUST_DEFINITION;
+        config.options.max_types = Some(5);
+        let mut transformer = Transformer::new(config, tokenizer.start_tokenizer().unwrap(), None).unwrap();
+        let result = transformer.start_transform_named();
+        let (_, root) = result.last().unwrap();
+
+        assert_eq!(transformer.warnings(), ["the maximum number of types was reached, 16 more were given the unknown type"]);
+
+        assert_eq!(result.len(), 5);
+        assert_eq!(root[5], "\tf3: F3,");
+        assert_eq!(root[6], "\tf4: serde_json::Value,");
+        assert_eq!(root[21], "\tf19: serde_json::Value,");
+    }
+
+    #[test]
+    fn empty_object_constructor() {
+        let expected_result = vec![
+            vec![
+                "class Root {",
+                "\tpublic Root() {",
+                "\t}",
+                "}",
+            ],
+        ];
+
+        for separator_at_end in [false, true] {
+            let mut config = JAVA_DEFINITION;
+            if let Some(ref mut constructor) = config.constructor {
+                constructor.separator_at_end = separator_at_end;
+            }
+
+            let transformer = Transformer::new(config, JsonRoot::Object(vec![]), None).unwrap();
+            let result = transformer.start_transform();
+
+            assert_eq!(result, expected_result);
+        }
+    }
+
+    #[test]
+    fn empty_object_dart_constructor() {
+        let expected_result = vec![
+            vec![
+                "class Root {",
+                "\tRoot({\n\t});",
+                "}",
+            ],
+        ];
+
+        let transformer = Transformer::new(DART_DEFINITION, JsonRoot::Object(vec![]), None).unwrap();
+        let result = transformer.start_transform();
+
+        assert_eq!(result, expected_result);
+
+        let json = "{\"f1\": 1, \"f2\": true}";
+        let lexer = Lexer::new(json);
+        let tokenizer = Tokenizer::new(lexer.start_lex(), ParseOptions::default());
+        let transformer = Transformer::new(DART_DEFINITION, tokenizer.start_tokenizer().unwrap(), None).unwrap();
+        let result = transformer.start_transform();
+
+        assert_eq!(result[0][3], "\tRoot({\n\t\tthis.f1,\n\t\tthis.f2,\n\t});");
+    }
+
+    #[test]
+    fn allow_dead_code() {
+        let json = "{\"f1\": {\"f2\": \"a\"}, \"f3\": [{\"f2\": \"a\"}, {\"f2\": \"a\"}]}";
+        let expected_result = vec![
+            vec![
+                "#[allow(dead_code)]",
+                "#[derive(Serialize, Deserialize, Debug)]",
+                "struct F1 {",
+                "\tf2: String,",
+                "}",
+            ],
+            vec![
+                "#[allow(dead_code)]",
+                "#[derive(Serialize, Deserialize, Debug)]\nenum F2 {",
+                "\t#[serde(rename = \"a\")]\n\tA,",
+                "}",
+            ],
+            vec![
+                "#[allow(dead_code)]",
+                "#[derive(Serialize, Deserialize, Debug)]",
+                "struct F3 {",
+                "\tf2: F2,",
+                "}",
+            ],
+            vec![
+                "#[allow(dead_code)]",
+                "#[derive(Serialize, Deserialize, Debug)]",
+                "struct Root {",
+                "\tf1: F1,",
+                "\tf3: Vec<F3>,",
+                "}",
+            ],
+        ];
+
+        let mut config = RUST_DEFINITION;
+        config.options.allow_dead_code = true;
+        config.options.constant_enums = true;
+
+        let lexer = Lexer::new(json);
+        let tokenizer = Tokenizer::new(lexer.start_lex(), ParseOptions::default());
+        let transformer = Transformer::new(config, tokenizer.start_tokenizer().unwrap(), None).unwrap();
+        let result = transformer.start_transform();
+
+        assert_eq!(result, expected_result);
+    }
+
+    struct ColorInference;
+
+    impl TypeInference for ColorInference {
+        fn classify_string(&self, value: &str) -> InferredType {
+            if value.len() == 7 && value.starts_with('#') && value[1..].chars().all(|char| char.is_ascii_hexdigit()) {
+                InferredType::Named("Color".to_owned())
+            } else {
+                InferredType::Plain
+            }
+        }
+    }
+
+    #[test]
+    fn custom_inference() {
+        let json = "{\"f1\": [\"#ff0000\", \"#00ff00\"], \"f2\": [\"#ff0000\", \"red\"], \"f3\": \"#0000ff\"}";
+        let expected_result = vec![
+            vec![
+                "#[derive(Serialize, Deserialize, Debug)]",
+                "struct Root {",
+                "\tf1: Vec<Color>,",
+                "\tf2: Vec<String>,",
+                "\tf3: Color,",
+                "}",
+            ],
+        ];
+
+        let lexer = Lexer::new(json);
+        let tokenizer = Tokenizer::new(lexer.start_lex(), ParseOptions::default());
+        let transformer = Transformer::with_inference(RUST_DEFINITION, tokenizer.start_tokenizer().unwrap(), None, Box::new(ColorInference)).unwrap();
+        let result = transformer.start_transform();
+
+        assert_eq!(result, expected_result);
+    }
+
+    #[test]
+    fn builder() {
+        let json = "{\"f1\": 1, \"f2\": {\"f3\": \"a\"}}";
+        let expected_result = vec![
+            vec![
+                "#[derive(Default, Builder)]\n#[builder(default)]",
+                "#[derive(Serialize, Deserialize, Debug)]",
+                "struct F2 {",
+                "\tpub f3: String,",
+                "}",
+            ],
+            vec![
+                "#[derive(Default, Builder)]\n#[builder(default)]",
+                "#[derive(Serialize, Deserialize, Debug)]",
+                "struct Root {",
+                "\tpub f1: i32,",
+                "\tpub f2: F2,",
+                "}",
+            ],
+        ];
+
+        let mut config = RUST_DEFINITION;
+        config.options.builder = true;
+
+        let lexer = Lexer::new(json);
+        let tokenizer = Tokenizer::new(lexer.start_lex(), ParseOptions::default());
+        let transformer = Transformer::new(config, tokenizer.start_tokenizer().unwrap(), None).unwrap();
+        let result = transformer.start_transform();
+
+        assert_eq!(result, expected_result);
+    }
+
+    #[test]
+    fn integer_maps() {
+        let json = "{\"f1\": {\"100\": \"a\", \"250\": \"b\"}}";
+        let expected_result = vec![
+            vec![
+                "#[derive(Serialize, Deserialize, Debug)]",
+                "struct Root {",
+                "\tf1: HashMap<i32, String>,",
+                "}",
+            ],
+        ];
+
+        let lexer = Lexer::new(json);
+        let options = ParseOptions { integer_maps: true, ..ParseOptions::default() };
+        let tokenizer = Tokenizer::new(lexer.start_lex(), options);
+        let transformer = Transformer::new(RUST_DEFINITION, tokenizer.start_tokenizer().unwrap(), None).unwrap();
+        let result = transformer.start_transform();
+
+        assert_eq!(result, expected_result);
+    }
+
+    #[test]
+    fn streamed_output() {
+        let json = "{\"f1\": {\"f2\": 1}, \"f3\": [{\"f4\": true}]}";
+        let expected_result = "\
+#[derive(Serialize, Deserialize, Debug)]
+struct F1 {
+\tf2: i32,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct F3 {
+\tf4: bool,
+}
+
+#[serde(deny_unknown_fields)]
+#[derive(Serialize, Deserialize, Debug)]
+struct Root {
+\tf1: F1,
+\tf3: Vec<F3>,
+}
+";
+
+        let mut config = RUST_DEFINITION;
+        config.root_attributes = vec!["#[serde(deny_unknown_fields)]".to_owned()];
+
+        let lexer = Lexer::new(json);
+        let tokenizer = Tokenizer::new(lexer.start_lex(), ParseOptions::default());
+        let mut transformer = Transformer::new(config, tokenizer.start_tokenizer().unwrap(), None).unwrap();
+        let mut output = Vec::new();
+        transformer.start_transform_to(&mut output).unwrap();
+
+        assert_eq!(String::from_utf8(output).unwrap(), expected_result);
+
+        let mut config = RUST_DEFINITION;
+        config.root_attributes = vec!["#[serde(deny_unknown_fields)]".to_owned()];
+
+        let lexer = Lexer::new(json);
+        let tokenizer = Tokenizer::new(lexer.start_lex(), ParseOptions::default());
+        let transformer = Transformer::new(config, tokenizer.start_tokenizer().unwrap(), None).unwrap();
+
+        assert_eq!(transformer.transform_to_string(), expected_result);
+    }
+
+    #[test]
+    fn field_sources() {
+        let a = "{\"f1\": 1}";
+        let b = "{\"f1\": 2, \"f2\": {\"f3\": true}}";
+        let expected_result = vec![
+            vec![
+                "#[derive(Serialize, Deserialize, Debug)]",
+                "struct F2 {",
+                "\t// from: b.json",
+                "\tf3: bool,",
+                "}",
+            ],
+            vec![
+                "#[derive(Serialize, Deserialize, Debug)]",
+                "struct Root {",
+                "\t// from: a.json, b.json",
+                "\tf1: i32,",
+                "\t// from: b.json",
+                "\tf2: Option<F2>,",
+                "}",
+            ],
+        ];
+
+        let mut sources = FieldSources::new();
+        let mut roots = Vec::new();
+        for (source, json) in [("a.json", a), ("b.json", b)] {
+            let tree = Tokenizer::new(Lexer::new(json).start_lex(), ParseOptions::default()).start_tokenizer().unwrap();
+            add_sources(&mut sources, &tree, source);
+            if let JsonRoot::Object(fields) = tree {
+                roots.push(fields);
+            }
+        }
+        let new_root = roots.pop().unwrap();
+        let mut root = roots.pop().unwrap();
+        Tokenizer::merge_documents(&mut root, new_root);
+
+        let mut config = RUST_DEFINITION;
+        config.options.field_sources = true;
+
+        let transformer = Transformer::new(config, JsonRoot::Object(root), None).unwrap().with_sources(sources);
+        let result = transformer.start_transform();
+
+        assert_eq!(result, expected_result);
+    }
+
+    #[test]
+    fn modules() {
+        let json = "{\"f1\": 1, \"f4\": {\"f5\": [{\"f6\": true}]}}";
+        let expected_result = vec![
+            vec![
+                "mod f4 {\n\tuse super::*;",
+                "\tmod f5 {\n\t\tuse super::*;",
+                "\t\t#[derive(Serialize, Deserialize, Debug)]",
+                "\t\tpub struct F5 {",
+                "\t\t\tf6: bool,",
+                "\t\t}",
+                "\t}",
+                "\t#[derive(Serialize, Deserialize, Debug)]",
+                "\tpub struct F4 {",
+                "\t\tf5: Vec<f5::F5>,",
+                "\t}",
+                "}",
+            ],
+            vec![
+                "#[derive(Serialize, Deserialize, Debug)]",
+                "struct Root {",
+                "\tf1: i32,",
+                "\tf4: f4::F4,",
+                "}",
+            ],
+        ];
+
+        let mut config = RUST_DEFINITION;
+        config.options.modules = true;
+
+        let lexer = Lexer::new(json);
+        let tokenizer = Tokenizer::new(lexer.start_lex(), ParseOptions::default());
+        let transformer = Transformer::new(config, tokenizer.start_tokenizer().unwrap(), None).unwrap();
+        let result = transformer.start_transform();
+
+        assert_eq!(result, expected_result);
+    }
+
+    #[test]
+    fn deny_unknown_fields() {
+        let json = "{\"f1\": {\"f2\": 1}}";
+        let expected_result = vec![
+            vec![
+                "#[derive(Serialize, Deserialize, Debug)]",
+                "#[serde(deny_unknown_fields)]",
+                "struct F1 {",
+                "\tf2: i32,",
+                "}",
+            ],
+            vec![
+                "#[derive(Serialize, Deserialize, Debug)]",
+                "#[serde(deny_unknown_fields)]",
+                "struct Root {",
+                "\tf1: F1,",
+                "}",
+            ],
+        ];
+
+        let mut config = RUST_DEFINITION;
+        config.options.deny_unknown_fields = true;
+
+        let lexer = Lexer::new(json);
+        let tokenizer = Tokenizer::new(lexer.start_lex(), ParseOptions::default());
+        let transformer = Transformer::new(config, tokenizer.start_tokenizer().unwrap(), None).unwrap();
+        let result = transformer.start_transform();
+
+        assert_eq!(result, expected_result);
+    }
+
+    #[test]
+    fn placeholder_like_keys() {
+        let json = "{\"{name}\": 1, \"A{field_type}\": \"a\"}";
+        let expected_result = vec![
+            vec![
+                "#[derive(Serialize, Deserialize, Debug)]",
+                "struct Root {",
+                "\t{name}: i32,",
+                "\t#[serde(rename = \"A{field_type}\")]",
+                "\ta{field_type}: String,",
+                "}",
+            ],
+        ];
+
+        let lexer = Lexer::new(json);
+        let tokenizer = Tokenizer::new(lexer.start_lex(), ParseOptions::default());
+        let transformer = Transformer::new(RUST_DEFINITION, tokenizer.start_tokenizer().unwrap(), None).unwrap();
+        let result = transformer.start_transform();
+
+        assert_eq!(result, expected_result);
+    }
+
+    #[test]
+    fn tuples() {
+        let json = "{\"f1\": [1, \"name\", true], \"f2\": [[1.5, {\"f3\": 1}]]}";
+        let expected_result = vec![
+            vec![
+                "#[derive(Serialize, Deserialize, Debug)]",
+                "struct F2 {",
+                "\tf3: i32,",
+                "}",
+            ],
+            vec![
+                "#[derive(Serialize, Deserialize, Debug)]",
+                "struct Root {",
+                "\tf1: (i32, String, bool),",
+                "\tf2: Vec<(f32, F2)>,",
+                "}",
+            ],
+        ];
+
+        let lexer = Lexer::new(json);
+        let options = ParseOptions { tuples: true, ..ParseOptions::default() };
+        let tokenizer = Tokenizer::new(lexer.start_lex(), options);
+        let transformer = Transformer::new(RUST_DEFINITION, tokenizer.start_tokenizer().unwrap(), None).unwrap();
+        let result = transformer.start_transform();
+
+        assert_eq!(result, expected_result);
+
+        let json = "{\"f1\": [1, \"name\", true]}";
+        let lexer = Lexer::new(json);
+        let options = ParseOptions { tuples: true, ..ParseOptions::default() };
+        let tokenizer = Tokenizer::new(lexer.start_lex(), options);
+        let transformer = Transformer::new(TYPESCRIPT_DEFINITION, tokenizer.start_tokenizer().unwrap(), None).unwrap();
+        let result = transformer.start_transform();
+
+        assert_eq!(result[0][1], "\tf1: [number, string, boolean];");
+    }
+
+    #[test]
+    fn wrapped_constructor_arguments() {
+        let json = "{\"f1\": 1, \"f2\": 2, \"f3\": 3, \"f4\": 4, \"f5\": 5, \"f6\": 6, \"f7\": 7, \"f8\": 8, \"f9\": 9, \"f10\": {\"f11\": 10}}";
+        let mut config = JAVA_DEFINITION;
+        config.options.max_line_width = Some(80);
+
+        let lexer = Lexer::new(json);
+        let tokenizer = Tokenizer::new(lexer.start_lex(), ParseOptions::default());
+        let transformer = Transformer::new(config, tokenizer.start_tokenizer().unwrap(), None).unwrap();
+        let result = transformer.start_transform();
+
+        assert_eq!(result[0][2], "\tpublic F10(int f11) {");
+        assert_eq!(
+            result[1][11],
+            "\tpublic Root(\n\t\tint f1,\n\t\tint f2,\n\t\tint f3,\n\t\tint f4,\n\t\tint f5,\n\t\tint f6,\n\t\tint f7,\n\t\tint f8,\n\t\tint f9,\n\t\tF10 f10\n\t) {",
+        );
+    }
+
+    #[test]
+    fn empty_objects_as_maps() {
+        let json = "{\"meta\": {}, \"items\": [{}], \"user\": {\"id\": 1}}";
+        let expected_result = vec![
+            vec![
+                "#[derive(Serialize, Deserialize, Debug)]",
+                "struct User {",
+                "\tid: i32,",
+                "}",
+            ],
+            vec![
+                "#[derive(Serialize, Deserialize, Debug)]",
+                "struct Root {",
+                "\tmeta: HashMap<String, serde_json::Value>,",
+                "\titems: Vec<HashMap<String, serde_json::Value>>,",
+                "\tuser: User,",
+                "}",
+            ],
+        ];
+
+        let mut config = RUST_DEFINITION;
+        config.options.empty_object_as = EmptyObject::Map;
+
+        let lexer = Lexer::new(json);
+        let tokenizer = Tokenizer::new(lexer.start_lex(), ParseOptions::default());
+        let transformer = Transformer::new(config, tokenizer.start_tokenizer().unwrap(), None).unwrap();
+        let result = transformer.start_transform();
+
+        assert_eq!(result, expected_result);
+    }
+
+    #[test]
+    fn skip_annotations() {
+        let json = "{\"userId\": 1, \"displayName\": \"a\"}";
+        let expected_result = vec![
+            vec![
+                "#[derive(Serialize, Deserialize, Debug)]",
+                "struct Root {",
+                "\tuser_id: i32,",
+                "\tdisplay_name: String,",
+                "}",
+            ],
+        ];
+
+        let mut config = RUST_DEFINITION;
+        config.options.skip_annotations = true;
+
+        let lexer = Lexer::new(json);
+        let tokenizer = Tokenizer::new(lexer.start_lex(), ParseOptions::default());
+        let transformer = Transformer::new(config, tokenizer.start_tokenizer().unwrap(), None).unwrap();
+        let result = transformer.start_transform();
+
+        assert_eq!(result, expected_result);
+    }
+
+    #[test]
+    fn sorted_types() {
+        let json = "[{\"type\": \"a\", \"x\": {\"y\": [{\"z\": 1}]}}, {\"type\": \"b\", \"w\": 2}]";
+
+        let mut config = KOTLIN_DEFINITION;
+        config.options.sort_types = true;
+
+        let lexer = Lexer::new(json);
+        let options = ParseOptions { discriminator: Some("type".to_owned()), ..ParseOptions::default() };
+        let tokenizer = Tokenizer::new(lexer.start_lex(), options);
+        let mut transformer = Transformer::new(config, tokenizer.start_tokenizer().unwrap(), None).unwrap();
+        let result = transformer.start_transform_named();
+        let names: Vec<&str> = result.iter().map(|(name, _)| name.as_str()).collect();
+
+        assert_eq!(names, vec!["Y", "X", "RootItem", "A", "B", "Root"]);
+        for (i, (_, lines)) in result.iter().enumerate() {
+            for (name, _) in &result[i + 1..] {
+                assert!(!references(lines, name), "{} is referenced before its declaration", name);
+            }
+        }
+    }
+
+    #[test]
+    fn types_sorted_by_name() {
+        let json = "{\"zone\": {\"id\": 1}, \"user\": {\"address\": {\"city\": \"a\"}}, \"badge\": {\"level\": 2}}";
+        let transform = |sort_types: bool| {
+            let mut config = RUST_DEFINITION;
+            config.options.sort_types_by_name = true;
+            config.options.sort_types = sort_types;
+
+            let lexer = Lexer::new(json);
+            let tokenizer = Tokenizer::new(lexer.start_lex(), ParseOptions::default());
+            let mut transformer = Transformer::new(config, tokenizer.start_tokenizer().unwrap(), None).unwrap();
+            let result = transformer.start_transform_named();
+            result.into_iter().map(|(name, _)| name).collect::<Vec<String>>()
+        };
+
+        assert_eq!(transform(false), vec!["Address", "Badge", "Root", "User", "Zone"]);
+        assert_eq!(transform(true), vec!["Address", "Badge", "User", "Zone", "Root"]);
+    }
+
+    #[test]
+    fn schema_doc_comments() {
+        let json = r#"{
+            "type": "object",
+            "required": ["user_id", "address"],
+            "properties": {
+                "user_id": {"type": "integer", "description": "Unique identifier.\nNever reused."},
+                "address": {"type": "object", "required": ["zip"], "properties": {"zip": {"type": "string", "description": "Postal code."}}}
+            }
+        }"#;
+        let expected_result = vec![
+            vec![
+                "#[derive(Serialize, Deserialize, Debug)]",
+                "struct Address {",
+                "\t/// Postal code.",
+                "\tzip: String,",
+                "}",
+            ],
+            vec![
+                "#[derive(Serialize, Deserialize, Debug)]",
+                "struct Root {",
+                "\t/// Unique identifier.\n\t/// Never reused.",
+                "\tuser_id: i32,",
+                "\taddress: Address,",
+                "}",
+            ],
+        ];
+
+        let lexer = Lexer::new(json);
+        let (tree, docs) = SchemaReader::new(lexer.start_lex()).start_schema().unwrap();
+        let transformer = Transformer::new(RUST_DEFINITION, tree, None).unwrap().with_docs(docs);
+        let result = transformer.start_transform();
+
+        assert_eq!(result, expected_result);
+    }
+
+    #[test]
+    fn strip_underscores() {
+        let json = "{\"_internal\": {\"_id_\": 1}}";
+        let expected_result = vec![
+            vec![
+                "#[derive(Serialize, Deserialize, Debug)]",
+                "struct Internal {",
+                "\t#[serde(rename = \"_id_\")]",
+                "\tid: i32,",
+                "}",
+            ],
+            vec![
+                "#[derive(Serialize, Deserialize, Debug)]",
+                "struct Root {",
+                "\t#[serde(rename = \"_internal\")]",
+                "\tinternal: Internal,",
+                "}",
+            ],
+        ];
+
+        let mut config = RUST_DEFINITION;
+        config.options.underscores = Underscores::Strip;
+
+        let lexer = Lexer::new(json);
+        let tokenizer = Tokenizer::new(lexer.start_lex(), ParseOptions::default());
+        let transformer = Transformer::new(config, tokenizer.start_tokenizer().unwrap(), None).unwrap();
+        let result = transformer.start_transform();
+
+        assert_eq!(result, expected_result);
+    }
+
+    #[test]
+    fn empty_arrays() {
+        let json = "{\"items\": []}";
+        let expected_results = [
+            (RUST_DEFINITION, vec!["#[derive(Serialize, Deserialize, Debug)]", "struct Root {", "\titems: Vec<serde_json::Value>,", "}"]),
+            (DART_DEFINITION, vec!["class Root {", "\tfinal List<dynamic>? items;", "\tRoot({\n\t\tthis.items,\n\t});", "}"]),
+        ];
+
+        for (config, expected_result) in expected_results {
+            let lexer = Lexer::new(json);
+            let tokenizer = Tokenizer::new(lexer.start_lex(), ParseOptions::default());
+            let transformer = Transformer::new(config, tokenizer.start_tokenizer().unwrap(), None).unwrap();
+
+            assert_eq!(transformer.start_transform(), vec![expected_result]);
+        }
+
+        let mut config = RUST_DEFINITION;
+        config.empty_array_type = Some(Cow::Borrowed("String"));
+        let tree = Tokenizer::new(Lexer::new(json).start_lex(), ParseOptions::default()).start_tokenizer().unwrap();
+        let result = Transformer::new(config, tree, None).unwrap().start_transform();
+
+        assert_eq!(result[0][2], "\titems: Vec<String>,");
+    }
+
+    #[test]
+    fn empty_objects_as_units() {
+        let json = "{\"meta\": {}, \"id\": 1}";
+        let expected_result = vec![
+            vec![
+                "#[derive(Serialize, Deserialize, Debug)]\nstruct Meta;",
+            ],
+            vec![
+                "#[derive(Serialize, Deserialize, Debug)]",
+                "struct Root {",
+                "\tmeta: Meta,",
+                "\tid: i32,",
+                "}",
+            ],
+        ];
+
+        let mut config = RUST_DEFINITION;
+        config.options.empty_object_as = EmptyObject::Unit;
+
+        let lexer = Lexer::new(json);
+        let tokenizer = Tokenizer::new(lexer.start_lex(), ParseOptions::default());
+        let transformer = Transformer::new(config, tokenizer.start_tokenizer().unwrap(), None).unwrap();
+        let result = transformer.start_transform();
+
+        assert_eq!(result, expected_result);
+    }
+
+    #[test]
+    fn ascii_only() {
+        let json = "{\"café\": 1, \"straße\": {\"id\": 2}}";
+        let expected_result = vec![
+            vec![
+                "#[derive(Serialize, Deserialize, Debug)]",
+                "struct Strasse {",
+                "\tid: i32,",
+                "}",
+            ],
+            vec![
+                "#[derive(Serialize, Deserialize, Debug)]",
+                "struct Root {",
+                "\t#[serde(rename = \"café\")]",
+                "\tcafe: i32,",
+                "\t#[serde(rename = \"straße\")]",
+                "\tstrasse: Strasse,",
+                "}",
+            ],
+        ];
+
+        let mut config = RUST_DEFINITION;
+        config.options.ascii_only = true;
+
+        let lexer = Lexer::new(json);
+        let tokenizer = Tokenizer::new(lexer.start_lex(), ParseOptions::default());
+        let transformer = Transformer::new(config, tokenizer.start_tokenizer().unwrap(), None).unwrap();
+
+        assert_eq!(transformer.start_transform(), expected_result);
     }
 
     #[test]
@@ -289,19 +3702,61 @@ mod tests {
     fn fail_on_bad_config() {
         let bad_config = TransformConfig {
             type_definition: Cow::Borrowed("{nn}"),
+            unit_definition: None,
+            type_attributes: vec![],
+            declaration_attributes: vec![],
+            root_attributes: vec![],
+            banner: None,
+            file_header: None,
+            dead_code_attribute: None,
+            deny_unknown_fields_attribute: None,
+            file_extension: None,
+            single_file_extension: None,
+            acronyms: vec![],
+            visibility: None,
+            readonly_modifier: None,
             field_definition: Cow::Borrowed("\t{field_ame}: {field_ype}"),
+            optional_field_definition: None,
+            readonly_optional_field_definition: None,
+            source_comment: None,
+            doc_comment: None,
             name_change_annotation: Cow::Borrowed("a"),
             array_definition: Cow::Borrowed("Vec<{field_type}>"),
+            untyped_arrays: false,
+            fixed_array_definition: None,
+            map_definition: None,
+            tuple_definition: None,
+            alias_definition: None,
+            parse_helper: None,
             block_end: Cow::Borrowed("}"),
             int_type: Cow::Borrowed("i32"),
             float_type: Cow::Borrowed("f32"),
             bool_type: Cow::Borrowed("bool"),
             string_type: Cow::Borrowed("String"),
+            bytes_type: None,
+            bytes_annotation: None,
+            bool_string_annotation: None,
+            bool_string_helper: None,
+            decimal_type: None,
+            decimal_string_annotation: None,
+            decimal_string_helper: None,
+            unknown_type: None,
+            nullable_unknown_type: false,
+            empty_array_type: None,
+            object_map_type: None,
+            optional_definition: None,
             constructor: None,
+            enum_definition: None,
+            conversion: None,
+            sealed_definition: None,
+            builder: None,
+            module_definition: None,
+            nested_definition: None,
             case_type: CaseType::CamelCase,
-            object_case_type: CaseType::UpperCamelCase
+            object_case_type: CaseType::UpperCamelCase,
+            options: TransformOptions::DEFAULT,
         };
 
-        Transformer::new(bad_config, vec![], None).unwrap();
+        Transformer::new(bad_config, JsonRoot::Object(vec![]), None).unwrap();
     }
 }
\ No newline at end of file
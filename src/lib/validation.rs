@@ -0,0 +1,244 @@
+use std::fmt;
+use crate::lib::case::{convert_name, singularize, transliterate};
+use crate::lib::model::transform_config::{CaseType, TransformConfig};
+use crate::lib::model::tree::{JsonArrayType, JsonRoot, JsonTree};
+
+/// How bad a [Diagnostic] is.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Severity {
+    /// The output is generated, but may not be what is expected.
+    Warning,
+    /// The output won't compile.
+    Error,
+}
+
+/// Problem found in a tree before transforming it.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    /// Path of the field, as written in the `path_overrides` option.
+    pub path: String,
+    pub message: String,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let severity = match self.severity {
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        };
+        let path = if self.path.is_empty() { "(root)" } else { &self.path };
+        write!(f, "{}: {}: {}", severity, path, self.message)
+    }
+}
+
+/// Type names given to the objects of the tree, with the path of the first object given each name.
+type TypeNames = Vec<(String, String)>;
+
+/// Looks for problems that would make the generated types fail to compile: duplicate and empty field names,
+/// and objects whose type names collide. Names are compared once converted to the cases of `config`,
+/// so that `userID` and `user_id` collide in snake case.
+/// # Arguments
+/// * `tree` fields of the root object.
+/// * `config` definition the tree is transformed with.
+/// * `name` name of the root object, `Root` if missing.
+/// # Returns
+/// Problems found, in the order of the tree.
+pub fn validate_tree(tree: &[JsonTree], config: &TransformConfig, name: Option<&str>) -> Vec<Diagnostic> {
+    let mut validator = Validator::new(config, name);
+
+    validator.validate_fields(tree, "");
+    validator.diagnostics
+}
+
+/// Same as [validate_tree], for any root.
+pub fn validate_root(root: &JsonRoot, config: &TransformConfig, name: Option<&str>) -> Vec<Diagnostic> {
+    match root {
+        JsonRoot::Object(tree) => validate_tree(tree, config, name),
+        JsonRoot::Array(array_type) => {
+            let mut validator = Validator::new(config, name);
+            let item_name = config.options.item_name.clone().unwrap_or_else(|| format!("{}Item", name.unwrap_or("Root")));
+
+            validator.validate_array(array_type, "[]", &item_name);
+            validator.diagnostics
+        }
+        JsonRoot::Value(_) => vec![],
+    }
+}
+
+/// Walks a tree, converting its names as the transformer does.
+struct Validator<'a> {
+    config: &'a TransformConfig,
+    type_names: TypeNames,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl<'a> Validator<'a> {
+    fn new(config: &'a TransformConfig, name: Option<&str>) -> Self {
+        Self {
+            config,
+            type_names: vec![(String::from(name.unwrap_or("Root")), String::new())],
+            diagnostics: Vec::new(),
+        }
+    }
+
+    /// Name as written in the output, see [convert_name].
+    fn convert_case(&self, name: &str, case_type: &CaseType) -> String {
+        if self.config.options.ascii_only {
+            return convert_name(&transliterate(name), case_type, &self.config.acronyms, &self.config.options.underscores);
+        }
+        convert_name(name, case_type, &self.config.acronyms, &self.config.options.underscores)
+    }
+
+    fn validate_fields(&mut self, fields: &[JsonTree], path: &str) {
+        for (i, field) in fields.iter().enumerate() {
+            let name = field.name();
+            let field_path = if path.is_empty() { name.to_owned() } else { format!("{}.{}", path, name) };
+
+            if name.is_empty() {
+                self.diagnostics.push(Diagnostic {
+                    severity: Severity::Error,
+                    path: field_path,
+                    message: String::from("empty field name"),
+                });
+                continue;
+            }
+
+            // Fields such as `userID` and `user_id` are only told apart before their case is converted.
+            let field_name = self.convert_case(name, &self.config.case_type);
+            let previous = fields[..i].iter()
+                .map(|previous| previous.name())
+                .find(|previous| !previous.is_empty() && self.convert_case(previous, &self.config.case_type) == field_name);
+            match previous {
+                Some(previous) if previous == name => self.diagnostics.push(Diagnostic {
+                    severity: Severity::Error,
+                    path: field_path.clone(),
+                    message: format!("duplicate field {}", name),
+                }),
+                Some(previous) => self.diagnostics.push(Diagnostic {
+                    severity: Severity::Error,
+                    path: field_path.clone(),
+                    message: format!("fields {} and {} are both named {}", previous, name, field_name),
+                }),
+                None => (),
+            }
+
+            match field.required() {
+                JsonTree::JsonObject(_, tree) => {
+                    self.validate_type_name(name, &field_path);
+                    self.validate_fields(tree, &field_path);
+                }
+                JsonTree::JsonArray(_, array_type) => {
+                    self.validate_array(array_type, &format!("{}[]", field_path), &singularize(name));
+                }
+                JsonTree::Map(_, value_type) => {
+                    self.validate_array(value_type, &format!("{}{{}}", field_path), &singularize(name));
+                }
+                _ => (),
+            }
+        }
+    }
+
+    fn validate_array(&mut self, array_type: &JsonArrayType, path: &str, name: &str) {
+        match array_type {
+            JsonArrayType::JsonObject(tree) => {
+                self.validate_type_name(name, path);
+                self.validate_fields(tree, path);
+            }
+            JsonArrayType::JsonArray(array_type) => self.validate_array(array_type, &format!("{}[]", path), name),
+            JsonArrayType::Optional(array_type) | JsonArrayType::Fixed(_, array_type) => {
+                self.validate_array(array_type, path, name)
+            }
+            JsonArrayType::Tagged(_, variants) => {
+                for (value, tree) in variants {
+                    self.validate_type_name(value, path);
+                    self.validate_fields(tree, path);
+                }
+            }
+            JsonArrayType::Tuple(positions) => {
+                for array_type in positions {
+                    self.validate_array(array_type, path, name);
+                }
+            }
+            _ => (),
+        }
+    }
+
+    /// Reports an object whose type name was already given to another object.
+    fn validate_type_name(&mut self, name: &str, path: &str) {
+        if name.is_empty() {
+            return;
+        }
+
+        let type_name = self.convert_case(name, &self.config.object_case_type);
+        match self.type_names.iter().find(|(other_name, _)| *other_name == type_name) {
+            Some((_, other_path)) => self.diagnostics.push(Diagnostic {
+                severity: Severity::Warning,
+                path: path.to_owned(),
+                message: format!("type name {} is already given to {}, a number will be appended unless both have the same fields", type_name, if other_path.is_empty() { "(root)" } else { other_path }),
+            }),
+            None => self.type_names.push((type_name, path.to_owned())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::lib::model::transform_config::{JAVA_DEFINITION, RUST_DEFINITION};
+    use crate::lib::model::tree::JsonTree;
+    use crate::lib::validation::{Diagnostic, Severity, validate_tree};
+
+    #[test]
+    fn duplicate_and_empty_names() {
+        let tree = vec![
+            JsonTree::Int("a".to_owned()),
+            JsonTree::Bool("".to_owned()),
+            JsonTree::Float("a".to_owned()),
+        ];
+        let expected_result = vec![
+            Diagnostic { severity: Severity::Error, path: "".to_owned(), message: "empty field name".to_owned() },
+            Diagnostic { severity: Severity::Error, path: "a".to_owned(), message: "duplicate field a".to_owned() },
+        ];
+
+        assert_eq!(validate_tree(&tree, &RUST_DEFINITION, None), expected_result);
+    }
+
+    #[test]
+    fn type_name_collisions() {
+        let tree = vec![
+            JsonTree::JsonObject("user".to_owned(), vec![JsonTree::Int("id".to_owned())]),
+            JsonTree::JsonObject("group".to_owned(), vec![
+                JsonTree::JsonObject("user".to_owned(), vec![JsonTree::Int("id".to_owned())]),
+            ]),
+        ];
+        let diagnostics = validate_tree(&tree, &RUST_DEFINITION, None);
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].severity, Severity::Warning);
+        assert_eq!(diagnostics[0].to_string(), "warning: group.user: type name User is already given to user, a number will be appended unless both have the same fields");
+    }
+
+    #[test]
+    fn converted_name_collisions() {
+        let tree = vec![
+            JsonTree::Int("userID".to_owned()),
+            JsonTree::Int("user_id".to_owned()),
+        ];
+        let diagnostics = validate_tree(&tree, &RUST_DEFINITION, None);
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].to_string(), "error: user_id: fields userID and user_id are both named user_id");
+        // Camel case keeps them apart.
+        assert!(validate_tree(&tree, &JAVA_DEFINITION, None).is_empty());
+    }
+
+    #[test]
+    fn root_name_collision() {
+        let tree = vec![JsonTree::JsonObject("order".to_owned(), vec![JsonTree::Int("id".to_owned())])];
+        let diagnostics = validate_tree(&tree, &RUST_DEFINITION, Some("Order"));
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].to_string(), "warning: order: type name Order is already given to (root), a number will be appended unless both have the same fields");
+        assert!(validate_tree(&tree, &RUST_DEFINITION, None).is_empty());
+    }
+}